@@ -0,0 +1,121 @@
+use serde::Deserialize;
+
+/// What the UI calls an agent (sidebar row, input title, flash messages)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentLabel {
+    /// Working directory, shortened with the configured path style
+    #[default]
+    Path,
+    /// Tmux target, e.g. `main:0.1`
+    Target,
+    /// Tmux window name
+    WindowName,
+    /// Custom template; see [`AgentLabel::PLACEHOLDERS`]
+    Template(String),
+}
+
+impl AgentLabel {
+    /// Placeholders understood by [`AgentLabel::Template`]
+    pub const PLACEHOLDERS: &'static [&'static str] = &[
+        "{path}",
+        "{dir}",
+        "{target}",
+        "{session}",
+        "{window}",
+        "{window_name}",
+        "{pane}",
+        "{agent}",
+    ];
+}
+
+/// Values substituted into a label template
+pub struct LabelFields<'a> {
+    pub path: &'a str,
+    pub dir: &'a str,
+    pub target: &'a str,
+    pub session: &'a str,
+    pub window: u32,
+    pub window_name: &'a str,
+    pub pane: u32,
+    pub agent: &'a str,
+}
+
+/// Replaces the known placeholders in `template`; unknown ones are left as they are
+pub fn render_template(template: &str, fields: &LabelFields) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let Some(end) = tail.find('}') else {
+            break;
+        };
+        let value = match &tail[..=end] {
+            "{path}" => Some(fields.path.to_string()),
+            "{dir}" => Some(fields.dir.to_string()),
+            "{target}" => Some(fields.target.to_string()),
+            "{session}" => Some(fields.session.to_string()),
+            "{window}" => Some(fields.window.to_string()),
+            "{window_name}" => Some(fields.window_name.to_string()),
+            "{pane}" => Some(fields.pane.to_string()),
+            "{agent}" => Some(fields.agent.to_string()),
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                out.push_str(&value);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> LabelFields<'static> {
+        LabelFields {
+            path: "/h/u/tmuxcc",
+            dir: "tmuxcc",
+            target: "main:1.2",
+            session: "main",
+            window: 1,
+            window_name: "code",
+            pane: 2,
+            agent: "Claude",
+        }
+    }
+
+    #[test]
+    fn test_template_substitutes_every_placeholder() {
+        let template = AgentLabel::PLACEHOLDERS.join(" ");
+        assert_eq!(
+            render_template(&template, &fields()),
+            "/h/u/tmuxcc tmuxcc main:1.2 main 1 code 2 Claude"
+        );
+    }
+
+    #[test]
+    fn test_template_mixed_text() {
+        assert_eq!(
+            render_template("{agent}@{session}:{window_name} [{dir}]", &fields()),
+            "Claude@main:code [tmuxcc]"
+        );
+    }
+
+    #[test]
+    fn test_template_keeps_unknown_and_unclosed_braces() {
+        assert_eq!(render_template("{nope} {dir}", &fields()), "{nope} tmuxcc");
+        assert_eq!(render_template("{{dir}}", &fields()), "{tmuxcc}");
+        assert_eq!(render_template("{dir", &fields()), "{dir");
+        assert_eq!(render_template("", &fields()), "");
+    }
+}
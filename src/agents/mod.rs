@@ -1,5 +1,11 @@
+mod label;
+mod path_display;
 mod subagent;
 mod types;
 
+pub use label::{render_template, AgentLabel, LabelFields};
+pub use path_display::PathDisplay;
 pub use subagent::{Subagent, SubagentStatus, SubagentType};
-pub use types::{AgentStatus, AgentType, ApprovalType, MonitoredAgent};
+pub use types::{
+    format_elapsed, AgentStatus, AgentType, ApprovalType, MonitoredAgent, DEFAULT_STALE_AFTER_SECS,
+};
@@ -0,0 +1,121 @@
+use serde::Deserialize;
+
+/// How working directories are shortened for display
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PathDisplay {
+    /// First letter of every component but the last: `/h/u/p/tmuxcc`
+    #[default]
+    FirstLetter,
+    /// Only the last N components: `…/projects/tmuxcc`
+    KeepLast(usize),
+    /// Full path with `$HOME` replaced by `~`: `~/projects/tmuxcc`
+    HomeTilde,
+}
+
+impl PathDisplay {
+    /// Formats `path` using this strategy
+    pub fn format(self, path: &str) -> String {
+        let home = std::env::var("HOME").ok();
+        self.format_with_home(path, home.as_deref())
+    }
+
+    /// Formats `path` using this strategy, with an explicit home directory
+    pub fn format_with_home(self, path: &str, home: Option<&str>) -> String {
+        if path.is_empty() {
+            return "~".to_string();
+        }
+
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        if parts.is_empty() {
+            return "/".to_string();
+        }
+
+        match self {
+            PathDisplay::FirstLetter => {
+                if parts.len() == 1 {
+                    return format!("/{}", parts[0]);
+                }
+
+                // Abbreviate all but the last component
+                let abbreviated: Vec<String> = parts[..parts.len() - 1]
+                    .iter()
+                    .map(|s| s.chars().next().unwrap_or('?').to_string())
+                    .collect();
+
+                format!("/{}/{}", abbreviated.join("/"), parts.last().unwrap())
+            }
+            PathDisplay::KeepLast(n) => {
+                let n = n.max(1);
+                if parts.len() <= n {
+                    format!("/{}", parts.join("/"))
+                } else {
+                    format!("…/{}", parts[parts.len() - n..].join("/"))
+                }
+            }
+            PathDisplay::HomeTilde => {
+                let home = home.map(|h| h.trim_end_matches('/')).filter(|h| !h.is_empty());
+                match home.and_then(|h| path.strip_prefix(h)) {
+                    Some("") => "~".to_string(),
+                    Some(rest) if rest.starts_with('/') => format!("~{}", rest),
+                    _ => path.to_string(),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "/home/user/projects/tmuxcc";
+    const HOME: Option<&str> = Some("/home/user");
+
+    #[test]
+    fn test_first_letter() {
+        assert_eq!(
+            PathDisplay::FirstLetter.format_with_home(PATH, HOME),
+            "/h/u/p/tmuxcc"
+        );
+        assert_eq!(PathDisplay::FirstLetter.format_with_home("/tmp", HOME), "/tmp");
+    }
+
+    #[test]
+    fn test_keep_last_components() {
+        assert_eq!(
+            PathDisplay::KeepLast(2).format_with_home(PATH, HOME),
+            "…/projects/tmuxcc"
+        );
+        assert_eq!(PathDisplay::KeepLast(1).format_with_home(PATH, HOME), "…/tmuxcc");
+        assert_eq!(PathDisplay::KeepLast(4).format_with_home(PATH, HOME), PATH);
+        assert_eq!(PathDisplay::KeepLast(0).format_with_home(PATH, HOME), "…/tmuxcc");
+    }
+
+    #[test]
+    fn test_home_tilde() {
+        assert_eq!(
+            PathDisplay::HomeTilde.format_with_home(PATH, HOME),
+            "~/projects/tmuxcc"
+        );
+        assert_eq!(PathDisplay::HomeTilde.format_with_home("/home/user", HOME), "~");
+        // Only whole components match
+        assert_eq!(
+            PathDisplay::HomeTilde.format_with_home("/home/username/x", HOME),
+            "/home/username/x"
+        );
+        assert_eq!(PathDisplay::HomeTilde.format_with_home(PATH, None), PATH);
+    }
+
+    #[test]
+    fn test_empty_and_root() {
+        for style in [
+            PathDisplay::FirstLetter,
+            PathDisplay::KeepLast(2),
+            PathDisplay::HomeTilde,
+        ] {
+            assert_eq!(style.format_with_home("", HOME), "~");
+            assert_eq!(style.format_with_home("/", HOME), "/");
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Local};
+use serde::Serialize;
 use std::fmt;
 
 /// Types of subagents that can be spawned
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SubagentType {
     Explore,
     Plan,
@@ -45,7 +47,8 @@ impl fmt::Display for SubagentType {
 }
 
 /// Status of a subagent
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SubagentStatus {
     Running,
     Completed,
@@ -78,7 +81,7 @@ impl fmt::Display for SubagentStatus {
 }
 
 /// Represents a subagent spawned by a parent agent
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Subagent {
     /// Unique identifier
     pub id: String,
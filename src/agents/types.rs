@@ -1,15 +1,27 @@
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
-use std::time::Instant;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
 
+use serde::{Serialize, Serializer};
+
+use super::label::{render_template, AgentLabel, LabelFields};
+use super::path_display::PathDisplay;
 use super::subagent::Subagent;
 
+/// Default for how long a working agent's pane may sit unchanged before it is marked stale
+pub const DEFAULT_STALE_AFTER_SECS: u64 = 600;
+
 /// Types of AI agents that can be monitored
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AgentType {
     ClaudeCode,
     OpenCode,
     CodexCli,
     GeminiCli,
+    /// A `[[custom_agents]]` agent, by its configured name
+    Custom(String),
     Unknown,
 }
 
@@ -21,6 +33,7 @@ impl AgentType {
             AgentType::OpenCode => "OpenCode",
             AgentType::CodexCli => "Codex CLI",
             AgentType::GeminiCli => "Gemini CLI",
+            AgentType::Custom(name) => name,
             AgentType::Unknown => "Unknown",
         }
     }
@@ -32,6 +45,7 @@ impl AgentType {
             AgentType::OpenCode => "Open",
             AgentType::CodexCli => "Codex",
             AgentType::GeminiCli => "Gemini",
+            AgentType::Custom(name) => name,
             AgentType::Unknown => "???",
         }
     }
@@ -44,7 +58,8 @@ impl fmt::Display for AgentType {
 }
 
 /// Types of approvals that agents may request
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ApprovalType {
     FileEdit,
     FileCreate,
@@ -111,7 +126,8 @@ impl fmt::Display for ApprovalType {
 }
 
 /// Status of an AI agent
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
 pub enum AgentStatus {
     /// Agent is idle and ready for input
     Idle,
@@ -175,9 +191,9 @@ impl fmt::Display for AgentStatus {
 }
 
 /// Represents a monitored AI agent in a tmux pane
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MonitoredAgent {
-    /// Unique identifier for this agent
+    /// Unique identifier for this agent (stable across pane respawns)
     pub id: String,
     /// Tmux target (e.g., "main:0.1")
     pub target: String,
@@ -198,17 +214,33 @@ pub struct MonitoredAgent {
     /// Detected subagents
     pub subagents: Vec<Subagent>,
     /// Last captured pane content
+    #[serde(skip)]
     pub last_content: String,
     /// Process ID
     pub pid: u32,
     /// When this agent was first detected
+    #[serde(rename = "uptime_secs", serialize_with = "serialize_elapsed_secs")]
     pub started_at: Instant,
     /// When the pane content was last updated
+    #[serde(rename = "updated_secs_ago", serialize_with = "serialize_elapsed_secs")]
     pub last_updated: Instant,
+    /// Hash of `last_content`, compared across polls to spot real changes
+    #[serde(skip)]
+    pub content_hash: u64,
+    /// When the pane content last differed from the previous poll
+    #[serde(rename = "unchanged_secs", serialize_with = "serialize_elapsed_secs")]
+    pub content_changed_at: Instant,
     /// Context remaining percentage (0-100), if detectable
     pub context_remaining: Option<u8>,
+    /// Model the agent runs on, e.g. "opus 4.1", if detectable
+    pub model: Option<String>,
     /// Git branch name (from hub API or git)
     pub branch: Option<String>,
+    /// How often the process in this pane was replaced (e.g. `respawn-pane`)
+    pub restart_count: u32,
+    /// When the current approval prompt first appeared; None unless awaiting approval
+    #[serde(rename = "awaiting_secs", serialize_with = "serialize_elapsed_secs_opt")]
+    pub awaiting_since: Option<Instant>,
 }
 
 impl MonitoredAgent {
@@ -241,11 +273,35 @@ impl MonitoredAgent {
             pid,
             started_at: now,
             last_updated: now,
+            content_hash: 0,
+            content_changed_at: now,
             context_remaining: None,
+            model: None,
             branch: None,
+            restart_count: 0,
+            awaiting_since: None,
         }
     }
 
+    /// A Claude Code agent in `/home/user/project` for tests. Session, window and
+    /// pane come from `target` (`session:window.pane`), which is also the id.
+    #[cfg(test)]
+    pub fn for_test(target: &str) -> Self {
+        let (session, location) = target.split_once(':').unwrap_or((target, ""));
+        let (window, pane) = location.split_once('.').unwrap_or((location, ""));
+        Self::new(
+            target.to_string(),
+            target.to_string(),
+            session.to_string(),
+            window.parse().unwrap_or(0),
+            "code".to_string(),
+            pane.parse().unwrap_or(0),
+            "/home/user/project".to_string(),
+            AgentType::ClaudeCode,
+            1,
+        )
+    }
+
     /// Returns the duration since this agent was first detected
     pub fn uptime(&self) -> std::time::Duration {
         self.started_at.elapsed()
@@ -253,14 +309,54 @@ impl MonitoredAgent {
 
     /// Returns a human-readable uptime string
     pub fn uptime_str(&self) -> String {
-        let secs = self.uptime().as_secs();
-        if secs < 60 {
-            format!("{}s", secs)
-        } else if secs < 3600 {
-            format!("{}m", secs / 60)
+        format_elapsed(self.uptime().as_secs())
+    }
+
+    /// Returns how long the agent has been waiting for approval, e.g. "waiting 2m"
+    pub fn awaiting_str(&self) -> Option<String> {
+        let since = self.awaiting_since?;
+        Some(format!("waiting {}", format_elapsed(since.elapsed().as_secs())))
+    }
+
+    /// Sets `awaiting_since` from the current status: kept from `previous` while the
+    /// same pane stays in AwaitingApproval, started now when it just began, cleared
+    /// otherwise
+    pub fn track_awaiting(&mut self, previous: Option<&MonitoredAgent>) {
+        self.awaiting_since = if matches!(self.status, AgentStatus::AwaitingApproval { .. }) {
+            Some(
+                previous
+                    .and_then(|p| p.awaiting_since)
+                    .unwrap_or_else(Instant::now),
+            )
         } else {
-            format!("{}h{}m", secs / 3600, (secs % 3600) / 60)
+            None
+        };
+    }
+
+    /// Hashes `last_content` and keeps `content_changed_at` from `previous` when the
+    /// content is the same as on its poll, or restarts it now when it changed
+    pub fn track_content_change(&mut self, previous: Option<&MonitoredAgent>) {
+        let mut hasher = DefaultHasher::new();
+        self.last_content.hash(&mut hasher);
+        self.content_hash = hasher.finish();
+        self.content_changed_at = match previous {
+            Some(p) if p.content_hash == self.content_hash => p.content_changed_at,
+            _ => Instant::now(),
+        };
+    }
+
+    /// Returns e.g. "stale 12m" when the agent claims to be working but its pane
+    /// hasn't changed for `threshold`. Idle panes are expected to sit still, and a
+    /// zero threshold turns the marker off.
+    pub fn stale_str(&self, threshold: Duration) -> Option<String> {
+        let unchanged = self.content_changed_at.elapsed();
+        if threshold.is_zero()
+            || unchanged < threshold
+            || !matches!(self.status, AgentStatus::Processing { .. })
+        {
+            return None;
         }
+        Some(format!("stale {}", format_elapsed(unchanged.as_secs())))
     }
 
     /// Returns a human-readable last updated string
@@ -280,6 +376,23 @@ impl MonitoredAgent {
         self.last_updated = Instant::now();
     }
 
+    /// Carries identity-bound state over from the previous poll's agent in the same pane.
+    ///
+    /// A pane keeps its logical agent across a PID change, so uptime survives a respawn;
+    /// the change is counted instead. Returns true if the PID changed.
+    pub fn carry_over_from(&mut self, previous: &MonitoredAgent) -> bool {
+        if previous.target != self.target || previous.agent_type != self.agent_type {
+            return false;
+        }
+        self.started_at = previous.started_at;
+        self.restart_count = previous.restart_count;
+        let restarted = previous.pid != self.pid;
+        if restarted {
+            self.restart_count += 1;
+        }
+        restarted
+    }
+
     /// Returns a short path (last component or abbreviated)
     pub fn short_path(&self) -> String {
         if self.path.is_empty() {
@@ -295,26 +408,57 @@ impl MonitoredAgent {
 
     /// Returns an abbreviated path like /U/p/P/project
     pub fn abbreviated_path(&self) -> String {
-        if self.path.is_empty() {
-            return "~".to_string();
-        }
+        PathDisplay::FirstLetter.format(&self.path)
+    }
 
-        let parts: Vec<&str> = self.path.split('/').filter(|s| !s.is_empty()).collect();
-        if parts.is_empty() {
-            return "/".to_string();
+    /// Returns the full path if `full` is set, otherwise the path shortened with `style`
+    pub fn display_path(&self, full: bool, style: PathDisplay) -> String {
+        if full && !self.path.is_empty() {
+            self.path.clone()
+        } else {
+            style.format(&self.path)
         }
+    }
 
-        if parts.len() == 1 {
-            return format!("/{}", parts[0]);
+    /// Returns the label the UI shows for this agent; `path` is the already formatted
+    /// working directory (see [`MonitoredAgent::display_path`])
+    pub fn display_label(&self, label: &AgentLabel, path: &str) -> String {
+        match label {
+            AgentLabel::Path => path.to_string(),
+            AgentLabel::Target => self.target.clone(),
+            AgentLabel::WindowName => self.window_name.clone(),
+            AgentLabel::Template(template) => render_template(
+                template,
+                &LabelFields {
+                    path,
+                    dir: &self.short_path(),
+                    target: &self.target,
+                    session: &self.session,
+                    window: self.window,
+                    window_name: &self.window_name,
+                    pane: self.pane,
+                    agent: self.agent_type.short_name(),
+                },
+            ),
         }
+    }
 
-        // Abbreviate all but the last component
-        let abbreviated: Vec<String> = parts[..parts.len() - 1]
-            .iter()
-            .map(|s| s.chars().next().unwrap_or('?').to_string())
-            .collect();
-
-        format!("/{}/{}", abbreviated.join("/"), parts.last().unwrap())
+    /// Expands label placeholders in text about to be sent to this agent.
+    /// Unlike in labels, `{path}` is the full working directory here.
+    pub fn expand_placeholders(&self, text: &str) -> String {
+        render_template(
+            text,
+            &LabelFields {
+                path: &self.path,
+                dir: &self.short_path(),
+                target: &self.target,
+                session: &self.session,
+                window: self.window,
+                window_name: &self.window_name,
+                pane: self.pane,
+                agent: self.agent_type.short_name(),
+            },
+        )
     }
 
     /// Returns the number of active subagents
@@ -332,6 +476,35 @@ impl MonitoredAgent {
     }
 }
 
+/// Serializes an `Instant` as whole seconds elapsed since it
+fn serialize_elapsed_secs<S: Serializer>(
+    instant: &Instant,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(instant.elapsed().as_secs())
+}
+
+fn serialize_elapsed_secs_opt<S: Serializer>(
+    instant: &Option<Instant>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match instant {
+        Some(instant) => serializer.serialize_some(&instant.elapsed().as_secs()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Formats elapsed seconds compactly: "45s", "12m", "3h5m"
+pub fn format_elapsed(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h{}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,4 +550,133 @@ mod tests {
         assert_eq!(agent.active_subagent_count(), 0);
         assert_eq!(agent.short_path(), "project");
     }
+
+    #[test]
+    fn test_display_label() {
+        let agent = MonitoredAgent::for_test("main:0.1");
+        let path = agent.abbreviated_path();
+        assert_eq!(agent.display_label(&AgentLabel::Path, &path), "/h/u/project");
+        assert_eq!(agent.display_label(&AgentLabel::Target, &path), "main:0.1");
+        assert_eq!(agent.display_label(&AgentLabel::WindowName, &path), "code");
+        let template = AgentLabel::Template("{window_name}/{dir} ({target})".to_string());
+        assert_eq!(agent.display_label(&template, &path), "code/project (main:0.1)");
+    }
+
+    #[test]
+    fn test_identity_survives_pid_change() {
+        let agent = |pid| MonitoredAgent {
+            pid,
+            ..MonitoredAgent::for_test("main:0.1")
+        };
+        let first = agent(100);
+
+        let mut same = agent(100);
+        assert!(!same.carry_over_from(&first));
+        assert_eq!(same.started_at, first.started_at);
+        assert_eq!(same.restart_count, 0);
+
+        let mut respawned = agent(200);
+        assert!(respawned.carry_over_from(&same));
+        assert_eq!(respawned.id, first.id);
+        assert_eq!(respawned.started_at, first.started_at);
+        assert_eq!(respawned.restart_count, 1);
+
+        // A different agent in the same pane starts fresh
+        let mut other = agent(300);
+        other.agent_type = AgentType::CodexCli;
+        assert!(!other.carry_over_from(&respawned));
+        assert_eq!(other.restart_count, 0);
+    }
+
+    #[test]
+    fn test_awaiting_since_tracks_approval() {
+        let approval = || AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::FileEdit,
+            details: "src/main.rs".to_string(),
+        };
+        let mut first = MonitoredAgent::for_test("main:0.1");
+        first.track_awaiting(None);
+        assert_eq!(first.awaiting_since, None);
+        assert_eq!(first.awaiting_str(), None);
+
+        // Prompt appears
+        let mut waiting = first.clone();
+        waiting.status = approval();
+        waiting.track_awaiting(Some(&first));
+        let since = waiting.awaiting_since.expect("set when approval appears");
+        assert_eq!(waiting.awaiting_str().as_deref(), Some("waiting 0s"));
+
+        // Still waiting on the next poll: the original time is kept
+        let mut still = waiting.clone();
+        still.awaiting_since = None;
+        still.track_awaiting(Some(&waiting));
+        assert_eq!(still.awaiting_since, Some(since));
+
+        // Answered
+        let mut answered = still.clone();
+        answered.status = AgentStatus::Idle;
+        answered.track_awaiting(Some(&still));
+        assert_eq!(answered.awaiting_since, None);
+    }
+
+    #[test]
+    fn test_content_change_tracking_and_stale() {
+        let mut first = MonitoredAgent::for_test("main:0.1");
+        first.status = AgentStatus::Processing {
+            activity: "Working...".to_string(),
+        };
+        first.last_content = "✻ Thinking… (12s · esc to interrupt)".to_string();
+        first.track_content_change(None);
+        // Pretend the content has sat still for a while
+        let long_ago = Instant::now() - Duration::from_secs(15 * 60);
+        first.content_changed_at = long_ago;
+
+        // Same content on the next poll: the old timestamp is kept
+        let mut same = first.clone();
+        same.content_changed_at = Instant::now();
+        same.track_content_change(Some(&first));
+        assert_eq!(same.content_changed_at, long_ago);
+        assert_eq!(
+            same.stale_str(Duration::from_secs(600)).as_deref(),
+            Some("stale 15m")
+        );
+        assert_eq!(same.stale_str(Duration::ZERO), None);
+
+        // Only a working agent is stale
+        let mut idle = same.clone();
+        idle.status = AgentStatus::Idle;
+        assert_eq!(idle.stale_str(Duration::from_secs(600)), None);
+
+        // New content restarts the clock
+        let mut changed = same.clone();
+        changed.last_content = "✻ Thinking… (13s · esc to interrupt)".to_string();
+        changed.track_content_change(Some(&same));
+        assert!(changed.content_changed_at > long_ago);
+        assert_eq!(changed.stale_str(Duration::from_secs(600)), None);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(42), "42s");
+        assert_eq!(format_elapsed(150), "2m");
+        assert_eq!(format_elapsed(3 * 3600 + 5 * 60), "3h5m");
+    }
+
+    #[test]
+    fn test_agent_serializes_to_json() {
+        let mut agent = MonitoredAgent::for_test("main:0.1");
+        agent.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "cargo test".to_string(),
+        };
+        agent.last_content = "lots of scrollback".to_string();
+
+        let json = serde_json::to_value(&agent).unwrap();
+        assert_eq!(json["target"], "main:0.1");
+        assert_eq!(json["agent_type"], "claude_code");
+        assert_eq!(json["status"]["state"], "awaiting_approval");
+        assert_eq!(json["status"]["approval_type"], "shell_command");
+        assert_eq!(json["uptime_secs"], 0);
+        assert!(json.get("last_content").is_none());
+    }
 }
@@ -3,17 +3,18 @@
 use std::collections::HashMap;
 
 use chrono::{Local, NaiveDate};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use crate::agents::{AgentStatus, AgentType, MonitoredAgent};
-use crate::state_reader::{
-    ActivityEntry, AutoCycleConfig, BoardData, CapacityData, DashboardData, McpServer,
-    MilestoneData, MultiAgentEntry, ProcessData, SessionData, SprintData,
-};
+use crate::state_reader::{BoardData, DashboardData, McpServer, MultiAgentEntry, SprintData};
 
 const DEFAULT_API_URL: &str = "http://localhost:3100";
 
+/// Default per-request timeout for hub API calls
+pub const DEFAULT_TIMEOUT_MS: u64 = 2000;
+
 // =============================================================================
 // Hub API response types (match hub_mcp JSON exactly)
 // =============================================================================
@@ -55,6 +56,9 @@ pub struct HubPane {
     pub space: Option<String>,
     #[serde(default)]
     pub issue_id: Option<String>,
+    /// `session:window.pane` of the tmux pane the hub runs this pane in, if any
+    #[serde(default)]
+    pub tmux_target: Option<String>,
 }
 
 /// Queue task from /api/queue endpoint
@@ -204,35 +208,6 @@ struct QueueResponse {
     tasks: Vec<QueueTask>,
 }
 
-/// Aggregate /api/dashboard response
-#[derive(Debug, Deserialize, Default)]
-struct DashboardApiResponse {
-    #[serde(default)]
-    capacity: CapacityData,
-    #[serde(default)]
-    sprints: Vec<Value>,
-    #[serde(default)]
-    board_summary: Vec<BoardSummaryEntry>,
-    #[serde(default)]
-    mcps: Vec<ApiMcpEntry>,
-    #[serde(default)]
-    activity: Vec<ActivityEntry>,
-    #[serde(default)]
-    auto_config: AutoCycleConfig,
-    #[serde(default)]
-    session: SessionData,
-    #[serde(default)]
-    milestones: Vec<MilestoneData>,
-    #[serde(default)]
-    processes: Vec<ProcessData>,
-    #[serde(default)]
-    agents: Vec<ApiMultiAgentEntry>,
-    #[serde(default)]
-    digest: AnalyticsDigest,
-    #[serde(default)]
-    alerts: AlertsResponse,
-}
-
 #[derive(Debug, Deserialize, Default)]
 struct BoardSummaryEntry {
     #[serde(default)]
@@ -271,6 +246,31 @@ pub struct FullDashboardResult {
     pub alerts: AlertsResponse,
 }
 
+/// The queue endpoints report failures as a 200 with an `{"error": ...}` body
+fn check_queue_reply(resp: &Value) -> anyhow::Result<()> {
+    match resp.get("error").and_then(Value::as_str) {
+        Some(error) => Err(anyhow::anyhow!("{}", error)),
+        None => Ok(()),
+    }
+}
+
+/// Takes section `key` out of the /api/dashboard response. A missing or null section
+/// is the default; one that is present but doesn't deserialize is the default too,
+/// with the error recorded so the dashboard can say why a panel is blank.
+fn take_section<T: DeserializeOwned + Default>(
+    resp: &mut Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> T {
+    match resp.remove(key) {
+        None | Some(Value::Null) => T::default(),
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            errors.push(format!("{}: {}", key, e));
+            T::default()
+        }),
+    }
+}
+
 // =============================================================================
 // Hub Client
 // =============================================================================
@@ -281,11 +281,13 @@ pub struct HubClient {
 }
 
 impl HubClient {
-    pub fn new(api_url: Option<String>) -> Self {
+    /// Creates a client for `api_url` (or the local default) whose requests give up
+    /// after `timeout`; a slow hub past that is reported as disconnected.
+    pub fn new(api_url: Option<String>, timeout: std::time::Duration) -> Self {
         Self {
             api_url: api_url.unwrap_or_else(|| DEFAULT_API_URL.to_string()),
             client: reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(2))
+                .timeout(timeout)
                 .build()
                 .expect("failed to create HTTP client"),
         }
@@ -322,23 +324,35 @@ impl HubClient {
     /// Fetch ALL dashboard data + analytics in one HTTP call
     pub async fn fetch_dashboard(&self) -> anyhow::Result<FullDashboardResult> {
         let url = format!("{}/api/dashboard", self.api_url);
-        let resp: DashboardApiResponse = self.client.get(&url).send().await?.json().await?;
+        let resp: Value = self.client.get(&url).send().await?.json().await?;
+        let Value::Object(resp) = resp else {
+            anyhow::bail!("/api/dashboard did not return a JSON object");
+        };
+        Ok(Self::parse_dashboard(resp))
+    }
+
+    /// Builds the dashboard section by section, so one malformed section blanks only
+    /// its own panel and is reported in `DashboardData::parse_errors`
+    fn parse_dashboard(mut resp: Map<String, Value>) -> FullDashboardResult {
+        let mut errors = Vec::new();
 
         // Convert sprints → SprintData
-        let sprint = Self::parse_sprint(&resp.sprints);
+        let sprints: Vec<Value> = take_section(&mut resp, "sprints", &mut errors);
+        let sprint = Self::parse_sprint(&sprints);
 
         // Convert board_summary → BoardData
+        let board_summary: Vec<BoardSummaryEntry> =
+            take_section(&mut resp, "board_summary", &mut errors);
         let board = BoardData {
-            spaces: resp
-                .board_summary
+            spaces: board_summary
                 .into_iter()
                 .map(|b| (b.name, b.counts))
                 .collect(),
         };
 
         // Convert MCPs
-        let mcps = resp
-            .mcps
+        let mcps: Vec<ApiMcpEntry> = take_section(&mut resp, "mcps", &mut errors);
+        let mcps = mcps
             .into_iter()
             .map(|m| McpServer {
                 name: m.name,
@@ -348,8 +362,8 @@ impl HubClient {
             .collect();
 
         // Convert agents
-        let multi_agent = resp
-            .agents
+        let agents: Vec<ApiMultiAgentEntry> = take_section(&mut resp, "agents", &mut errors);
+        let multi_agent = agents
             .into_iter()
             .map(|a| MultiAgentEntry {
                 pane_id: a.pane,
@@ -359,22 +373,32 @@ impl HubClient {
             })
             .collect();
 
-        Ok(FullDashboardResult {
+        let capacity = take_section(&mut resp, "capacity", &mut errors);
+        let activity = take_section(&mut resp, "activity", &mut errors);
+        let auto_config = take_section(&mut resp, "auto_config", &mut errors);
+        let session = take_section(&mut resp, "session", &mut errors);
+        let milestones = take_section(&mut resp, "milestones", &mut errors);
+        let processes = take_section(&mut resp, "processes", &mut errors);
+        let digest = take_section(&mut resp, "digest", &mut errors);
+        let alerts = take_section(&mut resp, "alerts", &mut errors);
+
+        FullDashboardResult {
             dashboard: DashboardData {
-                capacity: resp.capacity,
+                capacity,
                 sprint,
                 board,
                 mcps,
-                activity: resp.activity,
-                auto_config: resp.auto_config,
-                session: resp.session,
+                activity,
+                auto_config,
+                session,
                 multi_agent,
-                milestones: resp.milestones,
-                processes: resp.processes,
+                milestones,
+                processes,
+                parse_errors: errors,
             },
-            digest: resp.digest,
-            alerts: resp.alerts,
-        })
+            digest,
+            alerts,
+        }
     }
 
     /// Parse raw sprint JSON into SprintData (picks active or latest)
@@ -472,6 +496,36 @@ impl HubClient {
         Ok(resp)
     }
 
+    /// Cancel queue task `id` by removing it from the queue
+    pub async fn cancel_task(&self, id: &str) -> anyhow::Result<()> {
+        self.post_queue("delete", serde_json::json!({ "task_id": id }))
+            .await
+    }
+
+    /// Set the priority of queue task `id` (1 runs first)
+    pub async fn set_priority(&self, id: &str, priority: u8) -> anyhow::Result<()> {
+        self.post_queue(
+            "priority",
+            serde_json::json!({ "task_id": id, "priority": priority }),
+        )
+        .await
+    }
+
+    /// POST `body` to `/api/queue/{action}`
+    async fn post_queue(&self, action: &str, body: Value) -> anyhow::Result<()> {
+        let url = format!("{}/api/queue/{}", self.api_url, action);
+        let resp: Value = self
+            .client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        check_queue_reply(&resp)
+    }
+
     /// Fetch factory pipeline status
     pub async fn fetch_factory_status(&self) -> anyhow::Result<Vec<FactoryRequest>> {
         let url = format!("{}/api/factory/status", self.api_url);
@@ -499,7 +553,9 @@ impl HubClient {
 
         let mut agent = MonitoredAgent::new(
             format!("dx-{}", pane.pane),
-            format!("dx:{}:{}", pane.pane, pane.theme.to_lowercase()),
+            pane.tmux_target
+                .clone()
+                .unwrap_or_else(|| format!("dx:{}:{}", pane.pane, pane.theme.to_lowercase())),
             session_name,
             0,
             window_name,
@@ -517,3 +573,65 @@ impl HubClient {
         agent
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: Value) -> FullDashboardResult {
+        let Value::Object(resp) = json else {
+            panic!("test response must be an object");
+        };
+        HubClient::parse_dashboard(resp)
+    }
+
+    #[test]
+    fn test_parse_dashboard_absent_sections_default_silently() {
+        let result = parse(serde_json::json!({
+            "mcps": [{"name": "hub", "tools": 12, "is_rust": true}],
+            "session": null,
+        }));
+        assert_eq!(result.dashboard.mcps.len(), 1);
+        assert!(result.dashboard.session.current_task.is_empty());
+        assert!(result.dashboard.parse_errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_dashboard_reports_malformed_sections() {
+        let result = parse(serde_json::json!({
+            "mcps": [{"name": "hub", "tools": 12, "is_rust": true}],
+            "session": "oops",
+            "milestones": {"not": "a list"},
+        }));
+        // Good sections still come through
+        assert_eq!(result.dashboard.mcps.len(), 1);
+        assert!(result.dashboard.milestones.is_empty());
+
+        let errors = &result.dashboard.parse_errors;
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("session: "));
+        assert!(errors[1].starts_with("milestones: "));
+    }
+
+    #[test]
+    fn test_check_queue_reply_reads_error_body() {
+        let deleted = serde_json::json!({"status": "deleted", "task_id": "t1"});
+        assert!(check_queue_reply(&deleted).is_ok());
+        let missing = serde_json::json!({"error": "task not found"});
+        let err = check_queue_reply(&missing).unwrap_err();
+        assert_eq!(err.to_string(), "task not found");
+    }
+
+    #[test]
+    fn test_pane_to_agent_uses_tmux_target() {
+        let pane: HubPane = serde_json::from_value(serde_json::json!({
+            "pane": 3, "theme": "Blue", "project": "api", "tmux_target": "main:0.1",
+        }))
+        .unwrap();
+        assert_eq!(HubClient::pane_to_agent(&pane).target, "main:0.1");
+
+        let pane: HubPane =
+            serde_json::from_value(serde_json::json!({"pane": 3, "theme": "Blue"})).unwrap();
+        assert_eq!(HubClient::pane_to_agent(&pane).target, "dx:3:blue");
+    }
+}
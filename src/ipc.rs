@@ -2,13 +2,14 @@ use std::fs::OpenOptions;
 use std::io;
 use std::os::unix::io::AsRawFd;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::sync::watch;
 use tokio::time::MissedTickBehavior;
 
@@ -27,6 +28,7 @@ const VISION_RUNTIME_HEARTBEAT_MS: u64 = 5_000;
 const VISION_RUNTIME_STALE_MS: u64 = 15_000;
 const VISION_REPLAY_MAX_AGE_MS: u64 = 30_000;
 const VISION_REPLAY_MAX_COUNT: usize = 256;
+const TRANSITION_BUFFER: usize = 256;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ReplayEnvelope {
@@ -42,6 +44,10 @@ struct RuntimeRegistration {
     last_seen_ms: u64,
 }
 
+/// Agent status transitions published by the monitor, streamed to clients that
+/// connect with `{"subscribe":"transitions"}`
+static TRANSITIONS: OnceLock<broadcast::Sender<Value>> = OnceLock::new();
+
 pub struct LocalIpcGuard {
     runtime_id: String,
     stop_tx: watch::Sender<bool>,
@@ -205,6 +211,9 @@ async fn handle_connection(
     }
 
     let payload: Value = serde_json::from_slice(&buf)?;
+    if payload.get("subscribe").and_then(|v| v.as_str()) == Some("transitions") {
+        return stream_transitions(&mut stream, transitions().subscribe()).await;
+    }
     let project_path = payload
         .get("project_path")
         .or_else(|| payload.get("path"))
@@ -225,6 +234,41 @@ async fn handle_connection(
     Ok(())
 }
 
+fn transitions() -> &'static broadcast::Sender<Value> {
+    TRANSITIONS.get_or_init(|| broadcast::channel(TRANSITION_BUFFER).0)
+}
+
+/// Sends an agent status transition (`{target, from, to, at}`) to every
+/// client subscribed on the local IPC socket
+#[allow(dead_code)]
+pub fn publish_transition(transition: &impl Serialize) {
+    if let Ok(value) = serde_json::to_value(transition) {
+        // No subscribers is the common case, not an error
+        let _ = transitions().send(value);
+    }
+}
+
+/// Writes each transition from `rx` to `stream` as one JSON line until the
+/// client goes away
+async fn stream_transitions(
+    stream: &mut UnixStream,
+    mut rx: broadcast::Receiver<Value>,
+) -> anyhow::Result<()> {
+    loop {
+        match rx.recv().await {
+            Ok(transition) => {
+                let mut line = serde_json::to_vec(&transition)?;
+                line.push(b'\n');
+                stream.write_all(&line).await?;
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::debug!("transition subscriber missed {} events", skipped);
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
 fn replay_recent_events(app: &App, runtime_id: &str) {
     let path = vision_replay_log_path();
     let entries = with_exclusive_lock(&lock_path_for(&path), || {
@@ -509,6 +553,8 @@ fn advance_cursor(runtime_id: &str, seq: u64) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::io::AsyncBufReadExt;
+
     struct TempDxRoot {
         _guard: std::sync::MutexGuard<'static, ()>,
         _tmp: tempfile::TempDir,
@@ -642,6 +688,29 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn transitions_stream_as_json_lines() {
+        let (mut server, client) = UnixStream::pair().unwrap();
+        let rx = transitions().subscribe();
+        let streaming = tokio::spawn(async move { stream_transitions(&mut server, rx).await });
+
+        publish_transition(&serde_json::json!({
+            "target": "main:0.1",
+            "from": "Processing",
+            "to": "Idle",
+            "at": "2026-10-15T09:30:00Z",
+        }));
+        let mut lines = tokio::io::BufReader::new(client).lines();
+        let line = lines.next_line().await.unwrap().unwrap();
+        let event: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(event["target"], "main:0.1");
+        assert_eq!(event["to"], "Idle");
+
+        drop(lines);
+        publish_transition(&serde_json::json!({ "target": "main:0.1" }));
+        assert!(streaming.await.unwrap().is_err());
+    }
+
     #[test]
     fn advance_cursor_is_monotonic() {
         with_temp_dx_root(|| {
@@ -1,4 +1,5 @@
 pub mod agent_assets;
+pub mod agents;
 pub mod analytics;
 pub mod app;
 pub mod audit;
@@ -16,12 +17,15 @@ pub mod dxos_supervisor;
 pub mod engine;
 pub mod external_mcp;
 pub mod factory;
+pub mod hub_client;
 pub mod ipc;
 pub mod knowledge;
 pub mod machine;
 pub mod mcp;
 pub mod mcp_registry;
+pub mod monitor;
 pub mod multi_agent;
+pub mod parsers;
 pub mod provider_asset_plugins;
 pub mod provider_plugins;
 pub mod pty;
@@ -33,6 +37,7 @@ pub mod scanner;
 pub mod screen;
 pub mod session_stream;
 pub mod state;
+pub mod state_reader;
 pub mod sync;
 pub mod tmux;
 pub mod tracker;
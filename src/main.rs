@@ -46,8 +46,10 @@ mod web;
 mod workspace;
 
 use clap::{Parser, Subcommand};
+use dx_terminal::monitor;
 use serde_json::{json, Value};
 use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 #[derive(Parser)]
@@ -76,6 +78,18 @@ enum Commands {
     },
     /// Run TUI dashboard (standalone operator console)
     Tui,
+    /// Poll the agent monitor once, print the agents as a table and exit
+    List {
+        /// Print the agents as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+        /// Include detached tmux sessions
+        #[arg(long)]
+        all_sessions: bool,
+        /// Read panes from the `.txt` captures in DIR instead of tmux
+        #[arg(long, value_name = "DIR")]
+        replay: Option<PathBuf>,
+    },
     /// Run web dashboard server only
     Web {
         #[arg(long)]
@@ -134,6 +148,7 @@ fn runtime_identity(cli: &Cli, default_web_port: u16) -> String {
             cwd_hash,
         ),
         Some(Commands::Tui) => format!("tui-{:x}", cwd_hash),
+        Some(Commands::List { .. }) => format!("list-{:x}", cwd_hash),
         Some(Commands::Web { port }) => {
             format!("web-{}-{:x}", port.unwrap_or(default_web_port), cwd_hash)
         }
@@ -216,6 +231,13 @@ async fn main() -> anyhow::Result<()> {
                 .join()
                 .map_err(|_| anyhow::anyhow!("TUI thread panicked"))??;
         }
+        Some(Commands::List {
+            json,
+            all_sessions,
+            replay,
+        }) => {
+            run_list(json, all_sessions, replay).await?;
+        }
         Some(Commands::Web { port }) => {
             let port = port.unwrap_or(cfg.web_port);
             init_tracing();
@@ -232,6 +254,25 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// One poll of the agent monitor over the attached tmux sessions, every
+/// session with `all_sessions`, or the captures in `replay`
+async fn run_list(json: bool, all_sessions: bool, replay: Option<PathBuf>) -> anyhow::Result<()> {
+    let pane_source: Arc<dyn monitor::PaneSource> = match replay {
+        Some(dir) => Arc::new(monitor::ReplaySource::new(dir)),
+        None => Arc::new(
+            dx_terminal::tmux::TmuxClient::new()
+                .with_session_scope(dx_terminal::tmux::SessionScope::new(all_sessions)),
+        ),
+    };
+    let agents = monitor::poll_agents(pane_source).await?;
+    if json {
+        println!("{}", monitor::json_snapshot(&agents)?);
+    } else {
+        print!("{}", monitor::list_snapshot(&agents));
+    }
+    Ok(())
+}
+
 async fn run_gateway_cli(app: Arc<app::App>, command: GatewayCommands) -> anyhow::Result<()> {
     let output = match command {
         GatewayCommands::List { running_only } => {
@@ -0,0 +1,62 @@
+use serde::Deserialize;
+
+/// Where the activity of a Processing agent comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivitySource {
+    /// Parsed from pane content (e.g. Claude Code's "✽ Running tests…"),
+    /// falling back to the generic text
+    #[default]
+    Parsed,
+    /// Always the generic text from the title spinner / hysteresis ("Working...")
+    Title,
+}
+
+/// Picks the activity shown for a Processing agent
+pub fn resolve_activity(source: ActivitySource, generic: &str, parsed: Option<String>) -> String {
+    match (source, parsed) {
+        (ActivitySource::Parsed, Some(activity)) if !activity.is_empty() => activity,
+        _ => generic.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsed_activity_wins_when_preferred() {
+        assert_eq!(
+            resolve_activity(
+                ActivitySource::Parsed,
+                "Working...",
+                Some("Running tests…".to_string())
+            ),
+            "Running tests…"
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_generic() {
+        assert_eq!(
+            resolve_activity(ActivitySource::Parsed, "Working...", None),
+            "Working..."
+        );
+        assert_eq!(
+            resolve_activity(ActivitySource::Parsed, "Working...", Some(String::new())),
+            "Working..."
+        );
+    }
+
+    #[test]
+    fn test_title_source_ignores_parsed() {
+        assert_eq!(
+            resolve_activity(
+                ActivitySource::Title,
+                "Working...",
+                Some("Running tests…".to_string())
+            ),
+            "Working..."
+        );
+    }
+}
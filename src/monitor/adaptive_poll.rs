@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use crate::agents::{AgentStatus, MonitoredAgent};
+
+/// Default ceiling for the poll interval while every agent is idle
+pub const DEFAULT_MAX_IDLE_POLL_MS: u64 = 2000;
+
+/// Returns true if any agent is working or waiting on the user
+pub fn any_agent_active(agents: &[MonitoredAgent]) -> bool {
+    agents.iter().any(|agent| {
+        matches!(
+            agent.status,
+            AgentStatus::Processing { .. } | AgentStatus::AwaitingApproval { .. }
+        )
+    })
+}
+
+/// Picks the interval before the next poll.
+///
+/// Activity snaps back to `floor`; otherwise the interval doubles each poll up to
+/// `ceiling`, so an idle setup settles at the slow rate within a few polls.
+pub fn next_poll_interval(
+    current: Duration,
+    floor: Duration,
+    ceiling: Duration,
+    active: bool,
+) -> Duration {
+    if active {
+        floor
+    } else {
+        (current * 2).clamp(floor, ceiling.max(floor))
+    }
+}
+
+/// Attempts at the slow-cadence dashboard fetch before the cycle gives up
+pub const DASHBOARD_FETCH_ATTEMPTS: u32 = 2;
+
+/// Wait before the first retry of a failed hub fetch
+const RETRY_BASE: Duration = Duration::from_millis(150);
+
+/// Delay before retry number `attempt` (1-based). The base doubles per attempt and
+/// gets up to half of itself added from `seed`, so clients sharing a hub spread out.
+pub fn retry_delay(attempt: u32, seed: u32) -> Duration {
+    let base = RETRY_BASE * 2u32.pow(attempt.saturating_sub(1).min(4));
+    let spread = base.as_millis() as u32 / 2 + 1;
+    base + Duration::from_millis((seed % spread) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLOOR: Duration = Duration::from_millis(500);
+    const CEILING: Duration = Duration::from_millis(DEFAULT_MAX_IDLE_POLL_MS);
+
+    #[test]
+    fn test_idle_backs_off_to_ceiling() {
+        let mut interval = FLOOR;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            interval = next_poll_interval(interval, FLOOR, CEILING, false);
+            seen.push(interval.as_millis());
+        }
+        assert_eq!(seen, vec![1000, 2000, 2000, 2000]);
+    }
+
+    #[test]
+    fn test_activity_returns_to_floor() {
+        assert_eq!(next_poll_interval(CEILING, FLOOR, CEILING, true), FLOOR);
+    }
+
+    #[test]
+    fn test_ceiling_below_floor_keeps_floor() {
+        let ceiling = Duration::from_millis(100);
+        assert_eq!(next_poll_interval(FLOOR, FLOOR, ceiling, false), FLOOR);
+    }
+
+    #[test]
+    fn test_retry_delay_jitter_stays_in_range() {
+        for seed in [0, 1, 74, 75, 76, u32::MAX] {
+            let delay = retry_delay(1, seed);
+            assert!(delay >= RETRY_BASE && delay <= RETRY_BASE * 3 / 2, "{:?}", delay);
+        }
+        assert_eq!(retry_delay(1, 0), RETRY_BASE);
+        assert_ne!(retry_delay(1, 10), retry_delay(1, 20));
+    }
+
+    #[test]
+    fn test_retry_delay_grows_per_attempt() {
+        assert_eq!(retry_delay(2, 0), RETRY_BASE * 2);
+        assert_eq!(retry_delay(3, 0), RETRY_BASE * 4);
+        // Capped so a long outage never sleeps the poll loop for seconds
+        assert_eq!(retry_delay(50, 0), retry_delay(5, 0));
+    }
+}
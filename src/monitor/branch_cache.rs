@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long a resolved branch is trusted before `git` is asked again
+pub const BRANCH_TTL: Duration = Duration::from_secs(30);
+
+/// Git branch per working directory, so tmux agents get a branch without
+/// forking `git` for every pane on every poll. Non-git directories are cached
+/// as None too.
+#[derive(Debug, Default)]
+pub struct BranchCache {
+    /// path -> (when it was resolved, branch)
+    entries: HashMap<String, (Instant, Option<String>)>,
+}
+
+impl BranchCache {
+    /// Branch checked out in `path`, or None outside a git work tree or on a detached HEAD
+    pub fn branch(&mut self, path: &str) -> Option<String> {
+        self.lookup(path, Instant::now(), read_branch)
+    }
+
+    fn lookup(
+        &mut self,
+        path: &str,
+        now: Instant,
+        resolve: impl FnOnce(&str) -> Option<String>,
+    ) -> Option<String> {
+        if let Some((resolved_at, branch)) = self.entries.get(path) {
+            if now.duration_since(*resolved_at) < BRANCH_TTL {
+                return branch.clone();
+            }
+        }
+        let branch = resolve(path);
+        self.entries.insert(path.to_string(), (now, branch.clone()));
+        branch
+    }
+
+    /// Drops entries for directories no agent is working in anymore
+    pub fn retain_paths(&mut self, live: &[&str]) {
+        self.entries.retain(|path, _| live.contains(&path.as_str()));
+    }
+}
+
+/// Asks git for the current branch of `path`
+fn read_branch(path: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["-C", path, "rev-parse", "--abbrev-ref", "HEAD"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // A detached HEAD is reported as the literal "HEAD"
+    (!branch.is_empty() && branch != "HEAD").then_some(branch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_reuses_branch_within_ttl() {
+        let mut cache = BranchCache::default();
+        let start = Instant::now();
+        let calls = std::cell::Cell::new(0);
+        let resolve = |_: &str| {
+            calls.set(calls.get() + 1);
+            Some("feature/login".to_string())
+        };
+
+        assert_eq!(
+            cache.lookup("/srv/app", start, resolve),
+            Some("feature/login".to_string())
+        );
+        assert_eq!(
+            cache.lookup("/srv/app", start + Duration::from_secs(5), resolve),
+            Some("feature/login".to_string())
+        );
+        assert_eq!(calls.get(), 1);
+
+        cache.lookup("/srv/app", start + BRANCH_TTL, resolve);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_lookup_caches_non_git_directories() {
+        let mut cache = BranchCache::default();
+        let now = Instant::now();
+        assert_eq!(cache.lookup("/tmp", now, |_| None), None);
+        // Still None without asking again
+        assert_eq!(
+            cache.lookup("/tmp", now, |_| panic!("resolved twice")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_retain_paths_forgets_gone_directories() {
+        let mut cache = BranchCache::default();
+        let now = Instant::now();
+        cache.lookup("/srv/a", now, |_| Some("main".to_string()));
+        cache.lookup("/srv/b", now, |_| Some("dev".to_string()));
+
+        cache.retain_paths(&["/srv/b"]);
+        assert!(!cache.entries.contains_key("/srv/a"));
+        assert!(cache.entries.contains_key("/srv/b"));
+    }
+
+    #[test]
+    fn test_read_branch_outside_work_tree() {
+        assert_eq!(read_branch("/nonexistent/agentos-tui-branch-test"), None);
+    }
+}
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+use crate::agents::AgentType;
+
+/// `[agent_capture_lines]` section of the config: scrollback lines captured for
+/// each agent type, overriding the global `capture_lines`. Claude Code's dense
+/// UI may need more history to see its approval buttons; small agents need less.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AgentCaptureLines {
+    pub claude_code: Option<u32>,
+    pub opencode: Option<u32>,
+    pub codex_cli: Option<u32>,
+    pub gemini_cli: Option<u32>,
+}
+
+impl AgentCaptureLines {
+    /// Lines to capture for `agent_type`, or None to use the global count
+    pub fn for_agent(&self, agent_type: &AgentType) -> Option<u32> {
+        match agent_type {
+            AgentType::ClaudeCode => self.claude_code,
+            AgentType::OpenCode => self.opencode,
+            AgentType::CodexCli => self.codex_cli,
+            AgentType::GeminiCli => self.gemini_cli,
+            AgentType::Custom(_) | AgentType::Unknown => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_capture_lines_override() {
+        let lines = AgentCaptureLines {
+            claude_code: Some(400),
+            ..Default::default()
+        };
+        assert_eq!(lines.for_agent(&AgentType::ClaudeCode), Some(400));
+        assert_eq!(lines.for_agent(&AgentType::CodexCli), None);
+        assert_eq!(lines.for_agent(&AgentType::Unknown), None);
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use crate::agents::MonitoredAgent;
+
+/// Two or more agents working in the same directory (and therefore on the same branch)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCollision {
+    /// Shared working directory
+    pub path: String,
+    /// Branch reported by any of the agents
+    pub branch: Option<String>,
+    /// Targets of the colliding agents, in tree order
+    pub targets: Vec<String>,
+}
+
+impl PathCollision {
+    /// Returns true if `target` is one of the colliding agents
+    pub fn involves(&self, target: &str) -> bool {
+        self.targets.iter().any(|t| t == target)
+    }
+}
+
+/// Finds directories shared by more than one agent, across all sessions
+pub fn find_path_collisions(agents: &[MonitoredAgent]) -> Vec<PathCollision> {
+    let mut by_path: BTreeMap<&str, Vec<&MonitoredAgent>> = BTreeMap::new();
+    for agent in agents {
+        let path = agent.path.trim_end_matches('/');
+        if path.is_empty() {
+            continue;
+        }
+        by_path.entry(path).or_default().push(agent);
+    }
+
+    by_path
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|(path, group)| PathCollision {
+            path: path.to_string(),
+            branch: group.iter().find_map(|a| a.branch.clone()),
+            targets: group.iter().map(|a| a.target.clone()).collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(target: &str, path: &str) -> MonitoredAgent {
+        MonitoredAgent {
+            path: path.to_string(),
+            ..MonitoredAgent::for_test(target)
+        }
+    }
+
+    #[test]
+    fn test_collision_across_sessions() {
+        let mut other = agent("work:0.0", "/home/user/api/");
+        other.branch = Some("main".to_string());
+        let agents = vec![
+            agent("main:0.0", "/home/user/api"),
+            agent("main:0.1", "/home/user/web"),
+            other,
+        ];
+
+        let collisions = find_path_collisions(&agents);
+        assert_eq!(
+            collisions,
+            vec![PathCollision {
+                path: "/home/user/api".to_string(),
+                branch: Some("main".to_string()),
+                targets: vec!["main:0.0".to_string(), "work:0.0".to_string()],
+            }]
+        );
+        assert!(collisions[0].involves("work:0.0"));
+        assert!(!collisions[0].involves("main:0.1"));
+    }
+
+    #[test]
+    fn test_no_collision_for_distinct_or_empty_paths() {
+        let agents = vec![
+            agent("main:0.0", "/home/user/api"),
+            agent("main:0.1", "/home/user/api-v2"),
+            agent("main:0.2", ""),
+            agent("work:0.0", ""),
+        ];
+        assert!(find_path_collisions(&agents).is_empty());
+    }
+}
@@ -0,0 +1,387 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::tmux::{CaptureMode, PaneInfo, SessionScope, TmuxClient};
+
+use super::replay::PaneSource;
+
+/// `tmux_backend` config value: how the monitor talks to tmux
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TmuxBackend {
+    /// One `tmux` process per list and per capture
+    #[default]
+    Command,
+    /// Captures go through a long-lived `tmux -C` client
+    Control,
+}
+
+/// Reply to one control-mode command
+#[derive(Debug, PartialEq, Eq)]
+enum Reply {
+    Ok(String),
+    Error(String),
+}
+
+/// Reads from a `tmux -C` client until the reply to a command this client sent
+/// is complete. Notifications and replies to tmux's own commands (flags 0) in
+/// between are skipped.
+fn read_reply(reader: &mut impl BufRead) -> io::Result<Reply> {
+    let closed = || io::Error::new(io::ErrorKind::UnexpectedEof, "tmux control client exited");
+    let mut line = String::new();
+    // "%begin time number flags" arguments of the open block, and its output
+    let mut block: Option<(String, String)> = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(closed());
+        }
+        let text = line.trim_end_matches('\n');
+
+        let Some((guard, output)) = &mut block else {
+            if let Some(guard) = text.strip_prefix("%begin ") {
+                block = Some((guard.to_string(), String::new()));
+            } else if text == "%exit" || text.starts_with("%exit ") {
+                return Err(closed());
+            }
+            continue;
+        };
+        // Pane content can contain "%end" lines too; only the matching guard closes
+        let ok = match (text.strip_prefix("%end "), text.strip_prefix("%error ")) {
+            (Some(end), _) if end == guard => true,
+            (_, Some(end)) if end == guard => false,
+            _ => {
+                output.push_str(text);
+                output.push('\n');
+                continue;
+            }
+        };
+        let from_client = guard
+            .rsplit(' ')
+            .next()
+            .and_then(|flags| flags.parse::<u32>().ok())
+            .is_some_and(|flags| flags & 1 != 0);
+        let output = std::mem::take(output);
+        block = None;
+        if from_client {
+            return Ok(if ok {
+                Reply::Ok(output)
+            } else {
+                Reply::Error(output.trim_end().to_string())
+            });
+        }
+    }
+}
+
+/// Quotes `arg` for tmux's command parser, which expands `$` and `~` and treats
+/// `;`, `#` and braces specially outside single quotes
+fn quote_arg(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.:%@/+=,".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// The session id the control client should attach to, from
+/// `list-sessions -F '#{session_attached}\t#{session_id}'` output.
+///
+/// Attaching makes a session count as attached, so an already attached one is
+/// preferred; with the default scope a detached session is never picked, or its
+/// agents would start showing up.
+fn choose_session(sessions: &str, scope: SessionScope) -> Option<String> {
+    let sessions: Vec<(&str, &str)> = sessions
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+    sessions
+        .iter()
+        .find(|(attached, _)| SessionScope::Attached.includes(attached))
+        .or_else(|| sessions.first().filter(|_| scope == SessionScope::All))
+        .map(|(_, id)| id.to_string())
+}
+
+/// Whether an attach error means this tmux can't run the control client at all:
+/// it rejected `-C` or `attach-session -f` (tmux before 3.2). Anything else, such
+/// as the session closing mid-attach, may pass by the next poll.
+fn rejects_control_mode(error: &str) -> bool {
+    let error = error.to_lowercase();
+    ["unknown flag", "unknown option", "invalid option", "usage:"]
+        .iter()
+        .any(|marker| error.contains(marker))
+}
+
+/// A `tmux -C` client attached without pane output, resizing or input
+struct ControlClient {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    stderr: ChildStderr,
+}
+
+impl ControlClient {
+    fn attach(session: &str) -> Result<Self> {
+        let mut child = Command::new("tmux")
+            .args([
+                "-C",
+                "attach-session",
+                "-f",
+                "no-output,ignore-size,read-only",
+            ])
+            .args(["-t", session])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to start tmux control client")?;
+        let (Some(stdin), Some(stdout), Some(stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("tmux control client has no pipes"));
+        };
+        let mut client = Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            stderr,
+        };
+        // The first reply shows whether tmux accepted the attach
+        if let Err(e) = client.session_clients() {
+            let stderr = client.exit_message();
+            return Err(if stderr.is_empty() {
+                e
+            } else {
+                anyhow!("{}: {}", e, stderr)
+            });
+        }
+        Ok(client)
+    }
+
+    /// Stops the client and returns what tmux printed to stderr, e.g. why it
+    /// refused the command line
+    fn exit_message(&mut self) -> String {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let mut message = String::new();
+        let _ = self.stderr.read_to_string(&mut message);
+        message.trim().to_string()
+    }
+
+    /// Runs one tmux command and waits for its reply
+    fn command(&mut self, args: &[String]) -> io::Result<Reply> {
+        let words: Vec<String> = args.iter().map(|arg| quote_arg(arg)).collect();
+        writeln!(self.stdin, "{}", words.join(" "))?;
+        self.stdin.flush()?;
+        read_reply(&mut self.stdout)
+    }
+
+    /// Clients attached to the control client's session, itself included
+    fn session_clients(&mut self) -> Result<u32> {
+        let args = ["display-message", "-p", "#{session_attached}"].map(String::from);
+        match self.command(&args)? {
+            Reply::Ok(count) => Ok(count.trim().parse()?),
+            Reply::Error(message) => Err(anyhow!(message)),
+        }
+    }
+}
+
+impl Drop for ControlClient {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[derive(Default)]
+struct ControlState {
+    client: Option<ControlClient>,
+    /// tmux rejected the control client's command line (e.g. older than 3.2,
+    /// without `attach-session -f`), so attaching is not tried again
+    unsupported: bool,
+}
+
+/// Captures panes through one long-lived `tmux -C` client instead of a
+/// `tmux capture-pane` process per pane, so a poll forks a couple of processes
+/// rather than one per agent.
+///
+/// Panes are still listed by the `TmuxClient`, which also fills in process
+/// details. It takes over captures too whenever the control client is down:
+/// before a suitable session exists, after tmux exits, or for good if tmux
+/// doesn't support control mode.
+pub struct ControlModeSource {
+    fallback: Arc<TmuxClient>,
+    scope: SessionScope,
+    capture_lines: u32,
+    state: Mutex<ControlState>,
+}
+
+impl ControlModeSource {
+    pub fn new(fallback: Arc<TmuxClient>, scope: SessionScope, capture_lines: u32) -> Self {
+        Self {
+            fallback,
+            scope,
+            capture_lines,
+            state: Mutex::new(ControlState::default()),
+        }
+    }
+
+    /// Checks the control client once per poll: drops it when it died or is the
+    /// last client keeping a session attached, and (re)attaches when it's down
+    fn refresh_client(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.unsupported {
+            return;
+        }
+        if let Some(client) = state.client.as_mut() {
+            match client.session_clients() {
+                Ok(clients) if self.scope == SessionScope::All || clients > 1 => return,
+                Ok(_) => debug!("tmux session detached, dropping control client"),
+                Err(e) => debug!("tmux control client lost: {}", e),
+            }
+            state.client = None;
+        }
+
+        let Some(session) = Command::new("tmux")
+            .args(["list-sessions", "-F", "#{session_attached}\t#{session_id}"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                choose_session(&String::from_utf8_lossy(&output.stdout), self.scope)
+            })
+        else {
+            return;
+        };
+        match ControlClient::attach(&session) {
+            Ok(client) => state.client = Some(client),
+            Err(e) if rejects_control_mode(&format!("{:#}", e)) => {
+                warn!(
+                    "tmux doesn't support control mode, capturing panes one process at a time: {:#}",
+                    e
+                );
+                state.unsupported = true;
+            }
+            // Captures fall back for this poll; attaching is tried again on the next
+            Err(e) => debug!("tmux control client failed to attach: {:#}", e),
+        }
+    }
+}
+
+impl PaneSource for ControlModeSource {
+    fn list_panes(&self) -> Result<Vec<PaneInfo>> {
+        self.refresh_client();
+        self.fallback.list_panes()
+    }
+
+    fn capture_pane_mode(&self, target: &str, mode: CaptureMode) -> Result<String> {
+        self.capture_pane_lines(target, mode, self.capture_lines)
+    }
+
+    fn capture_pane_lines(&self, target: &str, mode: CaptureMode, lines: u32) -> Result<String> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = state.client.as_mut() {
+            match client.command(&mode.args(target, lines)) {
+                Ok(Reply::Ok(output)) => return Ok(output),
+                Ok(Reply::Error(message)) => {
+                    return Err(anyhow!("Failed to capture {}: {}", target, message))
+                }
+                Err(e) => {
+                    debug!("tmux control client lost: {}", e);
+                    state.client = None;
+                }
+            }
+        }
+        drop(state);
+        self.fallback.capture_pane_lines(target, mode, lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_reply_skips_notifications_and_server_blocks() {
+        let stream = "%begin 1792045604 264 0\n\
+                      %end 1792045604 264 0\n\
+                      %session-changed $0 work\n\
+                      %begin 1792045604 269 1\n\
+                      ❯ hello\n\
+                      %end 1 2 1\n\
+                      \n\
+                      %end 1792045604 269 1\n\
+                      %begin 1792045604 270 1\n\
+                      can't find pane: nosuch\n\
+                      %error 1792045604 270 1\n";
+        let mut reader = Cursor::new(stream);
+        assert_eq!(
+            read_reply(&mut reader).unwrap(),
+            Reply::Ok("❯ hello\n%end 1 2 1\n\n".to_string())
+        );
+        assert_eq!(
+            read_reply(&mut reader).unwrap(),
+            Reply::Error("can't find pane: nosuch".to_string())
+        );
+        assert!(read_reply(&mut reader).is_err());
+        assert!(read_reply(&mut Cursor::new("%begin 1 2 1\nhalf\n%exit\n")).is_err());
+    }
+
+    #[test]
+    fn test_quote_arg() {
+        assert_eq!(quote_arg("capture-pane"), "capture-pane");
+        assert_eq!(quote_arg("main:0.1"), "main:0.1");
+        assert_eq!(quote_arg("-200"), "-200");
+        assert_eq!(quote_arg("my session:0.1"), "'my session:0.1'");
+        assert_eq!(quote_arg("$1"), "'$1'");
+        assert_eq!(quote_arg("#{session_attached}"), "'#{session_attached}'");
+        assert_eq!(quote_arg("it's;x"), r"'it'\''s;x'");
+        assert_eq!(quote_arg(""), "''");
+    }
+
+    #[test]
+    fn test_rejects_control_mode() {
+        assert!(rejects_control_mode(
+            "tmux control client exited: command attach-session: unknown flag -f"
+        ));
+        assert!(rejects_control_mode(
+            "tmux control client exited: tmux: unknown option -- C\nusage: tmux [-2Ss]"
+        ));
+        assert!(!rejects_control_mode(
+            "tmux control client exited: can't find session: $3"
+        ));
+        assert!(!rejects_control_mode(
+            "no server running on /tmp/tmux-1000/default"
+        ));
+        assert!(!rejects_control_mode("Failed to start tmux control client"));
+    }
+
+    #[test]
+    fn test_choose_session_prefers_attached() {
+        let sessions = "0\t$0\n1\t$1\n2\t$2\n";
+        assert_eq!(
+            choose_session(sessions, SessionScope::Attached).as_deref(),
+            Some("$1")
+        );
+
+        let detached = "0\t$0\n0\t$3\n";
+        assert_eq!(choose_session(detached, SessionScope::Attached), None);
+        assert_eq!(
+            choose_session(detached, SessionScope::All).as_deref(),
+            Some("$0")
+        );
+        assert_eq!(choose_session("", SessionScope::All), None);
+    }
+}
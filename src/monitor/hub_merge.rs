@@ -0,0 +1,130 @@
+use crate::agents::{AgentStatus, MonitoredAgent};
+
+/// Adds an agent reported by the hub API to `agents`, or merges it into the tmux
+/// agent for the same pane.
+///
+/// The pane is matched by its tmux target when the hub reports one. Otherwise it
+/// falls back to path and agent type, but only when exactly one tmux agent has
+/// them; with several, there is no telling which pane the hub means.
+///
+/// The hub doesn't know PIDs or pane content, so a merged agent keeps the tmux
+/// side's target, PID, content and status. A non-empty hub branch replaces the
+/// tmux one, and the hub's status is only used when tmux couldn't tell.
+pub fn merge_hub_agent(agents: &mut Vec<MonitoredAgent>, hub: MonitoredAgent) {
+    let index = agents
+        .iter()
+        .position(|a| a.target == hub.target)
+        .or_else(|| {
+            let mut same_pane = agents
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| a.path == hub.path && a.agent_type == hub.agent_type);
+            match (same_pane.next(), same_pane.next()) {
+                (Some((i, _)), None) => Some(i),
+                _ => None,
+            }
+        });
+    let Some(local) = index.map(|i| &mut agents[i]) else {
+        agents.push(hub);
+        return;
+    };
+
+    if let Some(branch) = hub.branch.filter(|b| !b.is_empty()) {
+        local.branch = Some(branch);
+    }
+    if local.status == AgentStatus::Unknown {
+        local.status = hub.status;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::AgentType;
+
+    fn agent(target: &str, path: &str, pid: u32) -> MonitoredAgent {
+        MonitoredAgent {
+            path: path.to_string(),
+            pid,
+            ..MonitoredAgent::for_test(target)
+        }
+    }
+
+    #[test]
+    fn test_matching_pane_merged_into_tmux_agent() {
+        let mut local = agent("main:0.0", "/work/api", 4242);
+        local.last_content = "❯ ready".to_string();
+        local.status = AgentStatus::Idle;
+        let mut agents = vec![local];
+
+        let mut hub = agent("dx-3", "/work/api", 0);
+        hub.branch = Some("feature/login".to_string());
+        hub.status = AgentStatus::Processing {
+            activity: "tests".to_string(),
+        };
+        merge_hub_agent(&mut agents, hub);
+
+        assert_eq!(agents.len(), 1);
+        let merged = &agents[0];
+        assert_eq!(merged.target, "main:0.0");
+        assert_eq!(merged.pid, 4242);
+        assert_eq!(merged.last_content, "❯ ready");
+        assert_eq!(merged.branch.as_deref(), Some("feature/login"));
+        // tmux saw the pane itself, so its status wins
+        assert_eq!(merged.status, AgentStatus::Idle);
+    }
+
+    #[test]
+    fn test_missing_hub_fields_keep_tmux_values() {
+        let mut local = agent("main:0.0", "/work/api", 4242);
+        local.branch = Some("main".to_string());
+        let mut agents = vec![local];
+
+        let mut hub = agent("dx-3", "/work/api", 0);
+        hub.branch = None;
+        hub.status = AgentStatus::Idle;
+        merge_hub_agent(&mut agents, hub);
+        assert_eq!(agents[0].branch.as_deref(), Some("main"));
+        assert_eq!(agents[0].status, AgentStatus::Idle);
+
+        // Unknown tmux status is the gap the hub fills
+        agents[0].status = AgentStatus::Unknown;
+        let mut hub = agent("dx-3", "/work/api", 0);
+        hub.status = AgentStatus::Idle;
+        merge_hub_agent(&mut agents, hub);
+        assert_eq!(agents[0].status, AgentStatus::Idle);
+    }
+
+    #[test]
+    fn test_shared_path_matched_by_target() {
+        let mut agents = vec![
+            agent("main:0.0", "/work/api", 4242),
+            agent("main:0.1", "/work/api", 4243),
+        ];
+
+        let mut hub = agent("main:0.1", "/work/api", 0);
+        hub.branch = Some("feature/login".to_string());
+        merge_hub_agent(&mut agents, hub);
+        assert_eq!(agents.len(), 2);
+        assert_eq!(agents[0].branch, None);
+        assert_eq!(agents[1].branch.as_deref(), Some("feature/login"));
+
+        // Without a target the pane is ambiguous, so it is listed on its own
+        merge_hub_agent(&mut agents, agent("dx-3", "/work/api", 0));
+        let targets: Vec<&str> = agents.iter().map(|a| a.target.as_str()).collect();
+        assert_eq!(targets, vec!["main:0.0", "main:0.1", "dx-3"]);
+    }
+
+    #[test]
+    fn test_unmatched_pane_added() {
+        let mut agents = vec![agent("main:0.0", "/work/api", 4242)];
+        merge_hub_agent(&mut agents, agent("dx-3", "/work/web", 0));
+
+        let mut other_type = agent("dx-4", "/work/api", 0);
+        other_type.agent_type = AgentType::CodexCli;
+        merge_hub_agent(&mut agents, other_type);
+
+        let targets: Vec<&str> = agents.iter().map(|a| a.target.as_str()).collect();
+        assert_eq!(targets, vec!["main:0.0", "dx-3", "dx-4"]);
+    }
+}
@@ -1,5 +1,35 @@
+mod activity;
+mod adaptive_poll;
+mod branch_cache;
+mod capture;
+mod collisions;
+mod control_mode;
+mod hub_merge;
+mod parse_cache;
+mod poll_stats;
+mod replay;
+mod snapshot;
 mod system_stats;
 mod task;
+mod tree_diff;
 
-pub use system_stats::{SystemStats, SystemStatsCollector};
+pub use crate::tmux::{CaptureMode, SessionScope};
+
+pub use activity::{resolve_activity, ActivitySource};
+pub use adaptive_poll::{any_agent_active, next_poll_interval, DEFAULT_MAX_IDLE_POLL_MS};
+pub use capture::AgentCaptureLines;
+pub use collisions::{find_path_collisions, PathCollision};
+pub use control_mode::{ControlModeSource, TmuxBackend};
+pub use hub_merge::merge_hub_agent;
+pub use parse_cache::{ParseCache, ParsedPane};
+pub use poll_stats::{PaneTiming, PollStats};
+pub use replay::{PaneSource, ReplaySource, REPLAY_SESSION};
+pub use snapshot::{json_snapshot, list_snapshot, poll_agents};
+pub use system_stats::{
+    LoadAverage, StatsSample, SystemStats, SystemStatsCollector, STATS_HISTORY_LEN,
+};
 pub use task::{FactoryCommand, MonitorTask, MonitorUpdate};
+pub use tree_diff::{
+    closed_agents, newly_pending, status_transitions, ClosedAgent, StatusTransition,
+    RECENTLY_CLOSED_TTL,
+};
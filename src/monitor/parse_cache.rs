@@ -0,0 +1,142 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::agents::{AgentStatus, Subagent};
+use crate::parsers::AgentParser;
+
+/// Everything the parser extracts from one pane capture
+#[derive(Debug, Clone)]
+pub struct ParsedPane {
+    pub status: AgentStatus,
+    pub subagents: Vec<Subagent>,
+    pub context_remaining: Option<u8>,
+    pub activity: Option<String>,
+    pub model: Option<String>,
+}
+
+impl ParsedPane {
+    fn parse(parser: &dyn AgentParser, content: &str) -> Self {
+        Self {
+            status: parser.parse_status(content),
+            subagents: parser.parse_subagents(content),
+            context_remaining: parser.parse_context_remaining(content),
+            activity: parser.parse_activity(content),
+            model: parser.parse_model(content),
+        }
+    }
+}
+
+/// Last parse result per target, reused while the captured content is unchanged.
+///
+/// Idle panes rarely change between polls, and the regex-heavy parsers dominate
+/// the per-pane cost once the capture itself is done, so most polls only pay
+/// for capture-pane plus a hash.
+#[derive(Debug, Default)]
+pub struct ParseCache {
+    /// target -> (hash of parser name + content, parse result)
+    entries: HashMap<String, (u64, ParsedPane)>,
+}
+
+impl ParseCache {
+    /// Parses `content` with `parser`, unless the same content was parsed for `target`
+    /// by the same parser last time. Returns the result and whether it was a cache hit.
+    pub fn parse(
+        &mut self,
+        target: &str,
+        content: &str,
+        parser: &dyn AgentParser,
+    ) -> (ParsedPane, bool) {
+        let hash = content_hash(parser.agent_name(), content);
+        if let Some((cached_hash, parsed)) = self.entries.get(target) {
+            if *cached_hash == hash {
+                return (parsed.clone(), true);
+            }
+        }
+        let parsed = ParsedPane::parse(parser, content);
+        self.entries
+            .insert(target.to_string(), (hash, parsed.clone()));
+        (parsed, false)
+    }
+
+    /// Drops entries for panes that are gone
+    pub fn retain_targets(&mut self, live: &[String]) {
+        self.entries.retain(|target, _| live.contains(target));
+    }
+}
+
+fn content_hash(parser_name: &str, content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    parser_name.hash(&mut hasher);
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::agents::AgentType;
+
+    /// Parser that counts parse_status calls
+    #[derive(Default)]
+    struct CountingParser {
+        calls: AtomicUsize,
+    }
+
+    impl AgentParser for CountingParser {
+        fn agent_name(&self) -> &str {
+            "Counting"
+        }
+
+        fn agent_type(&self) -> AgentType {
+            AgentType::Unknown
+        }
+
+        fn matches(&self, _detection_strings: &[&str]) -> bool {
+            true
+        }
+
+        fn parse_status(&self, content: &str) -> AgentStatus {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if content.contains("busy") {
+                AgentStatus::Processing {
+                    activity: String::new(),
+                }
+            } else {
+                AgentStatus::Idle
+            }
+        }
+    }
+
+    #[test]
+    fn test_identical_capture_is_not_reparsed() {
+        let parser = CountingParser::default();
+        let mut cache = ParseCache::default();
+
+        let (first, hit) = cache.parse("main:0.0", "busy", &parser);
+        assert!(!hit);
+        let (second, hit) = cache.parse("main:0.0", "busy", &parser);
+        assert!(hit);
+
+        assert_eq!(parser.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.status, second.status);
+    }
+
+    #[test]
+    fn test_changed_content_or_gone_pane_reparses() {
+        let parser = CountingParser::default();
+        let mut cache = ParseCache::default();
+
+        cache.parse("main:0.0", "busy", &parser);
+        let (parsed, hit) = cache.parse("main:0.0", "done", &parser);
+        assert!(!hit);
+        assert_eq!(parsed.status, AgentStatus::Idle);
+
+        cache.retain_targets(&[]);
+        let (_, hit) = cache.parse("main:0.0", "done", &parser);
+        assert!(!hit);
+        assert_eq!(parser.calls.load(Ordering::SeqCst), 3);
+    }
+}
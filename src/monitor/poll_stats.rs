@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+/// Capture timing for a single pane
+#[derive(Debug, Clone)]
+pub struct PaneTiming {
+    pub target: String,
+    /// Name of the parser that matched the pane
+    pub parser: String,
+    pub capture: Duration,
+}
+
+/// Diagnostics collected during one monitor poll (shown in the debug overlay)
+#[derive(Debug, Clone, Default)]
+pub struct PollStats {
+    /// Wall time of the whole poll (tmux + hub)
+    pub poll_duration: Duration,
+    /// Panes that matched a parser, in poll order
+    pub panes: Vec<PaneTiming>,
+    /// Panes no parser recognised
+    pub unmatched_panes: usize,
+    /// Hub `/api/panes` round trip, if the hub was queried
+    pub api_latency: Option<Duration>,
+    /// Panes whose content was unchanged, so the previous parse was reused
+    pub parse_cache_hits: usize,
+}
+
+impl PollStats {
+    /// Records a captured pane and the parser that matched it
+    pub fn record_pane(&mut self, target: &str, parser: &str, capture: Duration) {
+        self.panes.push(PaneTiming {
+            target: target.to_string(),
+            parser: parser.to_string(),
+            capture,
+        });
+    }
+
+    /// Records a pane that no parser matched
+    pub fn record_unmatched(&mut self) {
+        self.unmatched_panes += 1;
+    }
+
+    /// Records a pane whose parse result came from the cache
+    pub fn record_cache_hit(&mut self) {
+        self.parse_cache_hits += 1;
+    }
+
+    /// Total time spent in capture-pane
+    pub fn total_capture(&self) -> Duration {
+        self.panes.iter().map(|p| p.capture).sum()
+    }
+
+    /// Pane with the longest capture time
+    pub fn slowest_pane(&self) -> Option<&PaneTiming> {
+        self.panes.iter().max_by_key(|p| p.capture)
+    }
+
+    /// Number of panes matched per parser, sorted by parser name
+    pub fn parser_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for pane in &self.panes {
+            match counts.iter_mut().find(|(name, _)| *name == pane.parser) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((pane.parser.clone(), 1)),
+            }
+        }
+        counts.sort_by(|a, b| a.0.cmp(&b.0));
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> PollStats {
+        let mut stats = PollStats::default();
+        stats.record_pane("main:0.0", "Claude Code", Duration::from_millis(4));
+        stats.record_pane("main:0.1", "Codex CLI", Duration::from_millis(9));
+        stats.record_pane("main:1.0", "Claude Code", Duration::from_millis(2));
+        stats.record_unmatched();
+        stats
+    }
+
+    #[test]
+    fn test_total_and_slowest() {
+        let stats = stats();
+        assert_eq!(stats.total_capture(), Duration::from_millis(15));
+        assert_eq!(stats.slowest_pane().unwrap().target, "main:0.1");
+        assert_eq!(stats.unmatched_panes, 1);
+    }
+
+    #[test]
+    fn test_parser_counts() {
+        assert_eq!(
+            stats().parser_counts(),
+            vec![("Claude Code".to_string(), 2), ("Codex CLI".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_empty_stats() {
+        let stats = PollStats::default();
+        assert_eq!(stats.total_capture(), Duration::ZERO);
+        assert!(stats.slowest_pane().is_none());
+        assert!(stats.parser_counts().is_empty());
+    }
+}
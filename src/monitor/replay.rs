@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::tmux::{CaptureMode, PaneInfo, TmuxClient};
+
+/// Session name replayed panes are listed under
+pub const REPLAY_SESSION: &str = "replay";
+
+/// Agent names a capture's file name can carry to pick its parser, e.g. `codex-busy.txt`
+const AGENT_HINTS: &[&str] = &["claude", "opencode", "codex", "gemini"];
+
+/// Where the monitor gets panes and their content from: tmux normally, a
+/// directory of captures in `--replay` mode
+pub trait PaneSource: Send + Sync {
+    fn list_panes(&self) -> Result<Vec<PaneInfo>>;
+
+    fn capture_pane_mode(&self, target: &str, mode: CaptureMode) -> Result<String>;
+
+    /// Like `capture_pane_mode`, with `lines` of scrollback instead of the default
+    fn capture_pane_lines(&self, target: &str, mode: CaptureMode, lines: u32) -> Result<String>;
+}
+
+impl PaneSource for TmuxClient {
+    fn list_panes(&self) -> Result<Vec<PaneInfo>> {
+        TmuxClient::list_panes(self)
+    }
+
+    fn capture_pane_mode(&self, target: &str, mode: CaptureMode) -> Result<String> {
+        TmuxClient::capture_pane_mode(self, target, mode)
+    }
+
+    fn capture_pane_lines(&self, target: &str, mode: CaptureMode, lines: u32) -> Result<String> {
+        TmuxClient::capture_pane_lines(self, target, mode, lines)
+    }
+}
+
+/// Serves every `.txt` file in a directory as one pane's captured content, so
+/// parsers can be exercised on saved scrollbacks without tmux. Files are read
+/// again on every poll, so editing one updates its agent live.
+pub struct ReplaySource {
+    dir: PathBuf,
+}
+
+impl ReplaySource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The `.txt` files in the directory, sorted by name; a pane's window index
+    /// is its position in this list
+    fn captures(&self) -> Result<Vec<PathBuf>> {
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read replay directory {}", self.dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+}
+
+impl PaneSource for ReplaySource {
+    fn list_panes(&self) -> Result<Vec<PaneInfo>> {
+        let path = self.dir.display().to_string();
+        Ok(self
+            .captures()?
+            .iter()
+            .enumerate()
+            .map(|(window, file)| {
+                let name = file_stem(file);
+                let command = detection_command(&name);
+                PaneInfo {
+                    session: REPLAY_SESSION.to_string(),
+                    window: window as u32,
+                    window_name: name,
+                    pane: 0,
+                    command: command.clone(),
+                    title: String::new(),
+                    path: path.clone(),
+                    pid: 0,
+                    cmdline: command,
+                    child_commands: Vec::new(),
+                    alternate_on: false,
+                }
+            })
+            .collect())
+    }
+
+    fn capture_pane_mode(&self, target: &str, _mode: CaptureMode) -> Result<String> {
+        let window = target
+            .strip_prefix(REPLAY_SESSION)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .and_then(|rest| rest.split('.').next())
+            .and_then(|window| window.parse::<usize>().ok())
+            .with_context(|| format!("Not a replay target: {}", target))?;
+        let captures = self.captures()?;
+        let file = captures
+            .get(window)
+            .with_context(|| format!("Replay capture {} is gone", target))?;
+        fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))
+    }
+
+    fn capture_pane_lines(&self, target: &str, mode: CaptureMode, _lines: u32) -> Result<String> {
+        // A capture file is replayed whole
+        self.capture_pane_mode(target, mode)
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Command a replayed pane reports, so the parser registry picks the parser
+/// named in the file name. Exports don't record the agent, so anything else is
+/// treated as Claude Code.
+fn detection_command(file_stem: &str) -> String {
+    let lower = file_stem.to_lowercase();
+    AGENT_HINTS
+        .iter()
+        .find(|hint| lower.contains(*hint))
+        .unwrap_or(&"claude")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::AgentType;
+    use crate::parsers::ParserRegistry;
+
+    fn replay_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "agentos-replay-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_list_panes_one_per_capture() {
+        let dir = replay_dir(
+            "list",
+            &[
+                ("codex-busy.txt", "codex output"),
+                ("main-2-1-20260304-050607.txt", "claude output"),
+                ("notes.md", "not a capture"),
+            ],
+        );
+        let source = ReplaySource::new(&dir);
+        let registry = ParserRegistry::default();
+
+        let panes = source.list_panes().unwrap();
+        assert_eq!(panes.len(), 2);
+        assert_eq!(panes[0].target(), "replay:0.0");
+        assert_eq!(panes[0].window_name, "codex-busy");
+        let types: Vec<AgentType> = panes
+            .iter()
+            .map(|pane| registry.find_parser_for_pane(pane).unwrap().agent_type())
+            .collect();
+        // Exports carry no agent name and fall back to Claude Code
+        assert_eq!(types, vec![AgentType::CodexCli, AgentType::ClaudeCode]);
+
+        assert_eq!(
+            source
+                .capture_pane_mode("replay:1.0", CaptureMode::Scrollback)
+                .unwrap(),
+            "claude output"
+        );
+        assert!(source
+            .capture_pane_mode("replay:5.0", CaptureMode::Scrollback)
+            .is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_directory_is_an_error() {
+        let source = ReplaySource::new("/nonexistent/agentos-replay");
+        assert!(source.list_panes().is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::agents::{format_elapsed, AgentStatus, MonitoredAgent};
+use crate::parsers::ParserRegistry;
+use crate::tmux::{process_uptimes, TmuxClient};
+
+use super::replay::PaneSource;
+use super::task::MonitorTask;
+
+/// Poll interval handed to the one-shot monitor; it never sleeps on it
+const SNAPSHOT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Runs one monitor poll over `pane_source` with the built-in parsers and
+/// returns the agents found, sorted by target. Used by `dx list`.
+pub async fn poll_agents(pane_source: Arc<dyn PaneSource>) -> anyhow::Result<Vec<MonitoredAgent>> {
+    // The task needs channels, but nothing is sent on them for a single poll
+    let (tx, _rx) = mpsc::channel(1);
+    let (_factory_tx, factory_rx) = mpsc::channel(1);
+
+    let mut monitor = MonitorTask::new(
+        Arc::new(TmuxClient::new()),
+        Arc::new(ParserRegistry::new(&[])),
+        None,
+        tx,
+        factory_rx,
+        SNAPSHOT_POLL_INTERVAL,
+    )
+    .with_pane_source(pane_source);
+    monitor.poll_once().await
+}
+
+/// `agents` as pretty JSON, for `dx list --json`.
+///
+/// Lets scripts check agent state without the TUI, e.g.
+/// `dx list --json | jq '.[] | select(.status.state == "awaiting_approval")'`.
+pub fn json_snapshot(agents: &[MonitoredAgent]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(agents)
+}
+
+/// `agents` as a plain-text table, one agent per line.
+///
+/// Columns are separated by spaces and no field contains one, so
+/// `dx list | awk '$3 ~ /^approval/ {print $1}'` works.
+pub fn list_snapshot(agents: &[MonitoredAgent]) -> String {
+    // A single poll has only just seen the agents, so their own uptime would
+    // always be 0s; the pane process knows how long it has run
+    let pids: Vec<u32> = agents.iter().map(|agent| agent.pid).collect();
+    format_agent_table(agents, &process_uptimes(&pids))
+}
+
+/// Header line plus one line per agent: target, type, status, uptime, context.
+/// Uptime comes from `uptimes` by pid and is `-` for an agent not in it.
+fn format_agent_table(agents: &[MonitoredAgent], uptimes: &HashMap<u32, Duration>) -> String {
+    let rows: Vec<[String; 5]> = agents
+        .iter()
+        .map(|agent| {
+            [
+                agent.target.clone(),
+                table_word(agent.agent_type.short_name()),
+                status_token(&agent.status),
+                uptimes
+                    .get(&agent.pid)
+                    .map(|uptime| format_elapsed(uptime.as_secs()))
+                    .unwrap_or_else(|| "-".to_string()),
+                agent
+                    .context_remaining
+                    .map(|pct| format!("{}%", pct))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    format_table(&rows)
+}
+
+/// `text` lowercased as one table field: a custom agent name like "My Bot"
+/// becomes `my-bot`, so no field contains a space
+fn table_word(text: &str) -> String {
+    let word = text.split_whitespace().collect::<Vec<_>>().join("-");
+    if word.is_empty() {
+        "-".to_string()
+    } else {
+        word.to_lowercase()
+    }
+}
+
+/// Single-word status for the table, e.g. `approval:shell`
+fn status_token(status: &AgentStatus) -> String {
+    match status {
+        AgentStatus::Idle => "idle".to_string(),
+        AgentStatus::Processing { .. } => "processing".to_string(),
+        AgentStatus::AwaitingApproval { approval_type, .. } => {
+            format!("approval:{}", approval_type.short_desc().to_lowercase())
+        }
+        AgentStatus::Error { .. } => "error".to_string(),
+        AgentStatus::Unknown => "unknown".to_string(),
+    }
+}
+
+/// Header line plus `rows`, padded into columns two spaces apart
+fn format_table(rows: &[[String; 5]]) -> String {
+    let header = ["TARGET", "TYPE", "STATUS", "UPTIME", "CONTEXT"].map(String::from);
+    let mut widths = header.clone().map(|h| h.len());
+    for row in rows {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(field, width)| format!("{:<width$}", field, width = width))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{AgentType, ApprovalType};
+
+    fn agent(target: &str, agent_type: AgentType, status: AgentStatus) -> MonitoredAgent {
+        MonitoredAgent {
+            agent_type,
+            status,
+            ..MonitoredAgent::for_test(target)
+        }
+    }
+
+    #[test]
+    fn test_format_agent_table() {
+        let mut busy = agent(
+            "main:0.0",
+            AgentType::ClaudeCode,
+            AgentStatus::Processing {
+                activity: "Reading files".to_string(),
+            },
+        );
+        busy.context_remaining = Some(42);
+        let mut waiting = agent(
+            "work:12.3",
+            AgentType::CodexCli,
+            AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "cargo test --workspace".to_string(),
+            },
+        );
+
+        waiting.pid = 2;
+        let uptimes = HashMap::from([(1, Duration::from_secs(150))]);
+
+        let table = format_agent_table(&[busy, waiting], &uptimes);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "TARGET     TYPE    STATUS          UPTIME  CONTEXT",
+                "main:0.0   claude  processing      2m      42%",
+                "work:12.3  codex   approval:shell  -       -",
+            ]
+        );
+        // Every row splits into the same five fields
+        assert!(lines
+            .iter()
+            .all(|line| line.split_whitespace().count() == 5));
+
+        assert_eq!(
+            format_agent_table(&[], &HashMap::new()),
+            "TARGET  TYPE  STATUS  UPTIME  CONTEXT\n"
+        );
+    }
+
+    #[test]
+    fn test_custom_agent_name_stays_one_field() {
+        let custom = agent(
+            "main:0.2",
+            AgentType::Custom(" Acme  Code Bot ".to_string()),
+            AgentStatus::Idle,
+        );
+        let table = format_agent_table(&[custom], &HashMap::new());
+        let row = table.lines().nth(1).unwrap();
+        assert_eq!(
+            row.split_whitespace().collect::<Vec<_>>(),
+            vec!["main:0.2", "acme-code-bot", "idle", "-", "-"]
+        );
+        assert_eq!(table_word(" "), "-");
+    }
+
+    #[test]
+    fn test_json_snapshot() {
+        let waiting = agent(
+            "work:12.3",
+            AgentType::CodexCli,
+            AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "cargo test --workspace".to_string(),
+            },
+        );
+
+        let json = json_snapshot(&[waiting]).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["target"], "work:12.3");
+        assert_eq!(parsed[0]["status"]["state"], "awaiting_approval");
+        assert_eq!(json_snapshot(&[]).unwrap(), "[]");
+    }
+}
@@ -1,9 +1,47 @@
+use std::collections::VecDeque;
 use std::time::Instant;
 use sysinfo::System;
 
 /// Number of CPU history samples to keep for sparkline
 const CPU_HISTORY_LEN: usize = 30;
 
+/// Number of samples kept for the stats graph panel (~1 minute at one per second)
+pub const STATS_HISTORY_LEN: usize = 60;
+
+/// One reading of CPU, memory and load for the stats graph panel
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatsSample {
+    /// CPU usage percentage (0-100)
+    pub cpu: f32,
+    /// Memory usage percentage (0-100)
+    pub memory_percent: f32,
+    /// One-minute load average (0 where the platform doesn't report one)
+    pub load: f64,
+}
+
+/// Run-queue load averaged over 1, 5 and 15 minutes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+impl LoadAverage {
+    /// The current load, or None where the platform doesn't report one (Windows)
+    fn read() -> Option<Self> {
+        if cfg!(windows) {
+            return None;
+        }
+        let load = System::load_average();
+        Some(Self {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        })
+    }
+}
+
 /// System resource statistics
 #[derive(Debug, Clone)]
 pub struct SystemStats {
@@ -15,6 +53,9 @@ pub struct SystemStats {
     pub memory_total: u64,
     /// CPU usage history for sparkline (oldest → newest)
     pub cpu_history: Vec<f32>,
+    load_avg: Option<LoadAverage>,
+    /// Usage percentage (0-100) of each logical core
+    core_usages: Vec<f32>,
     /// Last update time
     last_update: Instant,
 }
@@ -26,6 +67,8 @@ impl Default for SystemStats {
             memory_used: 0,
             memory_total: 0,
             cpu_history: Vec::with_capacity(CPU_HISTORY_LEN),
+            load_avg: None,
+            core_usages: Vec::new(),
             last_update: Instant::now(),
         }
     }
@@ -37,6 +80,26 @@ impl SystemStats {
         Self::default()
     }
 
+    /// Load average, if the platform reports one
+    pub fn load_avg(&self) -> Option<LoadAverage> {
+        self.load_avg
+    }
+
+    /// Usage percentage (0-100) of each logical core, in core order
+    pub fn core_usages(&self) -> &[f32] {
+        &self.core_usages
+    }
+
+    /// Stats with the given load and per-core usage, for rendering tests
+    #[cfg(test)]
+    pub fn with_load(load_avg: Option<LoadAverage>, core_usages: Vec<f32>) -> Self {
+        Self {
+            load_avg,
+            core_usages,
+            ..Self::default()
+        }
+    }
+
     /// Memory usage percentage (0-100)
     pub fn memory_percent(&self) -> f32 {
         if self.memory_total == 0 {
@@ -84,6 +147,8 @@ impl SystemStats {
 pub struct SystemStatsCollector {
     system: System,
     stats: SystemStats,
+    /// Samples for the stats graph (oldest → newest), at most STATS_HISTORY_LEN
+    history: VecDeque<StatsSample>,
 }
 
 impl SystemStatsCollector {
@@ -98,10 +163,18 @@ impl SystemStatsCollector {
             memory_used: system.used_memory(),
             memory_total: system.total_memory(),
             cpu_history: vec![cpu],
+            load_avg: LoadAverage::read(),
+            core_usages: core_usages(&system),
             last_update: Instant::now(),
         };
 
-        Self { system, stats }
+        let mut collector = Self {
+            system,
+            stats,
+            history: VecDeque::with_capacity(STATS_HISTORY_LEN),
+        };
+        collector.record_sample();
+        collector
     }
 
     /// Refresh statistics (throttled to avoid excessive updates)
@@ -115,19 +188,43 @@ impl SystemStatsCollector {
             self.stats.cpu_usage = self.system.global_cpu_usage();
             self.stats.memory_used = self.system.used_memory();
             self.stats.memory_total = self.system.total_memory();
+            self.stats.load_avg = LoadAverage::read();
+            self.stats.core_usages = core_usages(&self.system);
             // Push to history, keep bounded
             self.stats.cpu_history.push(self.stats.cpu_usage);
             if self.stats.cpu_history.len() > CPU_HISTORY_LEN {
                 self.stats.cpu_history.remove(0);
             }
             self.stats.last_update = Instant::now();
+            self.record_sample();
         }
     }
 
+    /// Appends the current readings to the graph history, dropping the oldest when full
+    fn record_sample(&mut self) {
+        if self.history.len() == STATS_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(StatsSample {
+            cpu: self.stats.cpu_usage,
+            memory_percent: self.stats.memory_percent(),
+            load: self.stats.load_avg.map_or(0.0, |load| load.one),
+        });
+    }
+
     /// Get current stats snapshot
     pub fn stats(&self) -> &SystemStats {
         &self.stats
     }
+
+    /// Recent samples for the stats graph, oldest first
+    pub fn system_stats_history(&self) -> &VecDeque<StatsSample> {
+        &self.history
+    }
+}
+
+fn core_usages(system: &System) -> Vec<f32> {
+    system.cpus().iter().map(|cpu| cpu.cpu_usage()).collect()
 }
 
 impl Default for SystemStatsCollector {
@@ -135,3 +232,42 @@ impl Default for SystemStatsCollector {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collector_reads_load_and_cores() {
+        let collector = SystemStatsCollector::new();
+        let stats = collector.stats();
+        assert!(!stats.core_usages().is_empty());
+        assert!(stats
+            .core_usages()
+            .iter()
+            .all(|usage| (0.0..=100.0).contains(usage)));
+        if cfg!(windows) {
+            assert_eq!(stats.load_avg(), None);
+        } else {
+            let load = stats.load_avg().unwrap();
+            assert!(load.one >= 0.0 && load.five >= 0.0 && load.fifteen >= 0.0);
+            // The graph samples the one-minute load
+            let sample = collector.system_stats_history().back().unwrap();
+            assert_eq!(sample.load, load.one);
+        }
+    }
+
+    #[test]
+    fn test_with_load_exposes_readings() {
+        let load = LoadAverage {
+            one: 1.5,
+            five: 1.0,
+            fifteen: 0.5,
+        };
+        let stats = SystemStats::with_load(Some(load), vec![10.0, 90.0]);
+        assert_eq!(stats.load_avg(), Some(load));
+        assert_eq!(stats.core_usages(), &[10.0, 90.0]);
+        assert_eq!(SystemStats::new().load_avg(), None);
+        assert!(SystemStats::new().core_usages().is_empty());
+    }
+}
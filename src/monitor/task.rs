@@ -2,31 +2,57 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, warn};
 
 use crate::hub_client::{HubClient, QueueTask, AlertsResponse, AnalyticsDigest, FactoryRequest};
 use crate::agents::{AgentStatus, MonitoredAgent};
-use crate::app::AgentTree;
 use crate::parsers::ParserRegistry;
 use crate::state_reader::DashboardData;
-use crate::tmux::{refresh_process_cache, TmuxClient};
+use crate::tmux::{refresh_process_cache, CaptureMode, TmuxClient};
+
+use super::activity::{resolve_activity, ActivitySource};
+use super::adaptive_poll::{
+    any_agent_active, next_poll_interval, retry_delay, DASHBOARD_FETCH_ATTEMPTS,
+    DEFAULT_MAX_IDLE_POLL_MS,
+};
+use super::branch_cache::BranchCache;
+use super::capture::AgentCaptureLines;
+use super::hub_merge::merge_hub_agent;
+use super::parse_cache::ParseCache;
+use super::poll_stats::PollStats;
+use super::replay::PaneSource;
+use super::tree_diff::{closed_agents, newly_pending, status_transitions, ClosedAgent, StatusTransition};
 
 /// Hysteresis duration - keep "Processing" status for this long after last active detection
 const STATUS_HYSTERESIS_MS: u64 = 2000;
 
+/// Transition events buffered per subscriber before the slowest one starts lagging
+const TRANSITION_CHANNEL_CAPACITY: usize = 256;
+
 /// Command sent from TUI to monitor for async execution
 #[derive(Debug)]
 pub enum FactoryCommand {
     Submit { request: String },
+    /// Cancel a queue task by id
+    CancelTask { id: String },
+    /// Change a queue task's priority
+    SetTaskPriority { id: String, priority: u8 },
+    /// Drop the hub backoff so the next poll calls the API again
+    Reconnect,
 }
 
 /// Update message sent from monitor to UI
 #[derive(Debug, Clone)]
 pub struct MonitorUpdate {
-    pub agents: AgentTree,
+    /// Agents found this poll, sorted by target
+    pub agents: Vec<MonitoredAgent>,
     pub queue_tasks: Vec<QueueTask>,
     pub hub_connected: bool,
+    /// Round-trip time of the last successful hub status request
+    pub hub_latency: Option<Duration>,
+    /// Consecutive failed hub requests; 0 while connected
+    pub hub_fail_count: u32,
     /// Flash message for connection state changes
     pub flash: Option<String>,
     /// 24h analytics digest (fetched on slow cadence)
@@ -37,25 +63,64 @@ pub struct MonitorUpdate {
     pub dashboard: Option<DashboardData>,
     /// Factory pipeline requests (fetched on slow cadence)
     pub factory_requests: Option<Vec<FactoryRequest>>,
+    /// Tmux agents that disappeared since the previous poll
+    pub closed_agents: Vec<ClosedAgent>,
+    /// Timing and parser diagnostics for this poll
+    pub poll_stats: PollStats,
+    /// Targets that started needing attention since the previous poll
+    pub newly_pending: Vec<String>,
+    /// Targets whose process was replaced (PID changed) since the previous poll
+    pub restarted: Vec<String>,
+    /// Status changes detected since the previous update, oldest first
+    pub transitions: Vec<StatusTransition>,
 }
 
 /// Background task that monitors tmux panes and hub API for AI agents
 pub struct MonitorTask {
-    tmux_client: Arc<TmuxClient>,
+    /// Where panes are listed and captured from (tmux unless replaying)
+    pane_source: Arc<dyn PaneSource>,
     parser_registry: Arc<ParserRegistry>,
     hub_client: Option<HubClient>,
     tx: mpsc::Sender<MonitorUpdate>,
     factory_rx: mpsc::Receiver<FactoryCommand>,
+    /// Fastest poll rate, used whenever an agent is active
     poll_interval: Duration,
+    /// Slowest poll rate, reached while every agent is idle
+    max_idle_poll: Duration,
+    /// Interval before the next poll
+    current_interval: Duration,
+    /// Per-agent-type scrollback overrides for pane captures
+    agent_capture_lines: AgentCaptureLines,
     /// Track when each agent was last seen as "active" (Processing/AwaitingApproval)
     /// Key: agent target string
     last_active: HashMap<String, Instant>,
     /// Consecutive API failures for exponential backoff
     api_fail_count: u32,
+    /// Round-trip time of the last successful `fetch_panes`
+    hub_latency: Option<Duration>,
     /// Whether API was connected last poll (for detecting transitions)
     was_connected: bool,
     /// Counter for slow-cadence analytics polling
     analytics_counter: u32,
+    /// Tmux agents seen in the previous poll (for detecting closed agents)
+    previous_tmux_agents: Vec<MonitoredAgent>,
+    /// Agents closed since the last update was sent
+    closed_since_update: Vec<ClosedAgent>,
+    /// Agents that started needing attention since the last update was sent
+    pending_since_update: Vec<String>,
+    /// Agents whose pane process was replaced since the last update was sent
+    restarted_since_update: Vec<String>,
+    /// Status changes detected since the last update was sent
+    transitions_since_update: Vec<StatusTransition>,
+    /// Diagnostics for the poll in progress
+    poll_stats: PollStats,
+    /// Where Processing activity text comes from
+    activity_source: ActivitySource,
+    /// Parse results keyed by target, reused while pane content is unchanged
+    parse_cache: ParseCache,
+    branch_cache: BranchCache,
+    /// Status transition events for external consumers (socket, webhooks, metrics)
+    transitions_tx: broadcast::Sender<StatusTransition>,
 }
 
 impl MonitorTask {
@@ -68,25 +133,71 @@ impl MonitorTask {
         poll_interval: Duration,
     ) -> Self {
         Self {
-            tmux_client,
+            pane_source: tmux_client,
             parser_registry,
             hub_client,
             tx,
             factory_rx,
             poll_interval,
+            max_idle_poll: Duration::from_millis(DEFAULT_MAX_IDLE_POLL_MS),
+            agent_capture_lines: AgentCaptureLines::default(),
+            current_interval: poll_interval,
             last_active: HashMap::new(),
             api_fail_count: 0,
+            hub_latency: None,
             was_connected: false,
             analytics_counter: 0,
+            previous_tmux_agents: Vec::new(),
+            closed_since_update: Vec::new(),
+            pending_since_update: Vec::new(),
+            restarted_since_update: Vec::new(),
+            transitions_since_update: Vec::new(),
+            poll_stats: PollStats::default(),
+            activity_source: ActivitySource::default(),
+            parse_cache: ParseCache::default(),
+            branch_cache: BranchCache::default(),
+            transitions_tx: broadcast::channel(TRANSITION_CHANNEL_CAPACITY).0,
         }
     }
 
+    /// Sets where Processing activity text comes from
+    pub fn with_activity_source(mut self, source: ActivitySource) -> Self {
+        self.activity_source = source;
+        self
+    }
+
+    /// Lists and captures panes from `source` instead of tmux, e.g. a `ReplaySource`
+    pub fn with_pane_source(mut self, source: Arc<dyn PaneSource>) -> Self {
+        self.pane_source = source;
+        self
+    }
+
+    /// Sets the slowest poll interval used while all agents are idle
+    pub fn with_max_idle_poll(mut self, max_idle_poll: Duration) -> Self {
+        self.max_idle_poll = max_idle_poll;
+        self
+    }
+
+    /// Captures more or less scrollback for the agent types `lines` names
+    pub fn with_agent_capture_lines(mut self, lines: AgentCaptureLines) -> Self {
+        self.agent_capture_lines = lines;
+        self
+    }
+
+    /// Returns a receiver for status transition events. Subscribe before `run()`
+    /// consumes the task; events sent while nobody listens are dropped.
+    pub fn subscribe_transitions(&self) -> broadcast::Receiver<StatusTransition> {
+        self.transitions_tx.subscribe()
+    }
+
     /// Runs the monitoring loop
     pub async fn run(mut self) {
+        // A command that cut the last sleep short; handled before the queued ones
+        let mut woken_by: Option<FactoryCommand> = None;
         loop {
             // Process any pending factory commands (non-blocking drain)
             let mut flash_from_factory: Option<String> = None;
-            while let Ok(cmd) = self.factory_rx.try_recv() {
+            while let Some(cmd) = woken_by.take().or_else(|| self.factory_rx.try_recv().ok()) {
                 match cmd {
                     FactoryCommand::Submit { request } => {
                         if let Some(ref client) = self.hub_client {
@@ -107,16 +218,50 @@ impl MonitorTask {
                                 Some("Factory: Hub not connected".to_string());
                         }
                     }
+                    FactoryCommand::CancelTask { id } => {
+                        flash_from_factory = Some(match self.hub_client {
+                            Some(ref client) => match client.cancel_task(&id).await {
+                                Ok(()) => format!("Queue: cancelled {}", id),
+                                Err(e) => format!("Queue error: {}", e),
+                            },
+                            None => "Queue: Hub not connected".to_string(),
+                        });
+                    }
+                    FactoryCommand::SetTaskPriority { id, priority } => {
+                        flash_from_factory = Some(match self.hub_client {
+                            Some(ref client) => match client.set_priority(&id, priority).await {
+                                Ok(()) => format!("Queue: {} is now P{}", id, priority),
+                                Err(e) => format!("Queue error: {}", e),
+                            },
+                            None => "Queue: Hub not connected".to_string(),
+                        });
+                    }
+                    FactoryCommand::Reconnect => {
+                        self.api_fail_count = 0;
+                        self.current_interval = self.poll_interval;
+                    }
                 }
             }
 
-            let (tree, queue_tasks, connected) = match self.poll_all().await {
+            let poll_start = Instant::now();
+            self.poll_stats = PollStats::default();
+            let (agents, queue_tasks, connected) = match self.poll_all().await {
                 Ok(result) => result,
                 Err(e) => {
                     warn!("Monitor poll error: {}", e);
-                    (AgentTree::new(), Vec::new(), false)
+                    (Vec::new(), Vec::new(), false)
                 }
             };
+            self.poll_stats.poll_duration = poll_start.elapsed();
+
+            // Poll slower while nothing is happening; a factory submit counts as activity
+            let active = any_agent_active(&agents) || flash_from_factory.is_some();
+            self.current_interval = next_poll_interval(
+                self.current_interval,
+                self.poll_interval,
+                self.max_idle_poll,
+                active,
+            );
 
             // Detect connection state transitions
             let flash = if let Some(msg) = flash_from_factory {
@@ -139,15 +284,31 @@ impl MonitorTask {
             let mut factory_requests = None;
             if connected && self.analytics_counter % 10 == 0 {
                 if let Some(ref client) = self.hub_client {
-                    // Single /api/dashboard call returns everything including digest + alerts
-                    match client.fetch_dashboard().await {
-                        Ok(result) => {
-                            dashboard = Some(result.dashboard);
-                            digest = Some(result.digest);
-                            alerts = Some(result.alerts);
-                        }
-                        Err(e) => {
-                            debug!("Dashboard fetch failed: {}", e);
+                    // Single /api/dashboard call returns everything including digest + alerts.
+                    // Retried once after a jittered pause; on failure the fields stay None
+                    // and the UI keeps the last good dashboard.
+                    let mut attempt = 0;
+                    loop {
+                        attempt += 1;
+                        match client.fetch_dashboard().await {
+                            Ok(result) => {
+                                dashboard = Some(result.dashboard);
+                                digest = Some(result.digest);
+                                alerts = Some(result.alerts);
+                                break;
+                            }
+                            Err(e) if attempt < DASHBOARD_FETCH_ATTEMPTS => {
+                                debug!("Dashboard fetch failed (attempt {}), retrying: {}", attempt, e);
+                                let seed = std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .unwrap_or_default()
+                                    .subsec_nanos();
+                                tokio::time::sleep(retry_delay(attempt, seed)).await;
+                            }
+                            Err(e) => {
+                                debug!("Dashboard fetch failed: {}", e);
+                                break;
+                            }
                         }
                     }
                     // Fetch factory pipeline status
@@ -163,27 +324,45 @@ impl MonitorTask {
             }
 
             let update = MonitorUpdate {
-                agents: tree,
+                agents,
                 queue_tasks,
                 hub_connected: connected,
+                hub_latency: self.hub_latency,
+                hub_fail_count: self.api_fail_count,
                 flash,
                 digest,
                 alerts,
                 dashboard,
                 factory_requests,
+                closed_agents: std::mem::take(&mut self.closed_since_update),
+                poll_stats: std::mem::take(&mut self.poll_stats),
+                newly_pending: std::mem::take(&mut self.pending_since_update),
+                restarted: std::mem::take(&mut self.restarted_since_update),
+                transitions: std::mem::take(&mut self.transitions_since_update),
             };
             if self.tx.send(update).await.is_err() {
                 debug!("Monitor channel closed, stopping");
                 break;
             }
 
-            tokio::time::sleep(self.poll_interval).await;
+            // A command ends the sleep early, so e.g. a reconnect polls right away
+            // instead of waiting out the backoff interval
+            tokio::select! {
+                _ = tokio::time::sleep(self.current_interval) => {}
+                Some(cmd) = self.factory_rx.recv() => woken_by = Some(cmd),
+            }
         }
     }
 
-    async fn poll_all(&mut self) -> anyhow::Result<(AgentTree, Vec<QueueTask>, bool)> {
+    /// Runs a single poll cycle without sending an update (`dx list`)
+    pub async fn poll_once(&mut self) -> anyhow::Result<Vec<MonitoredAgent>> {
+        let (agents, _, _) = self.poll_all().await?;
+        Ok(agents)
+    }
+
+    async fn poll_all(&mut self) -> anyhow::Result<(Vec<MonitoredAgent>, Vec<QueueTask>, bool)> {
         // Poll tmux agents
-        let mut tree = self.poll_tmux_agents().await?;
+        let mut agents = self.poll_tmux_agents().await?;
 
         // Poll Hub (if configured)
         let mut queue_tasks = Vec::new();
@@ -202,27 +381,24 @@ impl MonitorTask {
                     .is_multiple_of(backoff_polls);
 
             if !should_try_api {
-                return Ok((tree, queue_tasks, false));
+                return Ok((agents, queue_tasks, false));
             }
 
             // Fetch panes from Hub
-            match client.fetch_panes().await {
+            let api_start = Instant::now();
+            let panes_result = client.fetch_panes().await;
+            self.poll_stats.api_latency = Some(api_start.elapsed());
+            match panes_result {
                 Ok(panes) => {
                     connected = true;
+                    self.hub_latency = self.poll_stats.api_latency;
                     for pane in &panes {
                         // Show panes that have a real project or are actively running
                         let has_project = pane.project != "--" && !pane.project.is_empty();
                         let is_active = pane.pty_running || pane.status == "active";
                         if has_project || is_active {
-                            let agent = HubClient::pane_to_agent(pane);
-                            // Only add if not already detected via tmux
-                            let already_exists = tree
-                                .root_agents
-                                .iter()
-                                .any(|a| a.path == agent.path && a.agent_type == agent.agent_type);
-                            if !already_exists {
-                                tree.root_agents.push(agent);
-                            }
+                            // Panes also seen via tmux are merged into the tmux agent
+                            merge_hub_agent(&mut agents, HubClient::pane_to_agent(pane));
                         }
                     }
                 }
@@ -247,36 +423,60 @@ impl MonitorTask {
         }
 
         // Sort agents by target for consistent ordering
-        tree.root_agents.sort_by(|a, b| a.target.cmp(&b.target));
+        agents.sort_by(|a, b| a.target.cmp(&b.target));
 
-        Ok((tree, queue_tasks, connected))
+        Ok((agents, queue_tasks, connected))
     }
 
-    async fn poll_tmux_agents(&mut self) -> anyhow::Result<AgentTree> {
+    async fn poll_tmux_agents(&mut self) -> anyhow::Result<Vec<MonitoredAgent>> {
         // Refresh process cache once per poll cycle (much faster than per-pane)
         refresh_process_cache();
 
-        let panes = self.tmux_client.list_panes()?;
-        let mut tree = AgentTree::new();
+        let panes = self.pane_source.list_panes()?;
+        // Every listed pane, so an agent that exited to a shell can be told from a closed pane
+        let pane_targets: Vec<String> = panes.iter().map(|pane| pane.target()).collect();
+        let mut agents = Vec::new();
 
         for pane in panes {
             // Try to find a matching parser for the pane (checks command, title, cmdline)
             if let Some(parser) = self.parser_registry.find_parser_for_pane(&pane) {
                 let target = pane.target();
 
-                // Capture pane content
-                let content = match self.tmux_client.capture_pane(&target) {
+                // Capture pane content (alternate-screen apps have no useful history)
+                let mode = CaptureMode::for_pane(pane.alternate_on);
+                let capture_start = Instant::now();
+                let captured = match self.agent_capture_lines.for_agent(&parser.agent_type()) {
+                    Some(lines) => self.pane_source.capture_pane_lines(&target, mode, lines),
+                    None => self.pane_source.capture_pane_mode(&target, mode),
+                };
+                let content = match captured {
                     Ok(c) => c,
                     Err(e) => {
                         error!("Failed to capture pane {}: {}", target, e);
+                        // The pane is still listed, so keep the last poll's agent
+                        // rather than reporting it closed or exited
+                        if let Some(previous) = self
+                            .previous_tmux_agents
+                            .iter()
+                            .find(|p| p.target == target)
+                        {
+                            agents.push(previous.clone());
+                        }
                         continue;
                     }
                 };
+                self.poll_stats
+                    .record_pane(&target, parser.agent_name(), capture_start.elapsed());
 
-                // Parse status from content
-                let mut status = parser.parse_status(&content);
+                // Parse status from content (skipped when the capture hasn't changed)
+                let (parsed, cache_hit) = self.parse_cache.parse(&target, &content, parser);
+                if cache_hit {
+                    self.poll_stats.record_cache_hit();
+                }
+                let mut status = parsed.status;
 
-                // Check pane title for spinner (Claude Code specific)
+                // Check pane title for spinner (Claude Code specific), for when its
+                // "esc to interrupt" hint isn't on screen for the parser to see
                 let title_has_spinner = pane.title.chars().any(|c| {
                     matches!(
                         c,
@@ -328,15 +528,19 @@ impl MonitorTask {
                     }
                 }
 
-                // Parse subagents
-                let subagents = parser.parse_subagents(&content);
-
-                // Parse context remaining
-                let context_remaining = parser.parse_context_remaining(&content);
+                // Prefer what the agent says it is doing over the generic "Working..."
+                if let AgentStatus::Processing { activity } = &mut status {
+                    *activity = resolve_activity(
+                        self.activity_source,
+                        activity,
+                        parsed.activity,
+                    );
+                }
 
                 // Create monitored agent
+                // Identity is the pane, not the process, so a respawn keeps uptime
                 let mut agent = MonitoredAgent::new(
-                    format!("{}-{}", target, pane.pid),
+                    target.clone(),
                     target,
                     pane.session.clone(),
                     pane.window,
@@ -347,15 +551,145 @@ impl MonitorTask {
                     pane.pid,
                 );
                 agent.status = status;
-                agent.subagents = subagents;
+                agent.subagents = parsed.subagents;
                 agent.last_content = content;
-                agent.context_remaining = context_remaining;
+                agent.context_remaining = parsed.context_remaining;
+                agent.model = parsed.model;
+                agent.branch = self.branch_cache.branch(&agent.path);
                 agent.touch();
+                let previous =
+                    self.previous_tmux_agents.iter().find(|p| p.target == agent.target);
+                if let Some(previous) = previous {
+                    if agent.carry_over_from(previous) {
+                        debug!(
+                            "Agent {} restarted (pid {} -> {})",
+                            agent.target, previous.pid, agent.pid
+                        );
+                        self.restarted_since_update.push(agent.target.clone());
+                    }
+                }
+                agent.track_awaiting(previous);
+                agent.track_content_change(previous);
 
-                tree.root_agents.push(agent);
+                agents.push(agent);
+            } else {
+                self.poll_stats.record_unmatched();
             }
         }
 
-        Ok(tree)
+        // Remember agents that vanished since the last poll so the UI can show them briefly
+        self.closed_since_update.extend(closed_agents(
+            &self.previous_tmux_agents,
+            &agents,
+            &pane_targets,
+        ));
+        self.pending_since_update
+            .extend(newly_pending(&self.previous_tmux_agents, &agents));
+        for transition in status_transitions(&self.previous_tmux_agents, &agents) {
+            // Err only means there are no subscribers right now
+            let _ = self.transitions_tx.send(transition.clone());
+            self.transitions_since_update.push(transition);
+        }
+        self.previous_tmux_agents = agents.clone();
+
+        // Forget cached parses for panes that are gone
+        let live: Vec<String> = agents.iter().map(|a| a.target.clone()).collect();
+        self.parse_cache.retain_targets(&live);
+        let paths: Vec<&str> = agents.iter().map(|a| a.path.as_str()).collect();
+        self.branch_cache.retain_paths(&paths);
+
+        Ok(agents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use anyhow::{anyhow, Result};
+
+    use super::*;
+    use crate::tmux::PaneInfo;
+
+    /// One Claude Code pane whose capture fails while `fail` is set
+    #[derive(Default)]
+    struct FlakySource {
+        fail: AtomicBool,
+    }
+
+    impl PaneSource for FlakySource {
+        fn list_panes(&self) -> Result<Vec<PaneInfo>> {
+            Ok(vec![PaneInfo {
+                session: "main".to_string(),
+                command: "claude".to_string(),
+                path: "/home/user/project".to_string(),
+                pid: 42,
+                ..PaneInfo::default()
+            }])
+        }
+
+        fn capture_pane_mode(&self, target: &str, _mode: CaptureMode) -> Result<String> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(anyhow!("capture of {} timed out", target));
+            }
+            Ok("Do you want to proceed?\n❯ 1. Yes\n  2. No\n".to_string())
+        }
+
+        fn capture_pane_lines(
+            &self,
+            target: &str,
+            mode: CaptureMode,
+            _lines: u32,
+        ) -> Result<String> {
+            self.capture_pane_mode(target, mode)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_capture_keeps_agent() {
+        let source = Arc::new(FlakySource::default());
+        let (tx, _rx) = mpsc::channel(1);
+        let (_factory_tx, factory_rx) = mpsc::channel(1);
+        let mut monitor = MonitorTask::new(
+            Arc::new(TmuxClient::new()),
+            Arc::new(ParserRegistry::new(&[])),
+            None,
+            tx,
+            factory_rx,
+            Duration::from_millis(500),
+        )
+        .with_pane_source(source.clone());
+
+        let first = monitor.poll_once().await.unwrap();
+        assert_eq!(first.len(), 1);
+
+        source.fail.store(true, Ordering::SeqCst);
+        let second = monitor.poll_once().await.unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].status, first[0].status);
+        assert!(monitor.closed_since_update.is_empty());
+        assert!(monitor.transitions_since_update.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_polls_without_waiting() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let (factory_tx, factory_rx) = mpsc::channel(1);
+        let monitor = MonitorTask::new(
+            Arc::new(TmuxClient::new()),
+            Arc::new(ParserRegistry::new(&[])),
+            None,
+            tx,
+            factory_rx,
+            Duration::from_secs(60),
+        )
+        .with_pane_source(Arc::new(FlakySource::default()));
+        tokio::spawn(monitor.run());
+
+        rx.recv().await.unwrap();
+        factory_tx.send(FactoryCommand::Reconnect).await.unwrap();
+        // Without the wake-up the next update would only come after the 60s sleep
+        let update = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await;
+        assert!(update.unwrap().is_some());
     }
 }
@@ -0,0 +1,284 @@
+use std::mem::discriminant;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::agents::{AgentStatus, AgentType, MonitoredAgent};
+
+/// How long a closed agent stays in the "recently closed" list
+pub const RECENTLY_CLOSED_TTL: Duration = Duration::from_secs(10);
+
+/// An agent that was present in the previous poll but is gone now
+#[derive(Debug, Clone)]
+pub struct ClosedAgent {
+    /// Tmux target the agent was running in
+    pub target: String,
+    /// Abbreviated working directory, for display
+    pub path: String,
+    /// Type of the agent
+    pub agent_type: AgentType,
+    /// Last status seen before the agent disappeared
+    pub final_status: AgentStatus,
+    /// The pane is still open but no longer runs an agent: the agent exited or
+    /// crashed back to a shell, rather than the pane being closed
+    pub exited: bool,
+    /// When the disappearance was detected
+    pub closed_at: Instant,
+}
+
+impl ClosedAgent {
+    /// Returns true once the entry has been shown long enough
+    pub fn is_expired(&self) -> bool {
+        self.closed_at.elapsed() >= RECENTLY_CLOSED_TTL
+    }
+}
+
+/// Returns the agents of `previous` whose target no longer appears in `current`.
+/// `live_panes` holds every pane target listed this poll, agent or not.
+pub fn closed_agents(
+    previous: &[MonitoredAgent],
+    current: &[MonitoredAgent],
+    live_panes: &[String],
+) -> Vec<ClosedAgent> {
+    let now = Instant::now();
+    previous
+        .iter()
+        .filter(|prev| !current.iter().any(|a| a.target == prev.target))
+        .map(|prev| ClosedAgent {
+            target: prev.target.clone(),
+            path: prev.abbreviated_path(),
+            agent_type: prev.agent_type.clone(),
+            final_status: prev.status.clone(),
+            exited: live_panes.contains(&prev.target),
+            closed_at: now,
+        })
+        .collect()
+}
+
+/// Returns the targets that went from not needing attention in `previous` to needing it
+/// in `current`. Agents not seen before are skipped, so startup doesn't report everything.
+pub fn newly_pending(previous: &[MonitoredAgent], current: &[MonitoredAgent]) -> Vec<String> {
+    current
+        .iter()
+        .filter(|agent| agent.status.needs_attention())
+        .filter(|agent| {
+            previous
+                .iter()
+                .any(|prev| prev.target == agent.target && !prev.status.needs_attention())
+        })
+        .map(|agent| agent.target.clone())
+        .collect()
+}
+
+/// A change of an agent's status between two consecutive polls
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusTransition {
+    /// Tmux target of the agent
+    pub target: String,
+    /// Status in the previous poll
+    pub from: AgentStatus,
+    /// Status in the current poll
+    pub to: AgentStatus,
+    /// When the change was detected
+    pub at: DateTime<Utc>,
+}
+
+/// Returns the agents whose status kind changed between `previous` and `current`.
+/// Only the variant is compared, so a Processing agent whose activity text changes
+/// produces no event. Agents not seen before are skipped.
+pub fn status_transitions(
+    previous: &[MonitoredAgent],
+    current: &[MonitoredAgent],
+) -> Vec<StatusTransition> {
+    let at = Utc::now();
+    current
+        .iter()
+        .filter_map(|agent| {
+            let prev = previous.iter().find(|p| p.target == agent.target)?;
+            if discriminant(&prev.status) == discriminant(&agent.status) {
+                return None;
+            }
+            Some(StatusTransition {
+                target: agent.target.clone(),
+                from: prev.status.clone(),
+                to: agent.status.clone(),
+                at,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ApprovalType;
+
+    fn agent(target: &str, status: AgentStatus) -> MonitoredAgent {
+        MonitoredAgent {
+            status,
+            ..MonitoredAgent::for_test(target)
+        }
+    }
+
+    #[test]
+    fn test_closed_agents_reports_removed_targets() {
+        let previous = vec![
+            agent("main:0.0", AgentStatus::Idle),
+            agent(
+                "main:0.1",
+                AgentStatus::Processing {
+                    activity: "Building".to_string(),
+                },
+            ),
+        ];
+        let current = vec![agent("main:0.0", AgentStatus::Idle)];
+
+        let closed = closed_agents(&previous, &current, &["main:0.0".to_string()]);
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].target, "main:0.1");
+        assert_eq!(closed[0].path, "/h/u/project");
+        assert!(matches!(
+            closed[0].final_status,
+            AgentStatus::Processing { .. }
+        ));
+        assert!(!closed[0].exited);
+        assert!(!closed[0].is_expired());
+    }
+
+    #[test]
+    fn test_closed_agents_flags_exit_to_shell() {
+        let previous = vec![agent(
+            "main:0.1",
+            AgentStatus::Processing {
+                activity: "Building".to_string(),
+            },
+        )];
+        // The pane is still listed, but nothing in it matched a parser
+        let live = vec!["main:0.1".to_string()];
+        let closed = closed_agents(&previous, &[], &live);
+        assert_eq!(closed.len(), 1);
+        assert!(closed[0].exited);
+    }
+
+    #[test]
+    fn test_closed_agents_empty_when_nothing_removed() {
+        let previous = vec![agent("main:0.0", AgentStatus::Idle)];
+        let current = vec![
+            agent("main:0.0", AgentStatus::Idle),
+            agent("main:0.1", AgentStatus::Idle),
+        ];
+        assert!(closed_agents(&previous, &current, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_closed_agents_all_removed() {
+        let previous = vec![
+            agent("main:0.0", AgentStatus::Idle),
+            agent(
+                "main:1.0",
+                AgentStatus::Error {
+                    message: "panic".to_string(),
+                },
+            ),
+        ];
+        let closed = closed_agents(&previous, &[], &[]);
+        let targets: Vec<&str> = closed.iter().map(|c| c.target.as_str()).collect();
+        assert_eq!(targets, vec!["main:0.0", "main:1.0"]);
+    }
+
+    #[test]
+    fn test_newly_pending_reports_transitions_only() {
+        let approval = || AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "rm -rf target".to_string(),
+        };
+        let previous = vec![
+            agent("main:0.0", AgentStatus::Idle),
+            agent("main:0.1", approval()),
+            agent("main:0.2", AgentStatus::Idle),
+        ];
+        let current = vec![
+            agent("main:0.0", approval()),
+            // Already pending last poll
+            agent("main:0.1", approval()),
+            agent("main:0.2", AgentStatus::Idle),
+            // Not seen before
+            agent("main:0.3", approval()),
+        ];
+        assert_eq!(newly_pending(&previous, &current), vec!["main:0.0"]);
+        assert!(newly_pending(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_status_transitions_pairs_from_and_to() {
+        let previous = vec![
+            agent("main:0.0", AgentStatus::Idle),
+            agent(
+                "main:0.1",
+                AgentStatus::Processing {
+                    activity: "Reading".to_string(),
+                },
+            ),
+            agent(
+                "main:0.2",
+                AgentStatus::Processing {
+                    activity: "Reading".to_string(),
+                },
+            ),
+        ];
+        let current = vec![
+            agent(
+                "main:0.0",
+                AgentStatus::Processing {
+                    activity: "Building".to_string(),
+                },
+            ),
+            agent(
+                "main:0.1",
+                AgentStatus::Error {
+                    message: "panic".to_string(),
+                },
+            ),
+            // Only the activity text changed
+            agent(
+                "main:0.2",
+                AgentStatus::Processing {
+                    activity: "Writing".to_string(),
+                },
+            ),
+            // Not seen before
+            agent("main:0.3", AgentStatus::Idle),
+        ];
+
+        let transitions = status_transitions(&previous, &current);
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].target, "main:0.0");
+        assert_eq!(transitions[0].from, AgentStatus::Idle);
+        assert!(matches!(transitions[0].to, AgentStatus::Processing { .. }));
+        assert_eq!(transitions[1].target, "main:0.1");
+        assert!(matches!(transitions[1].from, AgentStatus::Processing { .. }));
+        assert!(matches!(transitions[1].to, AgentStatus::Error { .. }));
+    }
+
+    #[test]
+    fn test_status_transitions_empty_for_unchanged_tree() {
+        let tree = vec![
+            agent("main:0.0", AgentStatus::Idle),
+            agent("main:0.1", AgentStatus::Unknown),
+        ];
+        assert!(status_transitions(&tree, &tree).is_empty());
+        assert!(status_transitions(&[], &tree).is_empty());
+    }
+
+    #[test]
+    fn test_status_transition_serializes_as_event() {
+        let previous = vec![agent("main:0.0", AgentStatus::Idle)];
+        let current = vec![agent("main:0.0", AgentStatus::Unknown)];
+        let event = serde_json::to_value(&status_transitions(&previous, &current)[0]).unwrap();
+        assert_eq!(event["target"], "main:0.0");
+        assert_eq!(event["from"]["state"], "idle");
+        assert_eq!(event["to"]["state"], "unknown");
+        assert!(event["at"].is_string());
+    }
+}
@@ -21,6 +21,81 @@ fn is_version_like(s: &str) -> bool {
             .unwrap_or(false)
 }
 
+/// Hints Claude Code prints around multi-select questions
+const MULTI_SELECT_HINTS: &[&str] = &[
+    "select all that apply",
+    "select multiple",
+    "multiple selections",
+    "space to select",
+    "space to toggle",
+];
+
+/// Checkbox markers in front of multi-select choices, checked or not
+const CHECKBOXES: &[&str] = &["[ ]", "[x]", "[X]", "[✓]", "[✔]", "☐", "☑", "☒", "◻", "◼"];
+
+/// Check if a line tells the user more than one choice may be picked
+fn is_multi_select_hint(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    MULTI_SELECT_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+/// Returns the choice label without its leading checkbox, or None if it has none
+fn strip_checkbox(label: &str) -> Option<&str> {
+    CHECKBOXES
+        .iter()
+        .find_map(|checkbox| label.strip_prefix(checkbox))
+        .map(str::trim_start)
+}
+
+/// Position of the pane title in the detection strings (command, title, cmdline, children...)
+const TITLE_INDEX: usize = 1;
+
+/// Check if a pane title starts with a braille spinner frame, which Claude Code
+/// sets while it works ("⠐ Refactoring auth")
+fn has_spinner_title(title: &str) -> bool {
+    title
+        .trim_start()
+        .chars()
+        .next()
+        .is_some_and(|c| ('\u{2801}'..='\u{28FF}').contains(&c))
+}
+
+/// Extracts the activity from a Claude Code status line ("✽ Reading files… (3s · esc to interrupt)")
+pub fn parse_activity_line(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('✽') && !trimmed.starts_with('·') {
+        return None;
+    }
+    let activity = trimmed
+        .trim_start_matches('✽')
+        .trim_start_matches('·')
+        .trim();
+    // Extract just the main part (before parentheses with timing info)
+    let main_part = activity.split('(').next().unwrap_or(activity).trim();
+    if main_part.is_empty() {
+        None
+    } else {
+        Some(main_part.to_string())
+    }
+}
+
+/// Lines from the bottom searched for the "esc to interrupt" hint, which sits on
+/// the status line just above the input box
+const INTERRUPT_HINT_LINES: usize = 10;
+
+/// Longest error reason kept in `AgentStatus::Error`
+const ERROR_REASON_MAX_CHARS: usize = 60;
+
+/// Cuts `reason` to `max_chars`, marking the cut with an ellipsis
+fn truncate_reason(reason: &str, max_chars: usize) -> String {
+    if reason.chars().count() <= max_chars {
+        reason.to_string()
+    } else {
+        let cut: String = reason.chars().take(max_chars - 1).collect();
+        format!("{}…", cut)
+    }
+}
+
 /// Parser for Claude Code CLI output
 pub struct ClaudeCodeParser {
     // Approval patterns
@@ -38,6 +113,15 @@ pub struct ClaudeCodeParser {
 
     // Context remaining pattern
     context_pattern: Regex,
+
+    // Model patterns
+    model_banner_pattern: Regex,
+    model_id_pattern: Regex,
+
+    // Error patterns
+    api_error_pattern: Regex,
+    rate_limit_pattern: Regex,
+    panic_pattern: Regex,
 }
 
 impl ClaudeCodeParser {
@@ -82,9 +166,70 @@ impl ClaudeCodeParser {
             context_pattern: Regex::new(
                 r"(?i)Context\s+(?:left|remaining).*?(\d+)%"
             ).expect("invalid regex"),
+
+            // Welcome banner line, e.g. "▝▜█████▛▘  Sonnet 4.5 · Claude Max"
+            model_banner_pattern: Regex::new(
+                r"(?i)\b(opus|sonnet|haiku)\s+(\d+(?:\.\d+)?)\s*·"
+            ).expect("invalid regex"),
+            // Full model id, e.g. "Set model to opus (claude-opus-4-1-20250805)"
+            model_id_pattern: Regex::new(
+                r"(?i)\bclaude-(opus|sonnet|haiku)-(\d+)(?:-(\d))?\b"
+            ).expect("invalid regex"),
+
+            // Error patterns, matched at the start of a line (after the ⏺/⎿ gutter)
+            // e.g. "⎿ API Error: 500 {"type":"error",...}"
+            api_error_pattern: Regex::new(
+                r"^\s*(?:[⏺⎿]\s*)?API Error:?\s*(.+)$"
+            ).expect("invalid regex"),
+            // e.g. "Error: rate_limit_error" or "Rate limit exceeded, retry in 30s"
+            rate_limit_pattern: Regex::new(
+                r"(?i)^\s*(?:[⏺⎿]\s*)?(?:Error:\s*)?(rate[_ ]limit(?:[_ ]error|[_ ]exceeded)?.*)$"
+            ).expect("invalid regex"),
+            // e.g. "thread 'main' panicked at src/main.rs:10:5:", at the start of
+            // a line (or of tool output) so a sentence quoting one doesn't count
+            panic_pattern: Regex::new(
+                r"(?m)^\s*(?:⎿\s*)?thread '([^']*)' panicked at ([^\s:]+(?::\d+)*)"
+            ).expect("invalid regex"),
         }
     }
 
+    /// Looks for a failure printed near the bottom of the pane and returns a short reason
+    fn detect_error(&self, content: &str) -> Option<String> {
+        let lines: Vec<&str> = content.lines().collect();
+        // Only recent output counts; an error that scrolled away was already handled
+        let check_start = lines.len().saturating_sub(15);
+
+        for line in lines[check_start..].iter().rev() {
+            let reason = if let Some(cap) = self.panic_pattern.captures(line) {
+                format!("panic in '{}' at {}", &cap[1], cap[2].trim_end_matches(':'))
+            } else if let Some(cap) = self.api_error_pattern.captures(line) {
+                format!("API error {}", cap[1].trim())
+            } else if let Some(cap) = self.rate_limit_pattern.captures(line) {
+                cap[1].trim().to_string()
+            } else {
+                continue;
+            };
+            return Some(truncate_reason(&reason, ERROR_REASON_MAX_CHARS));
+        }
+        None
+    }
+
+    /// Detects the "(esc to interrupt)" hint Claude Code shows while it works,
+    /// returning the activity from the nearest `✽` line at or above it
+    fn detect_processing(&self, content: &str) -> Option<String> {
+        let lines: Vec<&str> = content.lines().collect();
+        let recent = &lines[lines.len().saturating_sub(INTERRUPT_HINT_LINES)..];
+        let hint = recent
+            .iter()
+            .rposition(|line| line.contains("esc to interrupt"))?;
+        let activity = recent[..=hint]
+            .iter()
+            .rev()
+            .find_map(|line| parse_activity_line(line))
+            .unwrap_or_else(|| "Working...".to_string());
+        Some(activity)
+    }
+
     fn detect_approval(&self, content: &str) -> Option<(ApprovalType, String)> {
         let lines: Vec<&str> = content.lines().collect();
         if lines.is_empty() {
@@ -97,12 +242,12 @@ impl ClaudeCodeParser {
         let recent = recent_lines.join("\n");
 
         // Check for user question with choices first (AskUserQuestion)
-        if let Some((choices, question)) = self.extract_user_question(&recent) {
+        if let Some((choices, question, multi_select)) = self.extract_user_question(&recent) {
             if !choices.is_empty() {
                 return Some((
                     ApprovalType::UserQuestion {
                         choices,
-                        multi_select: false,
+                        multi_select,
                     },
                     question,
                 ));
@@ -209,7 +354,8 @@ impl ClaudeCodeParser {
 
     /// Extract user question with numbered choices
     /// Only detects choices at the END of content (active prompt waiting for input)
-    fn extract_user_question(&self, content: &str) -> Option<(Vec<String>, String)> {
+    /// Returns the choices, the question and whether several choices may be picked
+    fn extract_user_question(&self, content: &str) -> Option<(Vec<String>, String, bool)> {
         let lines: Vec<&str> = content.lines().collect();
         if lines.is_empty() {
             return None;
@@ -237,6 +383,7 @@ impl ClaudeCodeParser {
         let mut question = String::new();
         let mut first_choice_idx = None;
         let mut last_choice_idx = None;
+        let mut has_checkboxes = false;
 
         // Pattern for numbered choices: "1. Option text" or "  1. Option text"
         let choice_pattern = Regex::new(r"^\s*(\d+)\.\s+(.+)$").ok()?;
@@ -273,6 +420,13 @@ impl ClaudeCodeParser {
                             .next()
                             .unwrap_or(choice_text)
                             .trim();
+                        let label = match strip_checkbox(label) {
+                            Some(rest) => {
+                                has_checkboxes = true;
+                                rest
+                            }
+                            None => label,
+                        };
 
                         choices.push(label.to_string());
 
@@ -289,8 +443,8 @@ impl ClaudeCodeParser {
                 }
             } else if !choices.is_empty() {
                 // Non-choice line after choices started
-                // Allow empty lines and very short lines
-                if !trimmed.is_empty() && trimmed.len() > 30 {
+                // Allow empty lines, very short lines and multi-select hints
+                if !trimmed.is_empty() && trimmed.len() > 30 && !is_multi_select_hint(trimmed) {
                     // Longer content after choices - not an active question prompt
                     choices.clear();
                     first_choice_idx = None;
@@ -333,11 +487,16 @@ impl ClaudeCodeParser {
             }
         }
 
-        if choices.len() >= 2 {
-            Some((choices, question))
-        } else {
-            None
+        if choices.len() < 2 {
+            return None;
         }
+        // Checkbox choices, or a hint anywhere from the question down
+        let hint_from = first_choice_idx.unwrap_or(0).saturating_sub(6);
+        let multi_select = has_checkboxes
+            || check_lines[hint_from..]
+                .iter()
+                .any(|line| is_multi_select_hint(line));
+        Some((choices, question, multi_select))
     }
 
     fn extract_file_path(&self, content: &str) -> Option<String> {
@@ -375,21 +534,28 @@ impl AgentParser for ClaudeCodeParser {
     }
 
     fn matches(&self, detection_strings: &[&str]) -> bool {
-        detection_strings.iter().any(|s| {
+        let named = detection_strings.iter().any(|s| {
             let lower = s.to_lowercase();
             // Match by name
             lower.contains("claude") || lower.contains("anthropic")
             // Match by Claude Code icon (✳) in title
             || s.contains('✳')
-            // Match by version number pattern (e.g., "2.1.11" as command)
-            || is_version_like(s)
-        })
+        });
+        if named {
+            return true;
+        }
+
+        // Claude Code shows its version (e.g. "2.1.11") as the command, but so do other
+        // tools. Only trust it when the title carries Claude's braille spinner too.
+        let title = detection_strings.get(TITLE_INDEX).copied().unwrap_or("");
+        has_spinner_title(title)
+            && detection_strings
+                .iter()
+                .enumerate()
+                .any(|(i, s)| i != TITLE_INDEX && is_version_like(s))
     }
 
     fn parse_status(&self, content: &str) -> AgentStatus {
-        // Title-based spinner detection in monitor/task.rs handles Processing state.
-        // Here we only check for approval prompts, otherwise return Idle.
-
         // Check for approval prompts (highest priority)
         if let Some((approval_type, details)) = self.detect_approval(content) {
             return AgentStatus::AwaitingApproval {
@@ -398,7 +564,19 @@ impl AgentParser for ClaudeCodeParser {
             };
         }
 
-        // Default to Idle - title spinner detection will override to Processing if needed
+        // "esc to interrupt" is only shown while working; it also outranks an error
+        // still on screen that the agent is retrying past
+        if let Some(activity) = self.detect_processing(content) {
+            return AgentStatus::Processing { activity };
+        }
+
+        // Failures the agent printed (API errors, rate limits, panics)
+        if let Some(message) = self.detect_error(content) {
+            return AgentStatus::Error { message };
+        }
+
+        // Default to Idle - title spinner detection in monitor/task.rs will override
+        // to Processing when the hint is scrolled off or hidden
         if content.trim().is_empty() {
             AgentStatus::Unknown
         } else {
@@ -466,6 +644,25 @@ impl AgentParser for ClaudeCodeParser {
             .and_then(|m| m.as_str().parse::<u8>().ok())
     }
 
+    fn parse_activity(&self, content: &str) -> Option<String> {
+        content.lines().rev().find_map(parse_activity_line)
+    }
+
+    fn parse_model(&self, content: &str) -> Option<String> {
+        // The latest mention wins, so a /model switch overrides the banner
+        content.lines().rev().find_map(|line| {
+            if let Some(cap) = self.model_banner_pattern.captures(line) {
+                return Some(format!("{} {}", cap[1].to_lowercase(), &cap[2]));
+            }
+            let cap = self.model_id_pattern.captures(line)?;
+            let version = match cap.get(3) {
+                Some(minor) => format!("{}.{}", &cap[2], minor.as_str()),
+                None => cap[2].to_string(),
+            };
+            Some(format!("{} {}", cap[1].to_lowercase(), version))
+        })
+    }
+
     fn approval_keys(&self) -> &str {
         "y"
     }
@@ -492,14 +689,38 @@ mod tests {
         // Match via ✳ icon in title
         assert!(parser.matches(&["node", "✳ Some Task", ""]));
         assert!(parser.matches(&["2.1.11", "✳ CLI取得の改善", ""]));
-        // Match via version number as command (Claude Code shows version)
-        assert!(parser.matches(&["2.1.11", "Some Title", ""]));
-        assert!(parser.matches(&["1.0.0", "", ""]));
+        // Match via version number as command plus a spinner title
+        assert!(parser.matches(&["2.1.11", "⠐ Refactoring auth", ""]));
+        assert!(parser.matches(&["zsh", "⠂ Working", "-zsh", "2.1.11"]));
         // No match
         assert!(!parser.matches(&["opencode", "OpenCode", "opencode"]));
         assert!(!parser.matches(&["fish", "~", "fish"]));
     }
 
+    #[test]
+    fn test_version_command_alone_is_not_claude() {
+        let parser = ClaudeCodeParser::new();
+        assert!(!parser.matches(&["2.1.11", "Some Title", ""]));
+        assert!(!parser.matches(&["1.0.0", "", ""]));
+        assert!(!parser.matches(&["18.2.0", "node", "node 18.2.0"]));
+        assert!(!parser.matches(&["3.12.1", "python3", "python3 -m http.server"]));
+        // A spinner title alone isn't enough either
+        assert!(!parser.matches(&["cargo", "⠋ Building", "cargo watch"]));
+        // The version has to be a process, not the title text
+        assert!(!parser.matches(&["bash", "⠋ 2.1.11", "bash"]));
+    }
+
+    #[test]
+    fn test_has_spinner_title() {
+        assert!(has_spinner_title("⠐ Refactoring auth"));
+        assert!(has_spinner_title(" ⠿ Reading"));
+        assert!(!has_spinner_title("✳ Idle"));
+        assert!(!has_spinner_title("~/project"));
+        assert!(!has_spinner_title(""));
+        // Blank braille pattern isn't a spinner frame
+        assert!(!has_spinner_title("\u{2800} text"));
+    }
+
     #[test]
     fn test_is_version_like() {
         assert!(is_version_like("2.1.11"));
@@ -525,6 +746,137 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_user_question_single_select() {
+        let parser = ClaudeCodeParser::new();
+        let content = "Which database should we use?\n\n1. Postgres\n2. SQLite\n3. MySQL\n\nEnter to select";
+        match parser.parse_status(content) {
+            AgentStatus::AwaitingApproval {
+                approval_type:
+                    ApprovalType::UserQuestion {
+                        choices,
+                        multi_select,
+                    },
+                details,
+            } => {
+                assert_eq!(choices, vec!["Postgres", "SQLite", "MySQL"]);
+                assert!(!multi_select);
+                assert_eq!(details, "Which database should we use?");
+            }
+            other => panic!("Expected UserQuestion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_user_question_multi_select_hint() {
+        let parser = ClaudeCodeParser::new();
+        let content = "Which features do you want? (select all that apply)\n\n1. Auth\n2. Billing\n3. Search\n\nSpace to select · Enter to confirm";
+        match parser.parse_status(content) {
+            AgentStatus::AwaitingApproval {
+                approval_type:
+                    ApprovalType::UserQuestion {
+                        choices,
+                        multi_select,
+                    },
+                ..
+            } => {
+                assert_eq!(choices, vec!["Auth", "Billing", "Search"]);
+                assert!(multi_select);
+            }
+            other => panic!("Expected UserQuestion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_user_question_multi_select_checkboxes() {
+        let parser = ClaudeCodeParser::new();
+        let content = "Which tests should run?\n\n1. [ ] Unit\n2. [✔] Integration\n3. [ ] E2E";
+        match parser.parse_status(content) {
+            AgentStatus::AwaitingApproval {
+                approval_type:
+                    ApprovalType::UserQuestion {
+                        choices,
+                        multi_select,
+                    },
+                ..
+            } => {
+                assert_eq!(choices, vec!["Unit", "Integration", "E2E"]);
+                assert!(multi_select);
+            }
+            other => panic!("Expected UserQuestion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_activity() {
+        let parser = ClaudeCodeParser::new();
+        let content = "✽ Reading files (2s)\n⏺ Read(src/main.rs)\n✽ Running tests… (12s · esc to interrupt)\n> ";
+        assert_eq!(
+            parser.parse_activity(content).as_deref(),
+            Some("Running tests…")
+        );
+        assert_eq!(parser.parse_activity("> "), None);
+    }
+
+    #[test]
+    fn test_esc_to_interrupt_means_processing() {
+        let parser = ClaudeCodeParser::new();
+        let content = "⏺ Read(src/auth.rs)\n\
+                       ⎿  Read 120 lines\n\
+                       \n\
+                       ✽ Thinking… (8s · ↑ 1.2k tokens · esc to interrupt)\n\
+                       \n\
+                       ╭──────────────────────────────────────╮\n\
+                       │ >                                    │\n\
+                       ╰──────────────────────────────────────╯\n\
+                       ⏵⏵ accept edits on (shift+tab to cycle)";
+        assert_eq!(
+            parser.parse_status(content),
+            AgentStatus::Processing {
+                activity: "Thinking…".to_string()
+            }
+        );
+
+        // Without a status line above it, the hint still counts
+        assert_eq!(
+            parser.parse_status("⏺ Bash(cargo test)\n  (esc to interrupt)\n> "),
+            AgentStatus::Processing {
+                activity: "Working...".to_string()
+            }
+        );
+
+        // A hint that scrolled up is stale
+        let mut finished = content.to_string();
+        finished.push_str(&"\n⏺ Done.".repeat(INTERRUPT_HINT_LINES));
+        finished.push_str("\n> ");
+        assert_eq!(parser.parse_status(&finished), AgentStatus::Idle);
+    }
+
+    #[test]
+    fn test_parse_model_from_banner() {
+        let parser = ClaudeCodeParser::new();
+        let content = " ▐▛███▜▌   Claude Code v2.0.14\n▝▜█████▛▘  Sonnet 4.5 · Claude Max\n  ▘▘ ▝▝    /home/user/project\n\n> ";
+        assert_eq!(parser.parse_model(content).as_deref(), Some("sonnet 4.5"));
+    }
+
+    #[test]
+    fn test_parse_model_after_switch() {
+        let parser = ClaudeCodeParser::new();
+        let content = "▝▜█████▛▘  Sonnet 4.5 · Claude Max\n> /model\n  ⎿  Set model to opus (claude-opus-4-1-20250805)\n> ";
+        assert_eq!(parser.parse_model(content).as_deref(), Some("opus 4.1"));
+        assert_eq!(
+            parser.parse_model("model: claude-haiku-4-20250514").as_deref(),
+            Some("haiku 4")
+        );
+    }
+
+    #[test]
+    fn test_parse_model_ignores_prose() {
+        let parser = ClaudeCodeParser::new();
+        assert_eq!(parser.parse_model("Tip: /model to try Opus 4\n> "), None);
+        assert_eq!(parser.parse_model("This refactor is my magnum opus.\n> "), None);
+    }
+
     #[test]
     fn test_parse_thinking() {
         // Note: parse_status returns Idle for non-approval content.
@@ -599,4 +951,57 @@ This is just normal text.
             status
         );
     }
+
+    #[test]
+    fn test_error_rate_limit() {
+        let parser = ClaudeCodeParser::new();
+        let content = "⏺ Reading files\n  ⎿ Error: rate_limit_error\n\n> ";
+        assert_eq!(
+            parser.parse_status(content),
+            AgentStatus::Error {
+                message: "rate_limit_error".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_api_500() {
+        let parser = ClaudeCodeParser::new();
+        let content = "⏺ Working on it\n  ⎿  API Error: 500 {\"type\":\"error\",\"error\":{\"type\":\"api_error\",\"message\":\"Internal server error\"}}\n\n> ";
+        match parser.parse_status(content) {
+            AgentStatus::Error { message } => {
+                assert!(message.starts_with("API error 500"));
+                assert!(message.chars().count() <= ERROR_REASON_MAX_CHARS);
+            }
+            other => panic!("expected error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_error_rust_panic() {
+        let parser = ClaudeCodeParser::new();
+        let content = "$ cargo run\nthread 'main' panicked at src/main.rs:10:5:\nindex out of bounds\n";
+        let status = parser.parse_status(content);
+        assert_eq!(
+            status,
+            AgentStatus::Error {
+                message: "panic in 'main' at src/main.rs:10:5".to_string()
+            }
+        );
+        assert_eq!(status.indicator(), "✗");
+    }
+
+    #[test]
+    fn test_error_not_reported_for_old_or_prose_mentions() {
+        let parser = ClaudeCodeParser::new();
+        // Scrolled out of the recent window
+        let mut content = String::from("  ⎿ API Error: 529 overloaded\n");
+        content.push_str(&"done\n".repeat(20));
+        assert_eq!(parser.parse_status(&content), AgentStatus::Idle);
+        // Mentioned mid-sentence, not printed as a failure
+        let prose = "⏺ I added retry handling for the API Error: case and rate limits.\n> ";
+        assert_eq!(parser.parse_status(prose), AgentStatus::Idle);
+        let quoted = "⏺ The test failed with thread 'main' panicked at src/lib.rs:3:1, fixed.\n> ";
+        assert_eq!(parser.parse_status(quoted), AgentStatus::Idle);
+    }
 }
@@ -1,25 +1,170 @@
 use regex::Regex;
 
-use crate::agents::{AgentStatus, AgentType, ApprovalType, Subagent};
+use crate::agents::{AgentStatus, AgentType, ApprovalType, Subagent, SubagentStatus, SubagentType};
 
 use super::{safe_tail, AgentParser};
 
 /// Parser for Codex CLI output
 pub struct CodexCliParser {
-    approval_pattern: Regex,
+    // Approval patterns
+    choice_pattern: Regex,
+    command_pattern: Regex,
+    patch_pattern: Regex,
+    sandbox_pattern: Regex,
+
     processing_pattern: Regex,
     idle_pattern: Regex,
+
+    // Plan and tool execution patterns
+    plan_header_pattern: Regex,
+    plan_step_pattern: Regex,
+    exec_pattern: Regex,
 }
 
 impl CodexCliParser {
     pub fn new() -> Self {
         Self {
-            approval_pattern: Regex::new(r"(?i)\[y/n\]|\[yes/no\]|confirm|approve|run this")
-                .expect("invalid regex"),
+            // Answer options of an active prompt, e.g. "[y/N]", "▶ Yes (y)", "› 1. Yes, proceed"
+            choice_pattern: Regex::new(
+                r"(?i)\[y/n\]|\[yes/no\]|\bYes\b.*\(y\)|^\s*[›▶>]?\s*1\.\s*Yes\b"
+            ).expect("invalid regex"),
+            // "Allow command execution? [y/N]", "Would you like to run the following command?"
+            command_pattern: Regex::new(
+                r"(?i)Allow command( execution)?\?|would you like to run the following command\?|Run (this|the following) command\?"
+            ).expect("invalid regex"),
+            // "Would you like to make the following edits?", "Proceed with patch?"
+            patch_pattern: Regex::new(
+                r"(?i)would you like to (make|apply) the following edits\?|Proceed with patch\?|Allow (Codex to )?apply (this )?patch"
+            ).expect("invalid regex"),
+            // Sandbox escalation dialogs
+            sandbox_pattern: Regex::new(
+                r"(?i)outside (of )?the sandbox|escalated (permissions|privileges)|sandbox escalation"
+            ).expect("invalid regex"),
             processing_pattern: Regex::new(r"(?i)(thinking|running|executing|generating)").expect("invalid regex"),
             idle_pattern: Regex::new(r"(?i)(ready|waiting|>\s*$|\$\s*$)").expect("invalid regex"),
+            // "• Updated Plan", printed each time the plan changes
+            plan_header_pattern: Regex::new(r"^\s*[•●]?\s*Updated Plan\b").expect("invalid regex"),
+            // "└ ✔ Inspect parser", "  □ Add tests"
+            plan_step_pattern: Regex::new(r"^\s*(?:└\s*)?([✔✓□☐])\s+(.+?)\s*$").expect("invalid regex"),
+            // "• Running cargo test", replaced by "• Ran cargo test" once it finishes
+            exec_pattern: Regex::new(r"^\s*[•●]\s+(Running|Ran)\s+(.+?)\s*$").expect("invalid regex"),
+        }
+    }
+
+    /// Steps of the latest plan: done steps as completed, the first open step as
+    /// running. Steps after it haven't started and are left out.
+    fn parse_plan(&self, lines: &[&str]) -> Vec<Subagent> {
+        let Some(header) = lines
+            .iter()
+            .rposition(|l| self.plan_header_pattern.is_match(l))
+        else {
+            return Vec::new();
+        };
+
+        let mut steps = Vec::new();
+        let mut running_seen = false;
+        for line in &lines[header + 1..] {
+            let Some(cap) = self.plan_step_pattern.captures(line) else {
+                // An explanation may sit between the header and the steps
+                if steps.is_empty() && !running_seen && !line.trim_start().starts_with(['•', '●']) {
+                    continue;
+                }
+                break;
+            };
+            let status = if matches!(&cap[1], "✔" | "✓") {
+                SubagentStatus::Completed
+            } else if !running_seen {
+                running_seen = true;
+                SubagentStatus::Running
+            } else {
+                continue;
+            };
+            steps.push(
+                Subagent::new(
+                    format!("plan-{}", steps.len() + 1),
+                    SubagentType::Plan,
+                    cap[2].to_string(),
+                )
+                .with_status(status),
+            );
         }
+        steps
     }
+
+    /// Shell commands Codex ran or is running
+    fn parse_execs(&self, lines: &[&str]) -> Vec<Subagent> {
+        let mut execs: Vec<Subagent> = Vec::new();
+        for cap in lines.iter().filter_map(|l| self.exec_pattern.captures(l)) {
+            let command = cap[2].to_string();
+            if &cap[1] == "Running" {
+                execs.push(Subagent::new(
+                    format!("exec-{}", execs.len() + 1),
+                    SubagentType::Bash,
+                    command,
+                ));
+                continue;
+            }
+            let running = execs.iter_mut().rev().find(|s| {
+                s.status == SubagentStatus::Running && s.description == command
+            });
+            match running {
+                Some(exec) => exec.status = SubagentStatus::Completed,
+                None => execs.push(
+                    Subagent::new(format!("exec-{}", execs.len() + 1), SubagentType::Bash, command)
+                        .with_status(SubagentStatus::Completed),
+                ),
+            }
+        }
+        execs
+    }
+
+    fn detect_approval(&self, content: &str) -> Option<(ApprovalType, String)> {
+        let lines: Vec<&str> = content.trim_end().lines().map(strip_gutter).collect();
+        if lines.is_empty() {
+            return None;
+        }
+
+        // Only a prompt whose answer options are still on screen counts as pending
+        let last_lines = &lines[lines.len().saturating_sub(8)..];
+        if !last_lines.iter().any(|l| self.choice_pattern.is_match(l)) {
+            return None;
+        }
+
+        let recent_lines = &lines[lines.len().saturating_sub(15)..];
+        let recent = recent_lines.join("\n");
+        let command = extract_command(recent_lines).unwrap_or_default();
+
+        if self.sandbox_pattern.is_match(&recent) {
+            return Some((ApprovalType::Other("Sandbox escalation".to_string()), command));
+        }
+
+        if self.patch_pattern.is_match(&recent) {
+            return Some((ApprovalType::FileEdit, String::new()));
+        }
+
+        if self.command_pattern.is_match(&recent) {
+            return Some((ApprovalType::ShellCommand, command));
+        }
+
+        Some((
+            ApprovalType::Other("Pending approval".to_string()),
+            String::new(),
+        ))
+    }
+}
+
+/// Strips the "▌"/"│" gutter Codex draws in front of dialog lines
+fn strip_gutter(line: &str) -> &str {
+    line.trim_start_matches([' ', '▌', '│']).trim_end()
+}
+
+/// Finds the "$ command" line shown in an approval dialog
+fn extract_command(lines: &[&str]) -> Option<String> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|l| l.strip_prefix("$ "))
+        .map(|cmd| cmd.trim().to_string())
 }
 
 impl Default for CodexCliParser {
@@ -47,10 +192,10 @@ impl AgentParser for CodexCliParser {
     fn parse_status(&self, content: &str) -> AgentStatus {
         let recent = safe_tail(content, 500);
 
-        if self.approval_pattern.is_match(recent) {
+        if let Some((approval_type, details)) = self.detect_approval(content) {
             return AgentStatus::AwaitingApproval {
-                approval_type: ApprovalType::Other("Pending".to_string()),
-                details: String::new(),
+                approval_type,
+                details,
             };
         }
 
@@ -67,9 +212,12 @@ impl AgentParser for CodexCliParser {
         AgentStatus::Unknown
     }
 
-    fn parse_subagents(&self, _content: &str) -> Vec<Subagent> {
-        // Codex CLI doesn't have subagents
-        Vec::new()
+    fn parse_subagents(&self, content: &str) -> Vec<Subagent> {
+        // Codex has no subagents; its plan steps and commands fill the same role
+        let lines: Vec<&str> = content.lines().map(strip_gutter).collect();
+        let mut subagents = self.parse_plan(&lines);
+        subagents.extend(self.parse_execs(&lines));
+        subagents
     }
 }
 
@@ -84,4 +232,183 @@ mod tests {
         assert!(parser.matches(&["", "Codex CLI", ""]));
         assert!(!parser.matches(&["claude", "Claude", ""]));
     }
+
+    fn approval(content: &str) -> Option<(ApprovalType, String)> {
+        match CodexCliParser::new().parse_status(content) {
+            AgentStatus::AwaitingApproval {
+                approval_type,
+                details,
+            } => Some((approval_type, details)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_command_approval() {
+        let content = r#"
+codex
+I'll run the test suite to check the fix.
+
+▌Allow command?
+▌
+▌ $ cargo test --workspace
+▌
+▌ ▶ Yes (y)
+▌   Always for this session (a)
+▌   No, provide feedback (n)
+"#;
+        assert_eq!(
+            approval(content),
+            Some((ApprovalType::ShellCommand, "cargo test --workspace".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_numbered_command_approval() {
+        let content = r#"
+Would you like to run the following command?
+
+  $ rm -rf target/debug
+
+› 1. Yes, proceed
+  2. Yes, and don't ask again for this command
+  3. No, and tell Codex what to do differently (esc)
+
+Press enter to confirm or esc to cancel
+"#;
+        assert_eq!(
+            approval(content),
+            Some((ApprovalType::ShellCommand, "rm -rf target/debug".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_inline_command_approval() {
+        let content = "Allow command execution? [y/N]";
+        let (approval_type, _) = approval(content).unwrap();
+        assert_eq!(approval_type, ApprovalType::ShellCommand);
+    }
+
+    #[test]
+    fn test_patch_approval() {
+        let content = r#"
+Would you like to make the following edits?
+
+src/main.rs (+3 -1)
+
+› 1. Yes, proceed
+  2. No, and tell Codex what to do differently (esc)
+"#;
+        let (approval_type, _) = approval(content).unwrap();
+        assert_eq!(approval_type, ApprovalType::FileEdit);
+    }
+
+    #[test]
+    fn test_sandbox_escalation() {
+        let content = r#"
+The command failed in the sandbox. Retrying with escalated permissions.
+
+▌Allow command to run outside the sandbox?
+▌ $ npm install
+▌ ▶ Yes (y)
+▌   No (n)
+"#;
+        assert_eq!(
+            approval(content),
+            Some((
+                ApprovalType::Other("Sandbox escalation".to_string()),
+                "npm install".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_ordinary_output_is_not_approval() {
+        let samples = [
+            "Please confirm the release notes were approved before merging.\n> ",
+            "To install, run this script:\n  ./install.sh\n$ ",
+            "codex\nUpdated README to mention the approve/reject keys.\n▌ ",
+            "test result: ok. 12 passed; 0 failed\n$ ",
+        ];
+        for content in samples {
+            assert_eq!(approval(content), None, "false positive on {:?}", content);
+        }
+    }
+
+    #[test]
+    fn test_answered_prompt_scrolled_away() {
+        let mut content = String::from("Allow command?\n$ ls\n▶ Yes (y)\n");
+        for i in 0..12 {
+            content.push_str(&format!("output line {}\n", i));
+        }
+        content.push_str("> ");
+        assert_eq!(approval(&content), None);
+    }
+
+    fn summary(subagents: &[Subagent]) -> Vec<(SubagentType, String, SubagentStatus)> {
+        subagents
+            .iter()
+            .map(|s| (s.subagent_type.clone(), s.description.clone(), s.status.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_steps_progress() {
+        let parser = CodexCliParser::new();
+        let started = r#"
+codex
+I'll add the parser first, then cover it with tests.
+
+• Updated Plan
+  └ □ Add plan parsing to the Codex parser
+    □ Write tests for plan parsing
+
+• Running cargo check
+"#;
+        assert_eq!(
+            summary(&parser.parse_subagents(started)),
+            vec![
+                (
+                    SubagentType::Plan,
+                    "Add plan parsing to the Codex parser".to_string(),
+                    SubagentStatus::Running
+                ),
+                (SubagentType::Bash, "cargo check".to_string(), SubagentStatus::Running),
+            ]
+        );
+
+        let progressed = format!(
+            "{}{}",
+            started.replace("• Running cargo check", "• Ran cargo check\n  └ Finished dev profile"),
+            r#"
+• Updated Plan
+  └ ✔ Add plan parsing to the Codex parser
+    □ Write tests for plan parsing
+"#
+        );
+        assert_eq!(
+            summary(&parser.parse_subagents(&progressed)),
+            vec![
+                (
+                    SubagentType::Plan,
+                    "Add plan parsing to the Codex parser".to_string(),
+                    SubagentStatus::Completed
+                ),
+                (
+                    SubagentType::Plan,
+                    "Write tests for plan parsing".to_string(),
+                    SubagentStatus::Running
+                ),
+                (SubagentType::Bash, "cargo check".to_string(), SubagentStatus::Completed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_plan_no_subagents() {
+        let parser = CodexCliParser::new();
+        assert!(parser
+            .parse_subagents("codex\nUpdated the plan section of README.md.\n> ")
+            .is_empty());
+    }
 }
@@ -0,0 +1,139 @@
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::agents::{AgentStatus, AgentType, ApprovalType};
+
+use super::{safe_tail, AgentParser};
+
+/// One `[[custom_agents]]` entry of the config: an agent none of the built-in
+/// parsers know, detected and read through regexes
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomAgentConfig {
+    /// Name shown for the agent, e.g. "acme-agent"
+    pub name: String,
+    /// Regexes matched against the pane's command, title and command line; any match detects the agent
+    pub detect: Vec<String>,
+    /// Regex for an approval prompt in the pane's recent output
+    pub approval: Option<String>,
+    /// Regex for output meaning the agent is busy
+    #[serde(default)]
+    pub processing: Option<String>,
+}
+
+/// Parser for a user-defined agent, built from a `CustomAgentConfig`.
+/// Its agents have `AgentType::Custom` with the configured name; anything that
+/// isn't an approval or processing match counts as idle.
+pub struct ConfigurableParser {
+    name: String,
+    detect_patterns: Vec<Regex>,
+    approval_pattern: Option<Regex>,
+    processing_pattern: Option<Regex>,
+}
+
+impl ConfigurableParser {
+    /// Compiles the config's regexes; fails on the first invalid one
+    pub fn new(config: &CustomAgentConfig) -> Result<Self, regex::Error> {
+        let compile = |pattern: &Option<String>| pattern.as_deref().map(Regex::new).transpose();
+        Ok(Self {
+            name: config.name.clone(),
+            detect_patterns: config
+                .detect
+                .iter()
+                .map(|pattern| Regex::new(pattern))
+                .collect::<Result<_, _>>()?,
+            approval_pattern: compile(&config.approval)?,
+            processing_pattern: compile(&config.processing)?,
+        })
+    }
+}
+
+impl AgentParser for ConfigurableParser {
+    fn agent_name(&self) -> &str {
+        &self.name
+    }
+
+    fn agent_type(&self) -> AgentType {
+        AgentType::Custom(self.name.clone())
+    }
+
+    fn matches(&self, detection_strings: &[&str]) -> bool {
+        detection_strings
+            .iter()
+            .filter(|s| !s.is_empty())
+            .any(|s| self.detect_patterns.iter().any(|p| p.is_match(s)))
+    }
+
+    fn parse_status(&self, content: &str) -> AgentStatus {
+        let recent = safe_tail(content, 500);
+
+        if let Some(pattern) = &self.approval_pattern {
+            // The prompt's own line is the most useful detail to show
+            if let Some(line) = recent.lines().rev().find(|line| pattern.is_match(line)) {
+                return AgentStatus::AwaitingApproval {
+                    approval_type: ApprovalType::Other(self.name.clone()),
+                    details: line.trim().to_string(),
+                };
+            }
+        }
+
+        if self
+            .processing_pattern
+            .as_ref()
+            .is_some_and(|pattern| pattern.is_match(recent))
+        {
+            return AgentStatus::Processing {
+                activity: "Processing...".to_string(),
+            };
+        }
+
+        AgentStatus::Idle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn acme() -> CustomAgentConfig {
+        CustomAgentConfig {
+            name: "acme".to_string(),
+            detect: vec![r"^acme-agent\b".to_string()],
+            approval: Some(r"Proceed\? \[a\]ccept".to_string()),
+            processing: Some(r"(?i)crunching".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_matches_detection_regexes() {
+        let parser = ConfigurableParser::new(&acme()).unwrap();
+        assert_eq!(parser.agent_name(), "acme");
+        assert!(parser.matches(&["acme-agent", "", ""]));
+        assert!(parser.matches(&["node", "", "acme-agent --fast"]));
+        assert!(!parser.matches(&["claude", "Claude Code", ""]));
+    }
+
+    #[test]
+    fn test_parse_status_uses_configured_patterns() {
+        let parser = ConfigurableParser::new(&acme()).unwrap();
+
+        let status = parser.parse_status("wrote config.yml\nProceed? [a]ccept / [r]eject\n");
+        match status {
+            AgentStatus::AwaitingApproval { details, .. } => {
+                assert_eq!(details, "Proceed? [a]ccept / [r]eject");
+            }
+            other => panic!("expected approval, got {:?}", other),
+        }
+        assert!(matches!(
+            parser.parse_status("Crunching numbers..."),
+            AgentStatus::Processing { .. }
+        ));
+        assert_eq!(parser.parse_status("done.\n$ "), AgentStatus::Idle);
+    }
+
+    #[test]
+    fn test_invalid_regex_is_an_error() {
+        let mut config = acme();
+        config.approval = Some("(unclosed".to_string());
+        assert!(ConfigurableParser::new(&config).is_err());
+    }
+}
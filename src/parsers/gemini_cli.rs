@@ -6,22 +6,126 @@ use super::{safe_tail, AgentParser};
 
 /// Parser for Gemini CLI output
 pub struct GeminiCliParser {
-    approval_pattern: Regex,
+    // Approval patterns
+    choice_pattern: Regex,
+    menu_item_pattern: Regex,
+    file_edit_pattern: Regex,
+    shell_pattern: Regex,
+
     processing_pattern: Regex,
     idle_pattern: Regex,
+    context_pattern: Regex,
 }
 
 impl GeminiCliParser {
     pub fn new() -> Self {
         Self {
-            approval_pattern: Regex::new(r"(?i)\[y/n\]|\[yes/no\]|confirm|approve|allow").expect("invalid regex"),
+            // Inline answer options, e.g. "Apply this change? (y/n)"
+            choice_pattern: Regex::new(r"(?i)[(\[]y/n[)\]]|[(\[]yes/no[)\]]").expect("invalid regex"),
+            // Numbered menu entries, optionally with the selection marker: "● 1. Yes, allow once"
+            menu_item_pattern: Regex::new(r"^[●○›>]?\s*(\d+)\.\s+(.+)$").expect("invalid regex"),
+            // Tool confirmation headers: "? WriteFile Writing to src/main.rs", "Apply this change?"
+            file_edit_pattern: Regex::new(
+                r"(?i)apply this change\?|^\?\s*(WriteFile|Edit|Replace)\b"
+            ).expect("invalid regex"),
+            // "? Shell cargo build", "Allow execution of: 'cargo'?"
+            shell_pattern: Regex::new(r"(?i)allow execution|^\?\s*Shell\b").expect("invalid regex"),
             processing_pattern: Regex::new(r"(?i)(thinking|generating|processing|analyzing)")
                 .expect("invalid regex"),
             idle_pattern: Regex::new(r"(?i)(ready|waiting|>\s*$)").expect("invalid regex"),
+            // Token usage footer (e.g., "Tokens: 45,231 / 1,000,000")
+            context_pattern: Regex::new(r"(?i)Tokens:\s*([\d,]+)(?:\s*/\s*([\d,]+))?")
+                .expect("invalid regex"),
+        }
+    }
+
+    fn detect_approval(&self, content: &str) -> Option<(ApprovalType, String)> {
+        let lines: Vec<&str> = content
+            .lines()
+            .map(strip_box)
+            .filter(|l| !l.is_empty())
+            .collect();
+        let recent_lines = &lines[lines.len().saturating_sub(15)..];
+        let last = *recent_lines.last()?;
+
+        // A prompt is only pending while its options are the last thing on screen
+        let (choices, question) = match self.extract_menu(recent_lines) {
+            Some(menu) => menu,
+            None if self.choice_pattern.is_match(last) => (Vec::new(), last.to_string()),
+            None => return None,
+        };
+
+        // Tool confirmations carry a "? Tool ..." header above the question
+        let header = recent_lines.iter().rev().find(|l| l.starts_with('?'));
+        let details = header
+            .map(|h| h.trim_start_matches('?').trim().to_string())
+            .unwrap_or_default();
+        let is_tool = |pattern: &Regex| {
+            pattern.is_match(&question) || header.is_some_and(|h| pattern.is_match(h))
+        };
+
+        if is_tool(&self.file_edit_pattern) {
+            return Some((ApprovalType::FileEdit, details));
+        }
+        if is_tool(&self.shell_pattern) {
+            return Some((ApprovalType::ShellCommand, details));
         }
+        if choices.len() >= 2 {
+            return Some((
+                ApprovalType::UserQuestion {
+                    choices,
+                    multi_select: false,
+                },
+                question,
+            ));
+        }
+        Some((
+            ApprovalType::Other("Pending approval".to_string()),
+            question,
+        ))
+    }
+
+    /// Finds a numbered options menu ending on the last line, returning the choice
+    /// labels and the question line right above it
+    fn extract_menu(&self, lines: &[&str]) -> Option<(Vec<String>, String)> {
+        let mut choices = Vec::new();
+        let mut expected = None;
+        let mut question_idx = None;
+
+        for (i, line) in lines.iter().enumerate().rev() {
+            let Some(cap) = self.menu_item_pattern.captures(line) else {
+                question_idx = Some(i);
+                break;
+            };
+            let num: usize = cap[1].parse().ok()?;
+            // Numbers must count down to 1 without gaps, reading bottom-up
+            if expected.is_some_and(|e| e != num) {
+                return None;
+            }
+            let label = cap[2].trim_end_matches("(esc)").trim();
+            choices.push(label.to_string());
+            if num == 1 {
+                let before = i.checked_sub(1)?;
+                question_idx = Some(before);
+                break;
+            }
+            expected = Some(num - 1);
+        }
+
+        let question = lines[question_idx?];
+        if choices.len() < 2 || !question.contains('?') {
+            return None;
+        }
+        choices.reverse();
+        Some((choices, question.to_string()))
     }
 }
 
+/// Strips the rounded box Gemini draws around dialogs
+fn strip_box(line: &str) -> &str {
+    line.trim_matches(|c: char| c.is_whitespace() || "│╭╮╰╯─".contains(c))
+}
+
 impl Default for GeminiCliParser {
     fn default() -> Self {
         Self::new()
@@ -47,10 +151,10 @@ impl AgentParser for GeminiCliParser {
     fn parse_status(&self, content: &str) -> AgentStatus {
         let recent = safe_tail(content, 500);
 
-        if self.approval_pattern.is_match(recent) {
+        if let Some((approval_type, details)) = self.detect_approval(content) {
             return AgentStatus::AwaitingApproval {
-                approval_type: ApprovalType::Other("Pending".to_string()),
-                details: String::new(),
+                approval_type,
+                details,
             };
         }
 
@@ -71,6 +175,23 @@ impl AgentParser for GeminiCliParser {
         // Gemini CLI doesn't have subagents
         Vec::new()
     }
+
+    fn parse_context_remaining(&self, content: &str) -> Option<u8> {
+        // Use the most recent usage line; without a limit there is no percentage
+        let cap = self.context_pattern.captures_iter(content).last()?;
+        let used = parse_token_count(cap.get(1)?.as_str())?;
+        let limit = parse_token_count(cap.get(2)?.as_str())?;
+        if limit == 0 {
+            return None;
+        }
+        let remaining = limit.saturating_sub(used) * 100 / limit;
+        Some(remaining.min(100) as u8)
+    }
+}
+
+/// Parses a token count with optional thousands separators ("1,000,000")
+fn parse_token_count(s: &str) -> Option<u64> {
+    s.replace(',', "").parse().ok()
 }
 
 #[cfg(test)]
@@ -84,4 +205,109 @@ mod tests {
         assert!(parser.matches(&["", "Gemini CLI", ""]));
         assert!(!parser.matches(&["claude", "Claude", ""]));
     }
+
+    #[test]
+    fn test_parse_context_remaining() {
+        let parser = GeminiCliParser::new();
+        let content = "> explain main.rs\n\n~/project (main)   gemini-2.5-pro   Tokens: 45,231 / 1,000,000";
+        assert_eq!(parser.parse_context_remaining(content), Some(95));
+    }
+
+    #[test]
+    fn test_parse_context_remaining_uses_latest_line() {
+        let parser = GeminiCliParser::new();
+        let content = "Tokens: 10,000 / 100,000\n...\nTokens: 80,000 / 100,000";
+        assert_eq!(parser.parse_context_remaining(content), Some(20));
+    }
+
+    #[test]
+    fn test_parse_context_remaining_without_limit() {
+        let parser = GeminiCliParser::new();
+        assert_eq!(parser.parse_context_remaining("Tokens: 45,231"), None);
+        assert_eq!(parser.parse_context_remaining("no usage here"), None);
+    }
+
+    fn approval(content: &str) -> Option<(ApprovalType, String)> {
+        match GeminiCliParser::new().parse_status(content) {
+            AgentStatus::AwaitingApproval {
+                approval_type,
+                details,
+            } => Some((approval_type, details)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_file_write_confirmation() {
+        let content = r#"
+✦ I'll add the missing import.
+
+╭──────────────────────────────────────────────╮
+│ ? WriteFile Writing to src/main.rs           │
+│                                              │
+│  1 + use std::io;                            │
+│                                              │
+│ Apply this change?                           │
+│                                              │
+│ ● 1. Yes, allow once                         │
+│   2. Yes, allow always                       │
+│   3. Modify with external editor             │
+│   4. No (esc)                                │
+╰──────────────────────────────────────────────╯
+"#;
+        assert_eq!(
+            approval(content),
+            Some((
+                ApprovalType::FileEdit,
+                "WriteFile Writing to src/main.rs".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_inline_confirmation() {
+        let (approval_type, _) = approval("? Edit src/lib.rs\nApply this change? (y/n)").unwrap();
+        assert_eq!(approval_type, ApprovalType::FileEdit);
+
+        let (approval_type, details) = approval("? Shell cargo build\nAllow execution? (y/n)").unwrap();
+        assert_eq!(approval_type, ApprovalType::ShellCommand);
+        assert_eq!(details, "Shell cargo build");
+    }
+
+    #[test]
+    fn test_multi_choice_prompt() {
+        let content = r#"
+Which database should the migration target?
+● 1. PostgreSQL
+  2. SQLite
+  3. MySQL
+"#;
+        assert_eq!(
+            approval(content),
+            Some((
+                ApprovalType::UserQuestion {
+                    choices: vec![
+                        "PostgreSQL".to_string(),
+                        "SQLite".to_string(),
+                        "MySQL".to_string()
+                    ],
+                    multi_select: false,
+                },
+                "Which database should the migration target?".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_plain_prose_is_not_approval() {
+        let samples = [
+            "Please confirm the deploy was approved and allow a few minutes.\n> ",
+            "The fix needs two steps:\n1. Update the lockfile\n2. Rerun the tests\n> ",
+            "Should I continue?\n1. First I read the config\n2. Then I patched it\nDone.",
+            "✦ Generating a summary of the changes...",
+        ];
+        for content in samples {
+            assert_eq!(approval(content), None, "false positive on {:?}", content);
+        }
+    }
 }
@@ -1,10 +1,12 @@
 mod claude_code;
 mod codex_cli;
+mod configurable;
 mod gemini_cli;
 mod opencode;
 
-pub use claude_code::ClaudeCodeParser;
+pub use claude_code::{parse_activity_line, ClaudeCodeParser};
 pub use codex_cli::CodexCliParser;
+pub use configurable::{ConfigurableParser, CustomAgentConfig};
 pub use gemini_cli::GeminiCliParser;
 pub use opencode::OpenCodeParser;
 
@@ -48,6 +50,18 @@ pub trait AgentParser: Send + Sync {
         None
     }
 
+    /// Parses what the agent is currently doing from content (default: None)
+    fn parse_activity(&self, content: &str) -> Option<String> {
+        let _ = content;
+        None
+    }
+
+    /// Parses the model the agent is running on, e.g. "opus 4.1" (default: None)
+    fn parse_model(&self, content: &str) -> Option<String> {
+        let _ = content;
+        None
+    }
+
     /// Returns the key(s) to send for approval
     fn approval_keys(&self) -> &str {
         "y"
@@ -65,16 +79,23 @@ pub struct ParserRegistry {
 }
 
 impl ParserRegistry {
-    /// Creates a new registry with all default parsers
-    pub fn new() -> Self {
-        Self {
-            parsers: vec![
-                Box::new(ClaudeCodeParser::new()),
-                Box::new(OpenCodeParser::new()),
-                Box::new(CodexCliParser::new()),
-                Box::new(GeminiCliParser::new()),
-            ],
+    /// Creates a registry with all default parsers, followed by one per custom agent.
+    /// Built-in parsers are tried first so a broad custom regex can't claim their panes;
+    /// custom agents with an invalid regex are skipped with a warning.
+    pub fn new(custom_agents: &[CustomAgentConfig]) -> Self {
+        let mut parsers: Vec<Box<dyn AgentParser>> = vec![
+            Box::new(ClaudeCodeParser::new()),
+            Box::new(OpenCodeParser::new()),
+            Box::new(CodexCliParser::new()),
+            Box::new(GeminiCliParser::new()),
+        ];
+        for config in custom_agents {
+            match ConfigurableParser::new(config) {
+                Ok(parser) => parsers.push(Box::new(parser)),
+                Err(e) => tracing::warn!("Skipping custom agent {}: {}", config.name, e),
+            }
         }
+        Self { parsers }
     }
 
     /// Finds a parser that matches the given pane info
@@ -86,21 +107,51 @@ impl ParserRegistry {
             .map(|p| p.as_ref())
     }
 
+    /// Finds the parser for `agent_type`, custom agents by their name. None has
+    /// `AgentType::Unknown`, so none is returned for it.
+    pub fn find_parser_for_type(&self, agent_type: &AgentType) -> Option<&dyn AgentParser> {
+        if *agent_type == AgentType::Unknown {
+            return None;
+        }
+        self.parsers
+            .iter()
+            .find(|p| p.agent_type() == *agent_type)
+            .map(|p| p.as_ref())
+    }
+
+    /// Parses `content` as a pane of `agent_type`: status, subagents and context
+    /// remaining, the same way the monitor does for live panes. Lets tools and tests
+    /// drive any parser without a tmux pane; a type without a parser is `Unknown`.
+    pub fn parse_content(
+        &self,
+        agent_type: &AgentType,
+        content: &str,
+    ) -> (AgentStatus, Vec<Subagent>, Option<u8>) {
+        match self.find_parser_for_type(agent_type) {
+            Some(parser) => (
+                parser.parse_status(content),
+                parser.parse_subagents(content),
+                parser.parse_context_remaining(content),
+            ),
+            None => (AgentStatus::Unknown, Vec::new(), None),
+        }
+    }
 }
 
 impl Default for ParserRegistry {
     fn default() -> Self {
-        Self::new()
+        Self::new(&[])
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agents::{ApprovalType, SubagentStatus, SubagentType};
 
     #[test]
     fn test_parser_registry() {
-        let registry = ParserRegistry::new();
+        let registry = ParserRegistry::default();
 
         // Test finding parsers with various detection strings
         let claude_pane = PaneInfo {
@@ -114,6 +165,7 @@ mod tests {
             pid: 1234,
             cmdline: "/usr/bin/claude".to_string(),
             child_commands: Vec::new(),
+            alternate_on: false,
         };
         assert!(registry.find_parser_for_pane(&claude_pane).is_some());
 
@@ -128,6 +180,7 @@ mod tests {
             pid: 1235,
             cmdline: "opencode".to_string(),
             child_commands: Vec::new(),
+            alternate_on: false,
         };
         assert!(registry.find_parser_for_pane(&opencode_pane).is_some());
 
@@ -143,7 +196,143 @@ mod tests {
             pid: 1236,
             cmdline: "-zsh".to_string(),
             child_commands: vec!["claude -c".to_string(), "claude".to_string()],
+            alternate_on: false,
         };
         assert!(registry.find_parser_for_pane(&child_claude_pane).is_some());
     }
+
+    #[test]
+    fn test_custom_agents_after_builtins() {
+        let registry = ParserRegistry::new(&[
+            CustomAgentConfig {
+                name: "acme".to_string(),
+                detect: vec!["acme|claude".to_string()],
+                approval: None,
+                processing: None,
+            },
+            CustomAgentConfig {
+                name: "broken".to_string(),
+                detect: vec!["(".to_string()],
+                approval: None,
+                processing: None,
+            },
+        ]);
+        let pane = |command: &str| PaneInfo {
+            session: "main".to_string(),
+            window: 0,
+            window_name: "code".to_string(),
+            pane: 0,
+            command: command.to_string(),
+            title: String::new(),
+            path: "/home/user/project".to_string(),
+            pid: 1234,
+            cmdline: String::new(),
+            child_commands: Vec::new(),
+            alternate_on: false,
+        };
+
+        let acme = registry.find_parser_for_pane(&pane("acme")).unwrap();
+        assert_eq!(acme.agent_name(), "acme");
+        assert_eq!(acme.agent_type(), AgentType::Custom("acme".to_string()));
+        // The built-in parser still wins for its own panes
+        let claude = registry.find_parser_for_pane(&pane("claude")).unwrap();
+        assert_eq!(claude.agent_type(), AgentType::ClaudeCode);
+        assert_eq!(registry.parsers.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_content_claude_code() {
+        let registry = ParserRegistry::default();
+        let content = r#"
+⏺ Task(subagent_type="Explore", description="Find the parser entry points")
+  ⎿  Done
+
+Do you want to run this command?
+  cargo test
+
+  Yes
+  No
+
+Context left until auto-compact: 18%
+"#;
+        let (status, subagents, context) = registry.parse_content(&AgentType::ClaudeCode, content);
+        assert!(matches!(
+            status,
+            AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                ..
+            }
+        ));
+        assert_eq!(subagents.len(), 1);
+        assert_eq!(subagents[0].subagent_type, SubagentType::Explore);
+        assert_eq!(context, Some(18));
+    }
+
+    #[test]
+    fn test_parse_content_opencode() {
+        let registry = ParserRegistry::default();
+        let (status, subagents, context) =
+            registry.parse_content(&AgentType::OpenCode, "Thinking about your request...");
+        assert!(matches!(status, AgentStatus::Processing { .. }));
+        assert!(subagents.is_empty());
+        assert_eq!(context, None);
+    }
+
+    #[test]
+    fn test_parse_content_codex_cli() {
+        let registry = ParserRegistry::default();
+        let content = r#"
+• Updated Plan
+  └ ✔ Reproduce the failing test
+    □ Fix the off-by-one
+
+Would you like to run the following command?
+
+  $ cargo test --lib
+
+› 1. Yes, proceed
+  2. No, and tell Codex what to do differently (esc)
+"#;
+        let (status, subagents, context) = registry.parse_content(&AgentType::CodexCli, content);
+        assert_eq!(
+            status,
+            AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "cargo test --lib".to_string(),
+            }
+        );
+        let steps: Vec<(&str, &SubagentStatus)> = subagents
+            .iter()
+            .map(|s| (s.description.as_str(), &s.status))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("Reproduce the failing test", &SubagentStatus::Completed),
+                ("Fix the off-by-one", &SubagentStatus::Running),
+            ]
+        );
+        assert_eq!(context, None);
+    }
+
+    #[test]
+    fn test_parse_content_gemini_cli() {
+        let registry = ParserRegistry::default();
+        let content = "✦ Done. The tests pass now.\n\n~/project (main)   gemini-2.5-pro   Tokens: 45,231 / 1,000,000\n> ";
+        let (status, subagents, context) = registry.parse_content(&AgentType::GeminiCli, content);
+        assert_eq!(status, AgentStatus::Idle);
+        assert!(subagents.is_empty());
+        assert_eq!(context, Some(95));
+    }
+
+    #[test]
+    fn test_parse_content_unknown_type() {
+        let registry = ParserRegistry::default();
+        assert!(registry.find_parser_for_type(&AgentType::Unknown).is_none());
+        let (status, subagents, context) =
+            registry.parse_content(&AgentType::Unknown, "Do you want to proceed? [y/n]");
+        assert_eq!(status, AgentStatus::Unknown);
+        assert!(subagents.is_empty());
+        assert_eq!(context, None);
+    }
 }
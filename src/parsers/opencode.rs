@@ -9,6 +9,7 @@ pub struct OpenCodeParser {
     approval_pattern: Regex,
     processing_pattern: Regex,
     idle_pattern: Regex,
+    context_pattern: Regex,
 }
 
 impl OpenCodeParser {
@@ -20,6 +21,12 @@ impl OpenCodeParser {
             )
             .expect("invalid regex"),
             idle_pattern: Regex::new(r"(?i)(ready|waiting|idle|>\s*$)").expect("invalid regex"),
+            // Context used, as the session header shows it: "24.5K/12%",
+            // "12% context" or "Context: 12%"
+            context_pattern: Regex::new(
+                r"(?i)[\d.,]+[KM]?\s*/\s*(\d{1,3})%|(\d{1,3})%\s+context\b|\bcontext:?\s+(\d{1,3})%",
+            )
+            .expect("invalid regex"),
         }
     }
 }
@@ -73,6 +80,17 @@ impl AgentParser for OpenCodeParser {
         // OpenCode doesn't have subagents
         Vec::new()
     }
+
+    fn parse_context_remaining(&self, content: &str) -> Option<u8> {
+        // OpenCode shows how much is used; the latest header wins
+        let cap = self.context_pattern.captures_iter(content).last()?;
+        let used: u8 = (1..=3)
+            .find_map(|i| cap.get(i))?
+            .as_str()
+            .parse()
+            .ok()?;
+        Some(100u8.saturating_sub(used.min(100)))
+    }
 }
 
 #[cfg(test)]
@@ -95,4 +113,31 @@ mod tests {
 
         assert!(matches!(status, AgentStatus::Processing { .. }));
     }
+
+    #[test]
+    fn test_parse_context_remaining() {
+        let parser = OpenCodeParser::new();
+        let content = r#"
+  # Fix flaky login test                         24.5K/12% ($0.08)
+
+  I'll look at the retry logic in auth/session.ts first.
+
+  > 
+  enter send                                     opencode v0.9.4
+"#;
+        assert_eq!(parser.parse_context_remaining(content), Some(88));
+        assert_eq!(
+            parser.parse_context_remaining("Context: 40%
+...
+85% context"),
+            Some(15)
+        );
+    }
+
+    #[test]
+    fn test_parse_context_remaining_absent() {
+        let parser = OpenCodeParser::new();
+        assert_eq!(parser.parse_context_remaining("Thinking about your request..."), None);
+        assert_eq!(parser.parse_context_remaining("coverage is 85% now"), None);
+    }
 }
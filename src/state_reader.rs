@@ -216,6 +216,9 @@ pub struct DashboardData {
     pub multi_agent: Vec<MultiAgentEntry>,
     pub milestones: Vec<MilestoneData>,
     pub processes: Vec<ProcessData>,
+    /// Sections the hub sent but that didn't parse, as "section: error"; those
+    /// panels fall back to empty
+    pub parse_errors: Vec<String>,
 }
 
 impl DashboardData {
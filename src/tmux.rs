@@ -6,6 +6,27 @@
 use anyhow::{Context, Result};
 use std::process::Command;
 
+// The agent monitor's client (src/monitor, src/ui, `dx list`); the rest of dx
+// drives tmux through the functions below
+#[allow(dead_code)]
+mod capture;
+#[allow(dead_code)]
+mod client;
+#[allow(dead_code)]
+mod pane;
+#[allow(dead_code)]
+mod sessions;
+
+#[allow(unused_imports)]
+pub use self::{
+    capture::CaptureMode,
+    client::{TmuxAvailability, TmuxClient},
+    pane::{process_uptimes, refresh_process_cache, PaneInfo},
+    sessions::SessionScope,
+};
+#[cfg(test)]
+pub(crate) use self::client::ScriptedRunner;
+
 /// Default tmux session for factory agents
 const DEFAULT_SESSION: &str = "claude6";
 
@@ -0,0 +1,80 @@
+/// How a pane's content is captured with `tmux capture-pane`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// Normal screen: visible lines plus scrollback history
+    Scrollback,
+    /// Alternate screen (`#{alternate_on}`): the visible screen only.
+    /// The history belongs to the shell underneath and would confuse the parsers.
+    AlternateScreen,
+    /// The entire history (`-S -`), for exports rather than parsing
+    FullScrollback,
+}
+
+impl CaptureMode {
+    /// Picks the capture mode from the pane's `#{alternate_on}` flag
+    pub fn for_pane(alternate_on: bool) -> Self {
+        if alternate_on {
+            CaptureMode::AlternateScreen
+        } else {
+            CaptureMode::Scrollback
+        }
+    }
+
+    /// Builds the `tmux capture-pane` arguments for `target`
+    pub fn args(self, target: &str, capture_lines: u32) -> Vec<String> {
+        let mut args = vec![
+            "capture-pane".to_string(),
+            "-p".to_string(),
+            "-t".to_string(),
+            target.to_string(),
+        ];
+        match self {
+            CaptureMode::Scrollback => {
+                args.push("-S".to_string());
+                args.push(format!("-{}", capture_lines));
+            }
+            CaptureMode::FullScrollback => {
+                args.push("-S".to_string());
+                args.push("-".to_string());
+            }
+            CaptureMode::AlternateScreen => {}
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_pane() {
+        assert_eq!(CaptureMode::for_pane(false), CaptureMode::Scrollback);
+        assert_eq!(CaptureMode::for_pane(true), CaptureMode::AlternateScreen);
+    }
+
+    #[test]
+    fn test_scrollback_args_include_history() {
+        let args = CaptureMode::Scrollback.args("main:0.1", 200);
+        assert_eq!(
+            args,
+            vec!["capture-pane", "-p", "-t", "main:0.1", "-S", "-200"]
+        );
+    }
+
+    #[test]
+    fn test_alternate_screen_args_skip_history() {
+        let args = CaptureMode::AlternateScreen.args("main:0.1", 200);
+        assert_eq!(args, vec!["capture-pane", "-p", "-t", "main:0.1"]);
+        assert!(!args.iter().any(|a| a == "-S"));
+    }
+
+    #[test]
+    fn test_full_scrollback_args_start_at_history_top() {
+        let args = CaptureMode::FullScrollback.args("main:0.1", 200);
+        assert_eq!(
+            args,
+            vec!["capture-pane", "-p", "-t", "main:0.1", "-S", "-"]
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use std::io;
+use std::process::{Command, Output};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use tracing::info;
+
+use super::capture::CaptureMode;
+use super::pane::{PaneInfo, LIST_PANES_FORMAT, SESSION_ATTACHED_FIELD};
+use super::sessions::SessionScope;
+
+/// Buffer `paste_text` stages text in; deleted again by the paste
+const PASTE_BUFFER: &str = "dx-input";
+
+/// Whether tmux can be used for monitoring, and if not, why
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TmuxAvailability {
+    /// No runnable `tmux` binary on PATH
+    Missing,
+    /// tmux is installed but no server or session is running
+    NoSessions,
+    /// tmux is running with at least one session
+    Available,
+}
+
+impl TmuxAvailability {
+    /// Startup error explaining what to do, or None when tmux is usable
+    pub fn message(self) -> Option<&'static str> {
+        match self {
+            TmuxAvailability::Missing => {
+                Some("tmux is not installed (not found on PATH). Remove --tmux flag for native mode.")
+            }
+            TmuxAvailability::NoSessions => Some(
+                "tmux has no running sessions. Start one with `tmux new`, or remove --tmux flag for native mode.",
+            ),
+            TmuxAvailability::Available => None,
+        }
+    }
+
+    /// Classifies the outcome of running `tmux list-sessions`: spawn failure means
+    /// the binary is missing, a nonzero exit means there is no server/session.
+    fn from_list_sessions(result: std::io::Result<bool>) -> Self {
+        match result {
+            Err(_) => TmuxAvailability::Missing,
+            Ok(false) => TmuxAvailability::NoSessions,
+            Ok(true) => TmuxAvailability::Available,
+        }
+    }
+}
+
+/// Spawns the tmux binary. Tests swap in a scripted runner instead.
+pub(crate) trait CommandRunner: Send + Sync {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output>;
+}
+
+struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        Command::new(program).args(args).output()
+    }
+}
+
+/// Answers tmux commands from a script instead of spawning tmux, and records
+/// each one. Commands without a reply fail to spawn, as if tmux were missing.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct ScriptedRunner {
+    /// (command, success, stdout on success or stderr on failure)
+    replies: Vec<(&'static str, bool, &'static str)>,
+    calls: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl ScriptedRunner {
+    /// Answers every `command` (e.g. `send-keys`) with `output`
+    pub(crate) fn reply(
+        mut self,
+        command: &'static str,
+        success: bool,
+        output: &'static str,
+    ) -> Self {
+        self.replies.push((command, success, output));
+        self
+    }
+
+    /// The commands run so far, arguments joined by spaces
+    pub(crate) fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for ScriptedRunner {
+    fn output(&self, _program: &str, args: &[&str]) -> io::Result<Output> {
+        use std::os::unix::process::ExitStatusExt;
+
+        self.calls.lock().unwrap().push(args.join(" "));
+        let (_, success, text) = self
+            .replies
+            .iter()
+            .find(|(command, _, _)| *command == args[0])
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "tmux"))?;
+        let (stdout, stderr) = if *success { (*text, "") } else { ("", *text) };
+        Ok(Output {
+            status: std::process::ExitStatus::from_raw(if *success { 0 } else { 1 << 8 }),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        })
+    }
+}
+
+/// Runs the tmux commands the agent monitor needs: listing and capturing panes,
+/// and sending to them.
+///
+/// Every command that changes a pane, window or client goes through one gate,
+/// so in dry-run mode (`dry_run` in the config) they are logged instead of run.
+pub struct TmuxClient {
+    program: &'static str,
+    capture_lines: u32,
+    scope: SessionScope,
+    dry_run: bool,
+    runner: Arc<dyn CommandRunner>,
+}
+
+impl Default for TmuxClient {
+    fn default() -> Self {
+        Self {
+            program: "tmux",
+            capture_lines: 100,
+            scope: SessionScope::default(),
+            dry_run: false,
+            runner: Arc::new(SystemRunner),
+        }
+    }
+}
+
+impl TmuxClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capture_lines(capture_lines: u32) -> Self {
+        Self {
+            capture_lines,
+            ..Self::default()
+        }
+    }
+
+    /// Which sessions `list_panes` returns panes from
+    pub fn with_session_scope(mut self, scope: SessionScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Logs sends and other changes instead of running them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Runs tmux commands through `runner` instead of spawning tmux
+    #[cfg(test)]
+    pub(crate) fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Runs a tmux command and returns its output, failing on a nonzero exit
+    fn run(&self, args: &[&str]) -> Result<String> {
+        let output = self
+            .runner
+            .output(self.program, args)
+            .with_context(|| format!("Failed to execute tmux {}", args[0]))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("tmux {} failed: {}", args[0], stderr.trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Runs a tmux command that changes a pane, window or client. In dry-run
+    /// mode it is only logged.
+    fn mutate(&self, args: &[&str]) -> Result<()> {
+        if self.dry_run {
+            info!("dry-run: tmux {}", args.join(" "));
+            return Ok(());
+        }
+        self.run(args).map(drop)
+    }
+
+    /// Whether tmux is installed and has sessions, telling the two failures apart
+    pub fn availability(&self) -> TmuxAvailability {
+        TmuxAvailability::from_list_sessions(
+            self.runner
+                .output(self.program, &["list-sessions"])
+                .map(|o| o.status.success()),
+        )
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.run(&["list-sessions"]).is_ok()
+    }
+
+    /// Panes of every session in scope, with their process details
+    pub fn list_panes(&self) -> Result<Vec<PaneInfo>> {
+        let output = self.run(&["list-panes", "-a", "-F", LIST_PANES_FORMAT])?;
+        Ok(self
+            .scope
+            .filter_lines(&output, SESSION_ATTACHED_FIELD)
+            .filter_map(PaneInfo::parse)
+            .collect())
+    }
+
+    /// Whether `target` still names a pane.
+    ///
+    /// Only tmux answering "can't find" counts as gone. If tmux can't be run
+    /// or fails for another reason, the pane is assumed to be there and the
+    /// command that follows reports the real error, rather than the agent
+    /// being dropped from the list.
+    pub fn pane_exists(&self, target: &str) -> bool {
+        match self.runner.output(
+            self.program,
+            &["display-message", "-t", target, "-p", "#{pane_id}"],
+        ) {
+            Ok(output) if !output.status.success() => {
+                !String::from_utf8_lossy(&output.stderr).contains("can't find")
+            }
+            _ => true,
+        }
+    }
+
+    pub fn capture_pane(&self, target: &str) -> Result<String> {
+        self.capture_pane_mode(target, CaptureMode::Scrollback)
+    }
+
+    pub fn capture_pane_mode(&self, target: &str, mode: CaptureMode) -> Result<String> {
+        self.capture_pane_lines(target, mode, self.capture_lines)
+    }
+
+    /// Like `capture_pane_mode`, with `lines` of scrollback instead of the
+    /// client's default
+    pub fn capture_pane_lines(
+        &self,
+        target: &str,
+        mode: CaptureMode,
+        lines: u32,
+    ) -> Result<String> {
+        let args = mode.args(target, lines);
+        self.run(&args.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    /// The pane's entire history, for exports
+    pub fn capture_full_scrollback(&self, target: &str) -> Result<String> {
+        self.capture_pane_mode(target, CaptureMode::FullScrollback)
+    }
+
+    /// Sends keys by name, e.g. `Enter` or `C-c`
+    pub fn send_keys(&self, target: &str, keys: &str) -> Result<()> {
+        self.mutate(&["send-keys", "-t", target, keys])
+    }
+
+    /// Types `text` as-is, without looking up key names
+    pub fn send_keys_literal(&self, target: &str, text: &str) -> Result<()> {
+        self.mutate(&["send-keys", "-t", target, "-l", "--", text])
+    }
+
+    /// Pastes `text` in one go, bracketed if the pane's program asked for it
+    pub fn paste_text(&self, target: &str, text: &str) -> Result<()> {
+        self.mutate(&["set-buffer", "-b", PASTE_BUFFER, "--", text])?;
+        self.mutate(&["paste-buffer", "-p", "-d", "-b", PASTE_BUFFER, "-t", target])
+    }
+
+    pub fn send_interrupt(&self, target: &str) -> Result<()> {
+        self.send_keys(target, "C-c")
+    }
+
+    pub fn kill_pane(&self, target: &str) -> Result<()> {
+        self.mutate(&["kill-pane", "-t", target])
+    }
+
+    /// Switches the attached client's view to the pane
+    pub fn focus_pane(&self, target: &str) -> Result<()> {
+        self.mutate(&["select-window", "-t", target])?;
+        self.mutate(&["select-pane", "-t", target])
+    }
+
+    /// Selects the pane and toggles its zoom (`resize-pane -Z`). Returns whether
+    /// the window is zoomed afterwards.
+    pub fn zoom_pane(&self, target: &str) -> Result<bool> {
+        self.focus_pane(target)?;
+        self.mutate(&["resize-pane", "-Z", "-t", target])?;
+        let flag = self.run(&[
+            "display-message",
+            "-p",
+            "-t",
+            target,
+            "#{window_zoomed_flag}",
+        ])?;
+        Ok(flag.trim() == "1")
+    }
+
+    /// Opens a new window in `session`, started in `dir`, running `cmd`
+    pub fn new_window_with_command(&self, session: &str, dir: &str, cmd: &str) -> Result<()> {
+        self.mutate(&["new-window", "-t", session, "-c", dir, cmd])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unreachable_tmux(dry_run: bool) -> TmuxClient {
+        TmuxClient {
+            program: "/nonexistent/tmux",
+            ..TmuxClient::new().with_dry_run(dry_run)
+        }
+    }
+
+    #[test]
+    fn test_classifies_tmux_availability() {
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "tmux");
+        assert_eq!(
+            TmuxAvailability::from_list_sessions(Err(missing)),
+            TmuxAvailability::Missing
+        );
+        assert_eq!(
+            TmuxAvailability::from_list_sessions(Ok(false)),
+            TmuxAvailability::NoSessions
+        );
+        assert_eq!(
+            TmuxAvailability::from_list_sessions(Ok(true)),
+            TmuxAvailability::Available
+        );
+        assert!(TmuxAvailability::Missing
+            .message()
+            .unwrap()
+            .contains("not installed"));
+        assert!(TmuxAvailability::NoSessions
+            .message()
+            .unwrap()
+            .contains("no running sessions"));
+        assert_eq!(TmuxAvailability::Available.message(), None);
+    }
+
+    #[test]
+    fn test_dry_run_spawns_nothing() {
+        // Spawning the missing binary would fail, so Ok means nothing was run
+        let client = unreachable_tmux(true);
+        client.send_keys("main:0.1", "Enter").unwrap();
+        client.send_keys_literal("main:0.1", "yes").unwrap();
+        client.paste_text("main:0.1", "two\nlines").unwrap();
+        client.send_interrupt("main:0.1").unwrap();
+        client.kill_pane("main:0.1").unwrap();
+        client.focus_pane("main:0.1").unwrap();
+        client
+            .new_window_with_command("main", "/tmp", "vim .")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_without_dry_run_commands_run() {
+        let client = unreachable_tmux(false);
+        let err = client.send_keys("main:0.1", "Enter").unwrap_err();
+        assert!(err.to_string().contains("send-keys"));
+        assert!(client.kill_pane("main:0.1").is_err());
+        assert!(client.list_panes().is_err());
+        assert_eq!(client.availability(), TmuxAvailability::Missing);
+    }
+
+    fn scripted_tmux(runner: ScriptedRunner) -> TmuxClient {
+        TmuxClient::new().with_runner(Arc::new(runner))
+    }
+
+    #[test]
+    fn test_pane_exists() {
+        let live = scripted_tmux(ScriptedRunner::default().reply("display-message", true, "%3\n"));
+        assert!(live.pane_exists("main:0.1"));
+
+        let closed = scripted_tmux(ScriptedRunner::default().reply(
+            "display-message",
+            false,
+            "can't find pane: 1",
+        ));
+        assert!(!closed.pane_exists("main:0.1"));
+    }
+
+    #[test]
+    fn test_pane_exists_when_tmux_fails() {
+        // Neither failure says anything about the pane, so it must not be dropped
+        let no_server = scripted_tmux(ScriptedRunner::default().reply(
+            "display-message",
+            false,
+            "no server running on /tmp/tmux-1000/default",
+        ));
+        assert!(no_server.pane_exists("main:0.1"));
+
+        let missing = scripted_tmux(ScriptedRunner::default());
+        assert!(missing.pane_exists("main:0.1"));
+        assert_eq!(missing.availability(), TmuxAvailability::Missing);
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// `list-panes -F` format parsed by [`PaneInfo::parse`]. `#{session_attached}`
+/// is the second column so [`super::SessionScope::filter_lines`] can use it.
+pub(super) const LIST_PANES_FORMAT: &str = "#{session_name}\t#{session_attached}\t#{window_index}\t#{window_name}\t#{pane_index}\t#{pane_current_command}\t#{pane_title}\t#{pane_current_path}\t#{pane_pid}\t#{alternate_on}";
+
+/// Column of `#{session_attached}` in [`LIST_PANES_FORMAT`]
+pub(super) const SESSION_ATTACHED_FIELD: usize = 1;
+
+/// Processes running in a pane are looked up this many generations deep, which
+/// covers a shell running a wrapper that runs the agent
+const MAX_CHILD_DEPTH: usize = 3;
+
+/// A tmux pane and the processes running in it
+#[derive(Debug, Clone, Default)]
+pub struct PaneInfo {
+    pub session: String,
+    pub window: u32,
+    pub window_name: String,
+    pub pane: u32,
+    /// `#{pane_current_command}`, usually the shell or the agent's binary name
+    pub command: String,
+    pub title: String,
+    pub path: String,
+    pub pid: u32,
+    /// Full command line of the pane's process
+    pub cmdline: String,
+    /// Command lines of the processes started under the pane's process
+    pub child_commands: Vec<String>,
+    /// The pane is showing the alternate screen (`#{alternate_on}`)
+    pub alternate_on: bool,
+}
+
+impl PaneInfo {
+    /// Parses one line of `list-panes -F` output in [`LIST_PANES_FORMAT`].
+    /// Process details are filled in from the last [`refresh_process_cache`].
+    pub(super) fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [session, _attached, window, window_name, pane, command, title, path, pid, alternate_on] =
+            fields.as_slice()
+        else {
+            return None;
+        };
+        let pid = pid.parse().ok()?;
+        let (cmdline, child_commands) = process_details(pid);
+        Some(Self {
+            session: session.to_string(),
+            window: window.parse().ok()?,
+            window_name: window_name.to_string(),
+            pane: pane.parse().ok()?,
+            command: command.to_string(),
+            title: title.to_string(),
+            path: path.to_string(),
+            pid,
+            cmdline,
+            child_commands,
+            alternate_on: *alternate_on == "1",
+        })
+    }
+
+    /// The `session:window.pane` target tmux commands take
+    pub fn target(&self) -> String {
+        format!("{}:{}.{}", self.session, self.window, self.pane)
+    }
+
+    /// Everything a parser can recognize its agent by: command, title, command
+    /// line, then the child command lines
+    pub fn detection_strings(&self) -> Vec<&str> {
+        let mut strings = vec![
+            self.command.as_str(),
+            self.title.as_str(),
+            self.cmdline.as_str(),
+        ];
+        strings.extend(self.child_commands.iter().map(String::as_str));
+        strings
+    }
+}
+
+/// Command lines and children of every process, from one `ps` run
+#[derive(Debug, Default)]
+struct ProcessTable {
+    cmdlines: HashMap<u32, String>,
+    children: HashMap<u32, Vec<u32>>,
+}
+
+impl ProcessTable {
+    /// Parses `ps -eo pid=,ppid=,args=` output
+    fn parse(output: &str) -> Self {
+        let mut table = Self::default();
+        for line in output.lines() {
+            let mut fields = line.split_whitespace();
+            let (Some(pid), Some(ppid)) = (
+                fields.next().and_then(|f| f.parse::<u32>().ok()),
+                fields.next().and_then(|f| f.parse::<u32>().ok()),
+            ) else {
+                continue;
+            };
+            table
+                .cmdlines
+                .insert(pid, fields.collect::<Vec<_>>().join(" "));
+            table.children.entry(ppid).or_default().push(pid);
+        }
+        table
+    }
+
+    /// Command lines of `pid`'s descendants, nearest generation first
+    fn descendant_cmdlines(&self, pid: u32) -> Vec<String> {
+        let mut cmdlines = Vec::new();
+        let mut generation = vec![pid];
+        for _ in 0..MAX_CHILD_DEPTH {
+            generation = generation
+                .iter()
+                .filter_map(|pid| self.children.get(pid))
+                .flatten()
+                .copied()
+                .collect();
+            cmdlines.extend(
+                generation
+                    .iter()
+                    .filter_map(|pid| self.cmdlines.get(pid).cloned()),
+            );
+        }
+        cmdlines
+    }
+}
+
+static PROCESS_TABLE: Mutex<Option<ProcessTable>> = Mutex::new(None);
+
+/// Re-reads the process table that [`PaneInfo`] takes command lines from.
+/// Called once per poll, so listing panes costs one `ps` instead of one per pane.
+pub fn refresh_process_cache() {
+    let table = Command::new("ps")
+        .args(["-eo", "pid=,ppid=,args="])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| ProcessTable::parse(&String::from_utf8_lossy(&output.stdout)));
+    *PROCESS_TABLE.lock().unwrap_or_else(|e| e.into_inner()) = table;
+}
+
+/// `pid`'s command line and its children's, or nothing before the first refresh
+fn process_details(pid: u32) -> (String, Vec<String>) {
+    let table = PROCESS_TABLE.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(table) = table.as_ref() else {
+        return (String::new(), Vec::new());
+    };
+    (
+        table.cmdlines.get(&pid).cloned().unwrap_or_default(),
+        table.descendant_cmdlines(pid),
+    )
+}
+
+/// How long each of `pids` has been running, from `ps -o etime`. Processes that
+/// are gone are left out.
+pub fn process_uptimes(pids: &[u32]) -> HashMap<u32, Duration> {
+    if pids.is_empty() {
+        return HashMap::new();
+    }
+    let pid_list: Vec<String> = pids.iter().map(u32::to_string).collect();
+    // ps exits nonzero when any pid is gone but still prints the others
+    Command::new("ps")
+        .args(["-o", "pid=,etime=", "-p", &pid_list.join(",")])
+        .output()
+        .map(|output| parse_uptimes(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parses `ps -o pid=,etime=` output
+fn parse_uptimes(output: &str) -> HashMap<u32, Duration> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            Some((pid, parse_etime(fields.next()?)?))
+        })
+        .collect()
+}
+
+/// Parses a `ps` elapsed time, `[[dd-]hh:]mm:ss`
+fn parse_etime(etime: &str) -> Option<Duration> {
+    let (days, clock) = match etime.split_once('-') {
+        Some((days, clock)) => (days.parse::<u64>().ok()?, clock),
+        None => (0, etime),
+    };
+    let parts: Vec<&str> = clock.split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    let mut secs = 0;
+    for part in parts {
+        secs = secs * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(Duration::from_secs(days * 86_400 + secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_panes_line() {
+        let pane =
+            PaneInfo::parse("work\t1\t2\tagents\t1\tclaude\t✳ Fix bug\t/home/me/app\t4242\t1")
+                .unwrap();
+        assert_eq!(pane.target(), "work:2.1");
+        assert_eq!(pane.window_name, "agents");
+        assert_eq!(pane.path, "/home/me/app");
+        assert_eq!(pane.pid, 4242);
+        assert!(pane.alternate_on);
+        assert_eq!(&pane.detection_strings()[..2], &["claude", "✳ Fix bug"]);
+
+        assert!(PaneInfo::parse("work\t1\t2\tagents").is_none());
+        assert!(PaneInfo::parse("work\t1\tx\tagents\t1\tclaude\t\t/\t4242\t0").is_none());
+    }
+
+    #[test]
+    fn test_process_table_descendants() {
+        let table = ProcessTable::parse(
+            "  100     1 -zsh\n\
+               200   100 node /usr/local/bin/claude --resume\n\
+               300   200 rg TODO src\n\
+               400     1 sshd: me\n\
+             garbage\n",
+        );
+        assert_eq!(table.cmdlines[&100], "-zsh");
+        assert_eq!(
+            table.descendant_cmdlines(100),
+            vec!["node /usr/local/bin/claude --resume", "rg TODO src"]
+        );
+        assert!(table.descendant_cmdlines(300).is_empty());
+    }
+
+    #[test]
+    fn test_parse_uptimes() {
+        let uptimes = parse_uptimes(
+            "  4242       00:42\n  4243    01:02:03\n 4244  2-03:00:10\n 4245 soon\n",
+        );
+        assert_eq!(uptimes[&4242], Duration::from_secs(42));
+        assert_eq!(uptimes[&4243], Duration::from_secs(3723));
+        assert_eq!(
+            uptimes[&4244],
+            Duration::from_secs(2 * 86_400 + 3 * 3600 + 10)
+        );
+        assert!(!uptimes.contains_key(&4245));
+        assert_eq!(parse_etime("7"), None);
+        assert!(process_uptimes(&[]).is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+/// Which tmux sessions are scanned for agents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionScope {
+    /// Only sessions with at least one client attached
+    #[default]
+    Attached,
+    /// Every session, attached or not (`--all-sessions`)
+    All,
+}
+
+impl SessionScope {
+    pub fn new(include_detached: bool) -> Self {
+        if include_detached {
+            SessionScope::All
+        } else {
+            SessionScope::Attached
+        }
+    }
+
+    /// Returns true if a pane whose `#{session_attached}` is `session_attached` is kept.
+    ///
+    /// tmux reports the number of attached clients, so any non-zero count is attached.
+    pub fn includes(self, session_attached: &str) -> bool {
+        match self {
+            SessionScope::All => true,
+            SessionScope::Attached => session_attached
+                .trim()
+                .parse::<u32>()
+                .map(|clients| clients > 0)
+                .unwrap_or(false),
+        }
+    }
+
+    /// Keeps the tab-separated `list-panes` lines whose `attached_field` column passes
+    /// [`SessionScope::includes`]
+    pub fn filter_lines<'a>(
+        self,
+        output: &'a str,
+        attached_field: usize,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        output.lines().filter(move |line| {
+            line.split('\t')
+                .nth(attached_field)
+                .is_some_and(|attached| self.includes(attached))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // session_name, session_attached, window_index, pane_index, pane_current_command
+    const LIST_PANES: &str = "work\t1\t0\t0\tclaude\n\
+                              bg\t0\t0\t0\tcodex\n\
+                              pair\t2\t1\t0\tgemini\n";
+
+    fn sessions(scope: SessionScope) -> Vec<&'static str> {
+        scope
+            .filter_lines(LIST_PANES, 1)
+            .map(|line| line.split('\t').next().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_attached_only_skips_detached_sessions() {
+        assert_eq!(sessions(SessionScope::new(false)), vec!["work", "pair"]);
+    }
+
+    #[test]
+    fn test_all_sessions_keeps_detached() {
+        assert_eq!(
+            sessions(SessionScope::new(true)),
+            vec!["work", "bg", "pair"]
+        );
+    }
+
+    #[test]
+    fn test_malformed_attached_field() {
+        assert!(!SessionScope::Attached.includes(""));
+        assert!(SessionScope::All.includes(""));
+        assert_eq!(SessionScope::Attached.filter_lines("short", 1).count(), 0);
+    }
+}
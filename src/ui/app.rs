@@ -1,6 +1,6 @@
-use std::io;
+use std::io::{self, Write};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::{
@@ -11,22 +11,55 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::Rect,
+    widgets::{Paragraph, Wrap},
+    Frame, Terminal,
+};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::mpsc;
 
+use crate::agents::{AgentStatus, MonitoredAgent};
 use crate::hub_client::HubClient;
+use crate::ipc;
 use crate::app::{Action, AppState, Config};
-use crate::monitor::{FactoryCommand, MonitorTask, SystemStatsCollector};
+use crate::monitor::{
+    find_path_collisions, ControlModeSource, FactoryCommand, MonitorTask, ReplaySource,
+    SessionScope, SystemStatsCollector, TmuxBackend,
+};
 use crate::parsers::ParserRegistry;
 use crate::pty::PtyManager;
 use crate::tmux::TmuxClient;
 
 use super::components::{
-    AgentTreeWidget, AnalyticsWidget, DashboardWidget, FactoryPanelWidget, FooterWidget,
-    HeaderWidget, HelpWidget, InputWidget, PanePreviewWidget, QueuePanelWidget,
-    SubagentLogWidget,
+    approval_detail, AgentTreeWidget, AnalyticsWidget, ApprovalDetailWidget, DashboardWidget,
+    DebugOverlayWidget, EventLogWidget, FactoryPanelWidget, FooterWidget, HeaderWidget, HelpWidget,
+    InputWidget, PanePreviewWidget, QueuePanelWidget, SendPreviewWidget, StatsGraphWidget,
+    SubagentLogWidget, PREVIEW_HSCROLL_STEP,
+};
+use super::components::{bumped_priority, selected_task, visible_tasks};
+use super::approval::{approval_steps, question_steps, ChoiceHighlight, PostApprovalGuard};
+use super::audit::{AuditAction, AuditLog};
+use super::auto_approve::AutoApproveGuard;
+use super::confirm::ConfirmGuard;
+use super::clipboard::{approval_details, copy_to_clipboard};
+use super::event_log::{sent_text, EVENT_LOG_SCROLL_STEP};
+use super::export::{export_dir, write_export};
+use super::filter::{
+    clamp_to_filter, cursor_agent, operation_indices, retain_acknowledged, select_attention,
+    select_next_visible, select_prev_visible, session_fold_key, toggle_acknowledged, toggle_fold,
+    toggle_subagents, visible_indices, window_fold_key, window_targets,
+};
+use super::history::{history_path, InputHistory};
+use super::keys::KeysConfig;
+use super::label::agent_label;
+use super::send::{
+    choice_steps, input_steps, multi_select_answer, undo_steps, LastSend, PendingSend, SendStep,
 };
-use super::Layout;
+use super::sort::{apply_sort, sort_agents, SelectionSnapshot};
+use super::layout::MIN_HEIGHT;
+use super::{Layout, PreviewSearch, Spinner, Theme};
 
 /// Runs the main application loop
 pub async fn run_app(config: Config) -> Result<()> {
@@ -39,21 +72,41 @@ pub async fn run_app(config: Config) -> Result<()> {
 
     // Initialize state
     let mut state = AppState::new();
+    state.path_display = config.path_display;
+    state.agent_label = config.agent_label.clone();
+    state.agent_overrides = config.agent_overrides.clone();
+    state.footer_hints = config.footer_hints;
+    state.max_display_agents = config.max_display_agents;
+    state.show_subagents_in_tree = config.show_subagents_in_tree;
+    state.status_colors = config.status_colors.clone();
+    state.project_prefixes = config.project_prefixes.clone();
+    state.show_model = config.show_model;
+    state.sort_mode = config.sort_mode;
+    state.stale_after = Duration::from_secs(config.stale_after_secs);
+    state.theme = Theme::detect(config.no_color);
+    state.dry_run = config.dry_run;
+    state.spinner = Spinner::new(config.spinner, Duration::from_millis(config.spinner_tick_ms));
+    state.input_history = InputHistory::load(&history_path());
     let native_mode = config.native_mode;
 
     // Create tmux client and parser registry
-    let tmux_client = Arc::new(TmuxClient::with_capture_lines(config.capture_lines));
-    let parser_registry = Arc::new(ParserRegistry::new());
+    let tmux_client = Arc::new(
+        TmuxClient::with_capture_lines(config.capture_lines)
+            .with_session_scope(SessionScope::new(config.include_detached))
+            .with_dry_run(config.dry_run),
+    );
+    let parser_registry = Arc::new(ParserRegistry::new(&config.custom_agents));
 
     // Native mode (default): create PTY manager; legacy tmux mode if --tmux
     let mut pty_manager = if native_mode {
         state.flash("DX Terminal v0.3.0".to_string());
         Some(PtyManager::new())
     } else {
-        if !tmux_client.is_available() {
-            state.set_error(
-                "tmux is not running. Remove --tmux flag for native mode.".to_string(),
-            );
+        // Missing binary and a server without sessions need different fixes;
+        // replaying captures doesn't need tmux at all
+        let availability = config.replay_dir.is_none().then(|| tmux_client.availability());
+        if let Some(message) = availability.and_then(|a| a.message()) {
+            state.set_error(message.to_string());
         }
         None
     };
@@ -62,7 +115,12 @@ pub async fn run_app(config: Config) -> Result<()> {
     let hub_client = config
         .api_url
         .as_ref()
-        .map(|url| HubClient::new(Some(url.clone())));
+        .map(|url| {
+            HubClient::new(
+                Some(url.clone()),
+                Duration::from_millis(config.agentos_timeout_ms),
+            )
+        });
 
     // Create channel for monitor updates
     let (tx, mut rx) = mpsc::channel(32);
@@ -71,14 +129,39 @@ pub async fn run_app(config: Config) -> Result<()> {
     let (factory_tx, factory_rx) = mpsc::channel(8);
 
     // Start monitor task (watches tmux panes + hub API)
-    let monitor = MonitorTask::new(
+    let mut monitor = MonitorTask::new(
         tmux_client.clone(),
         parser_registry.clone(),
         hub_client,
         tx,
         factory_rx,
         Duration::from_millis(config.poll_interval_ms),
-    );
+    )
+    .with_activity_source(config.activity_source)
+    .with_agent_capture_lines(config.agent_capture_lines.clone())
+    .with_max_idle_poll(Duration::from_millis(config.max_idle_poll_ms));
+    // --replay: agents come from capture files instead of tmux
+    if let Some(dir) = &config.replay_dir {
+        state.flash(format!("Replaying captures from {}", dir.display()));
+        monitor = monitor.with_pane_source(Arc::new(ReplaySource::new(dir)));
+    } else if config.tmux_backend == TmuxBackend::Control {
+        monitor = monitor.with_pane_source(Arc::new(ControlModeSource::new(
+            tmux_client.clone(),
+            SessionScope::new(config.include_detached),
+            config.capture_lines,
+        )));
+    }
+    // Status changes go out to clients subscribed on the local IPC socket
+    let mut transitions = monitor.subscribe_transitions();
+    let transitions_handle = tokio::spawn(async move {
+        loop {
+            match transitions.recv().await {
+                Ok(transition) => ipc::publish_transition(&transition),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
     let monitor_handle = tokio::spawn(async move {
         monitor.run().await;
     });
@@ -95,11 +178,15 @@ pub async fn run_app(config: Config) -> Result<()> {
         &mut system_stats,
         &factory_tx,
         &mut pty_manager,
+        &config,
     )
     .await;
 
     // Cleanup
     monitor_handle.abort();
+    transitions_handle.abort();
+    // Losing the recall history isn't worth failing the exit over
+    let _ = state.input_history.save(&history_path());
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -111,6 +198,7 @@ pub async fn run_app(config: Config) -> Result<()> {
     result
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     state: &mut AppState,
@@ -119,7 +207,17 @@ async fn run_loop(
     system_stats: &mut SystemStatsCollector,
     factory_tx: &mpsc::Sender<FactoryCommand>,
     pty_manager: &mut Option<PtyManager>,
+    config: &Config,
 ) -> Result<()> {
+    let keys = &config.keys;
+    let mut post_approval = PostApprovalGuard::default();
+    let mut auto_approved = AutoApproveGuard::default();
+    let audit = AuditLog::from_config(&config.audit);
+    let mut kill_confirm = ConfirmGuard::default();
+    let mut last_send: Option<LastSend> = None;
+    // Where the last frame drew the detailed preview
+    let mut preview_area = Rect::default();
+
     loop {
         // Advance animation tick
         state.tick();
@@ -127,6 +225,9 @@ async fn run_loop(
         // Update system stats
         system_stats.refresh();
         state.system_stats = system_stats.stats().clone();
+        if state.show_stats_graph {
+            state.stats_history = system_stats.system_stats_history().iter().copied().collect();
+        }
 
         // Process PTY events in native mode
         if let Some(ref mut mgr) = pty_manager {
@@ -138,91 +239,18 @@ async fn run_loop(
             state.refresh_git_info();
         }
 
-        // Draw UI
-        terminal.draw(|frame| {
-            let size = frame.area();
-            let main_chunks = Layout::main_layout_all_with_analytics(
-                size,
-                state.show_queue,
-                state.show_dashboard,
-                state.show_factory,
-                state.show_analytics,
-            );
-
-            // Header
-            HeaderWidget::render(frame, main_chunks[0], state);
-
-            // Always show input widget at bottom of right column
-            let input_height = InputWidget::calculate_height(state.get_input(), 6);
-
-            if state.show_subagent_log {
-                // With subagent log: sidebar | summary+preview+input | subagent_log
-                let (left, preview, subagent_log) =
-                    Layout::content_layout_with_log(main_chunks[1], state.sidebar_width);
-                AgentTreeWidget::render(frame, left, state);
-
-                // Split preview area for summary, preview, and input
-                let preview_chunks = ratatui::layout::Layout::default()
-                    .direction(ratatui::layout::Direction::Vertical)
-                    .constraints([
-                        ratatui::layout::Constraint::Length(15),
-                        ratatui::layout::Constraint::Min(5),
-                        ratatui::layout::Constraint::Length(input_height + 2),
-                    ])
-                    .split(preview);
-                PanePreviewWidget::render_summary(frame, preview_chunks[0], state);
-                PanePreviewWidget::render_detailed(frame, preview_chunks[1], state);
-                InputWidget::render(frame, preview_chunks[2], state);
-                SubagentLogWidget::render(frame, subagent_log, state);
-            } else {
-                // Normal: sidebar | summary+preview+input
-                let (left, summary, preview, input_area) = Layout::content_layout_with_input(
-                    main_chunks[1],
-                    state.sidebar_width,
-                    input_height,
-                    state.show_summary_detail,
-                );
-                AgentTreeWidget::render(frame, left, state);
-                if state.show_summary_detail {
-                    PanePreviewWidget::render_summary(frame, summary, state);
-                }
-                PanePreviewWidget::render_detailed(frame, preview, state);
-                InputWidget::render(frame, input_area, state);
-            }
-
-            // Analytics panel (only when visible)
-            if state.show_analytics {
-                AnalyticsWidget::render(
-                    frame,
-                    main_chunks[2],
-                    &state.usage_tracker,
-                    &state.git_info_cache,
-                );
-            }
-
-            // Queue panel (only when visible)
-            if state.show_queue {
-                QueuePanelWidget::render(frame, main_chunks[3], state);
-            }
-
-            // Dashboard panel (only when visible)
-            if state.show_dashboard {
-                DashboardWidget::render(frame, main_chunks[4], state);
-            }
-
-            // Factory panel (only when visible)
-            if state.show_factory {
-                FactoryPanelWidget::render(frame, main_chunks[5], state);
-            }
+        // Keep local dashboard data current while the panel is open
+        if reads_local_dashboard(state) {
+            state.refresh_dashboard_if_needed();
+        }
 
-            // Footer
-            FooterWidget::render(frame, main_chunks[6], state);
+        // Re-parse the selected agent's summary only when its content changed
+        if let Some(agent) = state.agents.get_agent(state.selected_index) {
+            state.summary_cache.refresh(&agent.target, &agent.last_content);
+        }
 
-            // Help overlay
-            if state.show_help {
-                HelpWidget::render(frame, size);
-            }
-        })?;
+        // Draw UI
+        terminal.draw(|frame| preview_area = render(frame, state, config))?;
 
         // Handle events with short timeout for responsive UI (~60fps)
         let timeout = Duration::from_millis(16);
@@ -230,11 +258,25 @@ async fn run_loop(
         tokio::select! {
             // Handle monitor updates
             Some(update) = rx.recv() => {
-                state.agents = update.agents;
+                // Agents are matched by target so the cursor survives reordering
+                let selection = SelectionSnapshot::capture(state);
+                let previous = std::mem::replace(&mut state.agents.root_agents, update.agents);
+                sort_agents(&mut state.agents.root_agents, state.sort_mode);
+                selection.restore(state);
+                // Nothing left to show once the approval is answered
+                if state.selected_agent().and_then(approval_detail).is_none() {
+                    state.show_approval_detail = false;
+                }
                 state.queue_tasks = update.queue_tasks;
+                // Keep the queue selection on a listed task as tasks finish
+                let listed = visible_tasks(&state.queue_tasks).len();
+                state.queue_selected = state.queue_selected.min(listed.saturating_sub(1));
                 state.hub_connected = update.hub_connected;
+                state.hub_latency = update.hub_latency;
+                state.hub_fail_count = update.hub_fail_count;
                 if let Some(msg) = update.flash {
-                    state.flash(msg);
+                    // Connection changes must not be hidden by the next "Sent to"
+                    state.flash_critical(msg);
                 }
                 if let Some(d) = update.digest {
                     state.digest = d;
@@ -242,12 +284,46 @@ async fn run_loop(
                 if let Some(a) = update.alerts {
                     state.alerts = a;
                 }
+                // Only sent while connected; replaces whatever the local files said
                 if let Some(d) = update.dashboard {
                     state.dashboard = d;
                 }
                 if let Some(f) = update.factory_requests {
                     state.factory_requests = f;
                 }
+                state.poll_stats = update.poll_stats;
+                post_approval.retain_pending(&state.agents.root_agents);
+                auto_approved.retain_pending(&state.agents.root_agents);
+                state.choice_highlight.retain_questions(&state.agents.root_agents);
+                state.path_collisions = find_path_collisions(&state.agents.root_agents);
+                state
+                    .summary_cache
+                    .retain_targets(state.agents.root_agents.iter().map(|a| a.target.as_str()));
+                // An agent that dropped back to a shell may have crashed
+                let exited: Vec<String> = update
+                    .closed_agents
+                    .iter()
+                    .filter(|closed| closed.exited)
+                    .map(|closed| {
+                        state.event_log.push(&closed.target, "→ exited".to_string());
+                        format!("{} {}", closed.agent_type.short_name(), closed.target)
+                    })
+                    .collect();
+                if !exited.is_empty() {
+                    state.flash_critical(format!("Agent exited: {}", exited.join(", ")));
+                }
+                // Keep closed agents around briefly, unless their pane came back
+                state.recently_closed.extend(update.closed_agents);
+                let agents = &state.agents.root_agents;
+                state.recently_closed.retain(|closed| {
+                    !closed.is_expired() && !agents.iter().any(|a| a.target == closed.target)
+                });
+                state
+                    .toggled_subagents
+                    .retain(|target| agents.iter().any(|a| &a.target == target));
+                // A reviewed agent that moves on, or shows a new prompt, needs
+                // looking at again
+                retain_acknowledged(state, &previous);
                 // Populate agent branches from git cache
                 for agent in state.agents.root_agents.iter_mut() {
                     if agent.branch.is_none() || agent.branch.as_deref() == Some("") {
@@ -257,13 +333,61 @@ async fn run_loop(
                     }
                 }
 
-                // Ensure selected index is valid
-                if state.selected_index >= state.agents.root_agents.len() {
-                    state.selected_index = state.agents.root_agents.len().saturating_sub(1);
+                clamp_selection(state);
+
+                for transition in &update.transitions {
+                    state.event_log.record_transition(transition);
+                }
+                if !update.restarted.is_empty() {
+                    state.flash_critical(format!("Restarted: {}", update.restarted.join(", ")));
+                }
+
+                // Prompts matching an auto-approve rule are answered right away
+                if !config.auto_approve.is_empty() {
+                    let mut approved = Vec::new();
+                    for agent in &state.agents.root_agents {
+                        let Some(rule) = config.auto_approve.matching(agent) else {
+                            continue;
+                        };
+                        if !auto_approved.insert(&agent.target) {
+                            continue;
+                        }
+                        if send_approval(tmux_client, keys, &mut post_approval, &state.choice_highlight, agent).is_ok() {
+                            let details = match &agent.status {
+                                AgentStatus::AwaitingApproval { details, .. } => details.clone(),
+                                _ => String::new(),
+                            };
+                            approved.push((agent.target.clone(), format!("auto-approved ({}): {}", rule.pattern, details)));
+                        }
+                    }
+                    if !approved.is_empty() {
+                        state.flash(format!("Auto-approved {} agent(s)", approved.len()));
+                    }
+                    let targets: Vec<String> = approved.iter().map(|(target, _)| target.clone()).collect();
+                    audit_targets(audit.as_ref(), state, &targets, AuditAction::AutoApprove, None);
+                    for (target, text) in approved {
+                        state.event_log.push(&target, text);
+                    }
+                }
+
+                if config.bell_on_attention && !update.newly_pending.is_empty() {
+                    let backend = terminal.backend_mut();
+                    let _ = backend.write_all(b"\x07").and_then(|_| backend.flush());
+                    let labels: Vec<String> = update
+                        .newly_pending
+                        .iter()
+                        .map(|target| {
+                            state
+                                .agents
+                                .root_agents
+                                .iter()
+                                .find(|a| &a.target == target)
+                                .map(|a| agent_label(state, a))
+                                .unwrap_or_else(|| target.clone())
+                        })
+                        .collect();
+                    state.flash_critical(format!("Needs attention: {}", labels.join(", ")));
                 }
-                // Clean up invalid selections
-                let max_idx = state.agents.root_agents.len();
-                state.selected_agents.retain(|&idx| idx < max_idx);
             }
 
             // Handle keyboard and mouse events
@@ -276,10 +400,14 @@ async fn run_loop(
                     if let Event::Mouse(mouse) = event {
                         let size = terminal.size()?;
                         let area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
-                        let main_chunks = Layout::main_layout_all_with_analytics(area, state.show_queue, state.show_dashboard, state.show_factory, state.show_analytics);
-                        let footer_area = main_chunks[6];
+                        // Only the "too small" message is on screen; there is nothing to click
+                        if Layout::too_small(area) {
+                            continue;
+                        }
+                        let main_chunks = Layout::main_layout_with_stats(area, state.show_queue, state.show_dashboard, state.show_factory, state.show_analytics, state.show_stats_graph);
+                        let footer_area = main_chunks[7];
                         let (sidebar, _, _, input_area) = Layout::content_layout_with_input(
-                            main_chunks[1], state.sidebar_width, 3, state.show_summary_detail
+                            main_chunks[1], state.sidebar_width, 3, state.show_summary_detail, &config.layout
                         );
 
                         match mouse.kind {
@@ -292,46 +420,30 @@ async fn run_loop(
                                     use super::components::FooterButton;
                                     match button {
                                         FooterButton::Approve => {
-                                            let indices = state.get_operation_indices();
-                                            for idx in indices {
-                                                if let Some(agent) = state.agents.get_agent(idx) {
-                                                    if agent.status.needs_attention() {
-                                                        let target = agent.target.clone();
-                                                        let _ = tmux_client.send_keys(&target, "y");
-                                                        let _ = tmux_client.send_keys(&target, "Enter");
-                                                    }
-                                                }
-                                            }
+                                            let indices = operation_indices(state);
+                                            answer_agents(state, tmux_client, keys, &mut post_approval, audit.as_ref(), &indices, Answer::Approve);
                                             state.clear_selection();
                                         }
                                         FooterButton::Reject => {
-                                            let indices = state.get_operation_indices();
-                                            for idx in indices {
-                                                if let Some(agent) = state.agents.get_agent(idx) {
-                                                    if agent.status.needs_attention() {
-                                                        let target = agent.target.clone();
-                                                        let _ = tmux_client.send_keys(&target, "n");
-                                                        let _ = tmux_client.send_keys(&target, "Enter");
-                                                    }
-                                                }
-                                            }
+                                            let indices = operation_indices(state);
+                                            answer_agents(state, tmux_client, keys, &mut post_approval, audit.as_ref(), &indices, Answer::Reject);
                                             state.clear_selection();
                                         }
                                         FooterButton::ApproveAll => {
-                                            for agent in &state.agents.root_agents {
-                                                if agent.status.needs_attention() {
-                                                    let _ = tmux_client.send_keys(&agent.target, "y");
-                                                    let _ = tmux_client.send_keys(&agent.target, "Enter");
-                                                }
-                                            }
+                                            let indices = visible_indices(state);
+                                            answer_agents(state, tmux_client, keys, &mut post_approval, audit.as_ref(), &indices, Answer::Approve);
                                         }
                                         FooterButton::ToggleSelect => {
                                             state.toggle_selection();
                                         }
                                         FooterButton::Focus => {
-                                            if let Some(agent) = state.selected_agent() {
+                                            if let Some(agent) = cursor_agent(state) {
                                                 let target = agent.target.clone();
-                                                let _ = tmux_client.focus_pane(&target);
+                                                if tmux_client.pane_exists(&target) {
+                                                    let _ = tmux_client.focus_pane(&target);
+                                                } else {
+                                                    drop_gone_targets(state, &[target]);
+                                                }
                                             }
                                         }
                                         FooterButton::Help => {
@@ -347,17 +459,11 @@ async fn run_loop(
                                     && y >= sidebar.y && y < sidebar.y + sidebar.height
                                 {
                                     state.focus_sidebar();
-                                    // Calculate which agent was clicked based on row
-                                    // Each agent takes ~4 lines in the tree view (varies)
-                                    // Simple heuristic: use relative row position
-                                    let rel_y = (y - sidebar.y).saturating_sub(1) as usize;
-                                    let agents_count = state.agents.root_agents.len();
-                                    if agents_count > 0 {
-                                        // Estimate ~4 lines per agent (header + info + status)
-                                        let estimated_idx = rel_y / 4;
-                                        if estimated_idx < agents_count {
-                                            state.select_agent(estimated_idx);
-                                        }
+                                    if let Some(idx) = AgentTreeWidget::hit_test(x, y, sidebar, state) {
+                                        state.select_agent(idx);
+                                    } else if let Some(key) = AgentTreeWidget::header_at(x, y, sidebar, state) {
+                                        // Clicking a session or window header folds/unfolds it
+                                        toggle_fold(state, key);
                                     }
                                 }
                                 // Check if click is in input area
@@ -368,10 +474,10 @@ async fn run_loop(
                                 }
                             }
                             MouseEventKind::ScrollUp => {
-                                state.select_prev();
+                                select_prev_visible(state);
                             }
                             MouseEventKind::ScrollDown => {
-                                state.select_next();
+                                select_next_visible(state);
                             }
                             _ => {}
                         }
@@ -380,110 +486,305 @@ async fn run_loop(
 
                     // Handle keyboard events
                     if let Event::Key(key) = event {
-                        let action = map_key_to_action(key.code, key.modifiers, state);
+                        let action = map_key_to_action(key.code, key.modifiers, state, keys);
 
                         match action {
                             Action::Quit => {
                                 state.should_quit = true;
                             }
                             Action::NextAgent => {
-                                state.select_next();
+                                select_next_visible(state);
                             }
                             Action::PrevAgent => {
-                                state.select_prev();
+                                select_prev_visible(state);
+                            }
+                            Action::NextAttention | Action::PrevAttention => {
+                                let forward = matches!(action, Action::NextAttention);
+                                if !select_attention(state, forward) {
+                                    state.flash("No agents need attention".to_string());
+                                }
                             }
                             Action::ToggleSelection => {
                                 state.toggle_selection();
                             }
                             Action::SelectAll => {
-                                state.select_all();
+                                state.selected_agents = visible_indices(state).into_iter().collect();
+                            }
+                            Action::EnterFilter => {
+                                state.agent_filter.get_or_insert_with(String::new);
+                                state.filter_editing = true;
+                            }
+                            Action::FilterChar(c) => {
+                                state.agent_filter.get_or_insert_with(String::new).push(c);
+                                clamp_to_filter(state);
+                            }
+                            Action::FilterBackspace => {
+                                if let Some(filter) = state.agent_filter.as_mut() {
+                                    filter.pop();
+                                }
+                                clamp_to_filter(state);
+                            }
+                            Action::ApplyFilter => {
+                                state.filter_editing = false;
+                                if state.agent_filter.as_deref() == Some("") {
+                                    state.agent_filter = None;
+                                }
+                            }
+                            Action::ClearFilter => {
+                                state.agent_filter = None;
+                                state.filter_editing = false;
                             }
                             Action::ClearSelection => {
                                 state.clear_selection();
                             }
                             Action::Approve => {
-                                let indices = state.get_operation_indices();
-                                let mut approved = 0usize;
-                                for idx in &indices {
-                                    if let Some(agent) = state.agents.get_agent(*idx) {
-                                        if agent.status.needs_attention() {
-                                            let target = agent.target.clone();
-                                            if let Err(e) = tmux_client.send_keys(&target, "y") {
-                                                state.set_error(format!("Failed to approve: {}", e));
-                                                break;
-                                            }
-                                            if let Err(e) = tmux_client.send_keys(&target, "Enter") {
-                                                state.set_error(format!("Failed to send Enter: {}", e));
-                                                break;
-                                            }
-                                            approved += 1;
-                                        }
-                                    }
-                                }
+                                let indices = operation_indices(state);
+                                let approved = answer_agents(state, tmux_client, keys, &mut post_approval, audit.as_ref(), &indices, Answer::Approve);
                                 if approved > 0 {
                                     state.flash(format!("Approved {} agent(s)", approved));
                                 }
                                 state.clear_selection();
                             }
                             Action::Reject => {
-                                let indices = state.get_operation_indices();
-                                for idx in indices {
-                                    if let Some(agent) = state.agents.get_agent(idx) {
-                                        if agent.status.needs_attention() {
-                                            let target = agent.target.clone();
-                                            if let Err(e) = tmux_client.send_keys(&target, "n") {
-                                                state.set_error(format!("Failed to reject: {}", e));
-                                                break;
-                                            }
-                                            if let Err(e) = tmux_client.send_keys(&target, "Enter") {
-                                                state.set_error(format!("Failed to send Enter: {}", e));
-                                                break;
+                                let indices = operation_indices(state);
+                                answer_agents(state, tmux_client, keys, &mut post_approval, audit.as_ref(), &indices, Answer::Reject);
+                                state.clear_selection();
+                            }
+                            Action::ApproveAll => {
+                                let indices = visible_indices(state);
+                                answer_agents(state, tmux_client, keys, &mut post_approval, audit.as_ref(), &indices, Answer::Approve);
+                            }
+                            Action::FocusPane => {
+                                if let Some(agent) = cursor_agent(state) {
+                                    let target = agent.target.clone();
+                                    if !tmux_client.pane_exists(&target) {
+                                        drop_gone_targets(state, &[target]);
+                                    } else if let Err(e) = tmux_client.focus_pane(&target) {
+                                        state.set_error(format!("Failed to focus: {}", e));
+                                    }
+                                }
+                            }
+                            Action::FoldWindow | Action::FoldSession => {
+                                if let Some(agent) = cursor_agent(state) {
+                                    let key = if matches!(action, Action::FoldWindow) {
+                                        window_fold_key(&agent.session, agent.window)
+                                    } else {
+                                        session_fold_key(&agent.session)
+                                    };
+                                    toggle_fold(state, key);
+                                }
+                            }
+                            Action::NextChoice | Action::PrevChoice => {
+                                let forward = matches!(action, Action::NextChoice);
+                                let visible = cursor_agent(state).is_some();
+                                if let Some(agent) = state.agents.get_agent(state.selected_index).filter(|_| visible) {
+                                    state.choice_highlight.cycle(agent, forward);
+                                }
+                            }
+                            Action::ToggleAcknowledged => {
+                                toggle_acknowledged(state);
+                            }
+                            Action::UnfoldAll => {
+                                state.folded_groups.clear();
+                            }
+                            Action::ZoomPane => {
+                                if let Some(agent) = cursor_agent(state) {
+                                    let target = agent.target.clone();
+                                    if !tmux_client.pane_exists(&target) {
+                                        drop_gone_targets(state, &[target]);
+                                    } else {
+                                        match tmux_client.zoom_pane(&target) {
+                                            Ok(true) => state.flash(format!("Zoomed {}", target)),
+                                            Ok(false) => state.flash(format!("Unzoomed {}", target)),
+                                            Err(e) => state.set_error(format!("Failed to zoom: {}", e)),
+                                        }
+                                    }
+                                }
+                            }
+                            Action::OpenEditor => {
+                                if let Some(agent) = cursor_agent(state) {
+                                    let (session, path) = (agent.session.clone(), agent.path.clone());
+                                    match editor_from_env() {
+                                        None => state.set_error(
+                                            "Set $EDITOR or $VISUAL to open an editor".to_string(),
+                                        ),
+                                        Some(editor) => {
+                                            // The window starts in the agent's directory; `.`
+                                            // opens it rather than an empty buffer
+                                            let command = format!("{} .", editor);
+                                            match tmux_client.new_window_with_command(&session, &path, &command) {
+                                                Ok(()) => state.flash(format!("Opened {} in {}", editor, path)),
+                                                Err(e) => state.set_error(format!("Failed to open editor: {}", e)),
                                             }
                                         }
                                     }
                                 }
-                                state.clear_selection();
                             }
-                            Action::ApproveAll => {
-                                for agent in &state.agents.root_agents {
-                                    if agent.status.needs_attention() {
-                                        if let Err(e) = tmux_client.send_keys(&agent.target, "y") {
-                                            state.set_error(format!("Failed to approve {}: {}", agent.target, e));
-                                            break;
+                            Action::KillAgent { close_pane } => {
+                                let targets: Vec<String> = operation_indices(state)
+                                    .into_iter()
+                                    .filter_map(|idx| state.agents.get_agent(idx))
+                                    .map(|agent| agent.target.clone())
+                                    .collect();
+                                let (verb, key_hint) = if close_pane {
+                                    ("close", "Alt+x")
+                                } else {
+                                    ("interrupt", "x")
+                                };
+                                // Closing panes or hitting several agents needs a second press
+                                let needs_confirm =
+                                    !targets.is_empty() && (close_pane || targets.len() > 1);
+                                let confirm_key = format!("{} {}", verb, targets.join(","));
+                                if needs_confirm && !kill_confirm.confirm(&confirm_key) {
+                                    state.flash(format!(
+                                        "Press {} again to {} {} agent(s)",
+                                        key_hint,
+                                        verb,
+                                        targets.len()
+                                    ));
+                                } else {
+                                    let mut done = 0usize;
+                                    let mut gone = Vec::new();
+                                    for target in targets {
+                                        if !tmux_client.pane_exists(&target) {
+                                            gone.push(target);
+                                            continue;
                                         }
-                                        if let Err(e) = tmux_client.send_keys(&agent.target, "Enter") {
-                                            state.set_error(format!("Failed to send Enter to {}: {}", agent.target, e));
+                                        let result = if close_pane {
+                                            tmux_client.kill_pane(&target)
+                                        } else {
+                                            tmux_client.send_interrupt(&target)
+                                        };
+                                        if let Err(e) = result {
+                                            state.set_error(format!("Failed to {} {}: {}", verb, target, e));
                                             break;
                                         }
+                                        done += 1;
                                     }
+                                    if done > 0 {
+                                        if close_pane {
+                                            state.flash(format!("Closed {} pane(s)", done));
+                                        } else {
+                                            state.flash(format!("Interrupted {} agent(s)", done));
+                                        }
+                                    }
+                                    state.clear_selection();
+                                    drop_gone_targets(state, &gone);
                                 }
                             }
-                            Action::FocusPane => {
-                                if let Some(agent) = state.selected_agent() {
-                                    let target = agent.target.clone();
-                                    if let Err(e) = tmux_client.focus_pane(&target) {
-                                        state.set_error(format!("Failed to focus: {}", e));
+                            Action::SendEscape => {
+                                let targets: Vec<String> = operation_indices(state)
+                                    .into_iter()
+                                    .filter_map(|idx| state.agents.get_agent(idx))
+                                    .map(|agent| agent.target.clone())
+                                    .collect();
+                                let mut sent = 0usize;
+                                let mut gone = Vec::new();
+                                for target in targets {
+                                    if !tmux_client.pane_exists(&target) {
+                                        gone.push(target);
+                                        continue;
+                                    }
+                                    if let Err(e) = tmux_client.send_keys(&target, "Escape") {
+                                        state.set_error(format!("Failed to send Escape to {}: {}", target, e));
+                                        break;
                                     }
+                                    sent += 1;
                                 }
+                                if sent > 0 {
+                                    state.flash(format!("Sent Escape to {} agent(s)", sent));
+                                }
+                                drop_gone_targets(state, &gone);
                             }
                             Action::ToggleSubagentLog => {
                                 state.toggle_subagent_log();
                             }
+                            Action::ToggleEventLog => {
+                                state.show_event_log = !state.show_event_log;
+                            }
+                            Action::EventLogUp => {
+                                state.event_log.scroll_up(EVENT_LOG_SCROLL_STEP);
+                            }
+                            Action::EventLogDown => {
+                                state.event_log.scroll_down(EVENT_LOG_SCROLL_STEP);
+                            }
+                            Action::ToggleSubagentsInline => {
+                                toggle_subagents(state);
+                            }
                             Action::ToggleSummaryDetail => {
                                 state.toggle_summary_detail();
                             }
                             Action::Refresh => {
                                 state.clear_error();
                             }
+                            Action::Reconnect => {
+                                let _ = factory_tx.try_send(FactoryCommand::Reconnect);
+                                state.flash("Reconnecting to AgentOS".to_string());
+                            }
                             Action::ShowHelp => {
                                 state.toggle_help();
                             }
                             Action::HideHelp => {
                                 state.show_help = false;
                             }
+                            Action::ShowApprovalDetail => {
+                                if cursor_agent(state).and_then(approval_detail).is_some() {
+                                    state.show_approval_detail = true;
+                                } else {
+                                    state.flash("No pending approval to show".to_string());
+                                }
+                            }
+                            Action::HideApprovalDetail => {
+                                state.show_approval_detail = false;
+                            }
+                            Action::CycleSort => {
+                                state.sort_mode = state.sort_mode.next();
+                                apply_sort(state);
+                                state.flash(format!("Sort: {}", state.sort_mode.label()));
+                            }
+                            Action::ToggleFullPaths => {
+                                state.show_full_paths = !state.show_full_paths;
+                            }
+                            Action::ExportPane => {
+                                if let Some(agent) = cursor_agent(state) {
+                                    let target = agent.target.clone();
+                                    let result = tmux_client
+                                        .capture_full_scrollback(&target)
+                                        .and_then(|content| {
+                                            Ok(write_export(&export_dir(), agent, &content)?)
+                                        });
+                                    match result {
+                                        Ok(path) => {
+                                            state.flash(format!("Exported to {}", path.display()))
+                                        }
+                                        Err(e) => state.set_error(format!(
+                                            "Failed to export {}: {}",
+                                            target, e
+                                        )),
+                                    }
+                                }
+                            }
+                            Action::CopyDetails => {
+                                let details = cursor_agent(state)
+                                    .and_then(approval_details)
+                                    .map(str::to_string);
+                                match details {
+                                    Some(details) => match copy_to_clipboard(&details) {
+                                        Ok(()) => state.flash("Copied".to_string()),
+                                        Err(e) => state.set_error(format!("Failed to copy: {}", e)),
+                                    },
+                                    None => state.set_error("No approval details to copy".to_string()),
+                                }
+                            }
+                            Action::ToggleDebugOverlay => {
+                                state.toggle_debug_overlay();
+                            }
                             Action::FocusInput => {
                                 state.focus_input();
                             }
+                            Action::FocusPreview => {
+                                state.focus_preview();
+                            }
                             Action::FocusSidebar => {
                                 state.focus_sidebar();
                             }
@@ -492,12 +793,29 @@ async fn run_loop(
                             }
                             Action::InputChar(c) => {
                                 state.input_char(c);
+                                state.input_history.reset_position();
                             }
                             Action::InputNewline => {
                                 state.input_newline();
+                                state.input_history.reset_position();
                             }
                             Action::InputBackspace => {
                                 state.input_backspace();
+                                state.input_history.reset_position();
+                            }
+                            Action::HistoryPrev => {
+                                let input = state.get_input().to_string();
+                                if input.contains('\n') && state.get_cursor_position() > 0 {
+                                    // Don't swap out a multi-line draft on a stray Up
+                                    state.flash("Home, then Up to recall history over this draft".to_string());
+                                } else if let Some(text) = state.input_history.older(&input).map(str::to_string) {
+                                    replace_input(state, &text);
+                                }
+                            }
+                            Action::HistoryNext => {
+                                if let Some(text) = state.input_history.newer().map(str::to_string) {
+                                    replace_input(state, &text);
+                                }
                             }
                             Action::CursorLeft => {
                                 state.cursor_left();
@@ -511,12 +829,14 @@ async fn run_loop(
                             Action::CursorEnd => {
                                 state.cursor_end();
                             }
-                            Action::SendInput => {
+                            Action::SendInput | Action::SendInputBlock => {
+                                let as_block = matches!(action, Action::SendInputBlock);
                                 let input = state.take_input();
+                                state.input_history.push(&input);
                                 if !input.is_empty() {
-                                    if let Some(agent) = state.selected_agent() {
+                                    if let Some(agent) = cursor_agent(state) {
                                         let target = agent.target.clone();
-                                        let agent_path = agent.abbreviated_path();
+                                        let agent_path = agent_label(state, agent);
                                         let pane_num = agent.pane as u8 + 1;
 
                                         // Use PTY manager in native mode, tmux otherwise
@@ -525,47 +845,131 @@ async fn run_loop(
                                                 state.set_error(format!("Failed to send input: {}", e));
                                             } else {
                                                 state.flash(format!("Sent to {}", agent_path));
+                                                state.event_log.push(&target, sent_text(&input));
+                                                audit_targets(audit.as_ref(), state, std::slice::from_ref(&target), AuditAction::Send, Some(&input));
                                             }
-                                        } else {
-                                            if let Err(e) = tmux_client.send_keys_literal(&target, &input) {
-                                                state.set_error(format!("Failed to send input: {}", e));
-                                            } else if let Err(e) = tmux_client.send_keys(&target, "Enter") {
-                                                state.set_error(format!("Failed to send Enter: {}", e));
+                                        } else if !tmux_client.pane_exists(&target) {
+                                            drop_gone_targets(state, &[target]);
+                                        } else if let Some(numbers) = multi_select_answer(agent, &input) {
+                                            // "1 3" answers a multi-select question: toggle each, then submit
+                                            if let Err(e) = send_steps(tmux_client, &target, &choice_steps(&numbers)) {
+                                                state.set_error(format!("Failed to send choices: {}", e));
                                             } else {
-                                                state.flash(format!("Sent to {}", agent_path));
+                                                state.flash(format!("Picked {} for {}", input.trim(), agent_path));
+                                                state.event_log.push(&target, sent_text(&input));
+                                                audit_targets(audit.as_ref(), state, std::slice::from_ref(&target), AuditAction::Send, Some(&input));
                                             }
+                                        } else if let Some(pending) = PendingSend::for_agent(agent, agent_path.clone(), &input, as_block) {
+                                            // Show the expanded text first; Enter sends it
+                                            state.pending_send = Some(pending);
+                                        } else if let Err(e) = send_steps(tmux_client, &target, &input_steps(&input, as_block)) {
+                                            state.set_error(format!("Failed to send input: {}", e));
+                                        } else {
+                                            state.flash(format!("Sent to {}", agent_path));
+                                            state.event_log.push(&target, sent_text(&input));
+                                            audit_targets(audit.as_ref(), state, std::slice::from_ref(&target), AuditAction::Send, Some(&input));
+                                            last_send = Some(LastSend::new(target));
                                         }
                                     }
                                 }
                                 // Stay in input mode for consecutive inputs
                             }
+                            Action::ConfirmSend => {
+                                if let Some(pending) = state.pending_send.take() {
+                                    let steps = input_steps(&pending.text, pending.as_block);
+                                    if !tmux_client.pane_exists(&pending.target) {
+                                        drop_gone_targets(state, &[pending.target]);
+                                    } else if let Err(e) = send_steps(tmux_client, &pending.target, &steps) {
+                                        state.set_error(format!("Failed to send input: {}", e));
+                                    } else {
+                                        state.flash(format!("Sent to {}", pending.label));
+                                        state.event_log.push(&pending.target, sent_text(&pending.text));
+                                        audit_targets(audit.as_ref(), state, std::slice::from_ref(&pending.target), AuditAction::Send, Some(&pending.text));
+                                        last_send = Some(LastSend::new(pending.target));
+                                    }
+                                }
+                            }
+                            Action::CancelSend => {
+                                state.pending_send = None;
+                                state.flash("Send cancelled".to_string());
+                            }
+                            Action::UndoSend => match last_send.take() {
+                                Some(last) if last.is_undoable(Instant::now()) => {
+                                    if let Err(e) = send_steps(tmux_client, &last.target, &undo_steps(&keys.undo_send)) {
+                                        state.set_error(format!("Failed to undo: {}", e));
+                                    } else {
+                                        state.flash(format!("Undo sent to {} (best-effort)", last.target));
+                                    }
+                                }
+                                _ => state.flash("Nothing to undo".to_string()),
+                            },
                             Action::SendInputToAll => {
                                 let input = state.take_input();
+                                state.input_history.push(&input);
                                 if !input.is_empty() {
-                                    let indices = state.get_operation_indices();
-                                    let mut sent = 0usize;
+                                    // Undo only covers single-agent sends
+                                    last_send = None;
+                                    let indices = operation_indices(state);
+                                    let mut sent = Vec::new();
+                                    let mut gone = Vec::new();
                                     for idx in &indices {
                                         if let Some(agent) = state.agents.get_agent(*idx) {
                                             let target = agent.target.clone();
-                                            if tmux_client.send_keys_literal(&target, &input).is_ok() {
-                                                let _ = tmux_client.send_keys(&target, "Enter");
-                                                sent += 1;
+                                            if !tmux_client.pane_exists(&target) {
+                                                gone.push(target);
+                                                continue;
+                                            }
+                                            if send_steps(tmux_client, &target, &input_steps(&input, false)).is_ok() {
+                                                sent.push(target);
                                             }
                                         }
                                     }
-                                    if sent > 0 {
-                                        state.flash(format!("Sent to {} agent(s)", sent));
+                                    if !sent.is_empty() {
+                                        state.flash(format!("Sent to {} agent(s)", sent.len()));
+                                    }
+                                    state.event_log.record_action(&sent, &sent_text(&input));
+                                    audit_targets(audit.as_ref(), state, &sent, AuditAction::Send, Some(&input));
+                                    drop_gone_targets(state, &gone);
+                                }
+                            }
+                            Action::SendInputToWindow => {
+                                let input = state.take_input();
+                                state.input_history.push(&input);
+                                if !input.is_empty() {
+                                    // Undo only covers single-agent sends
+                                    last_send = None;
+                                    let mut sent = Vec::new();
+                                    let mut gone = Vec::new();
+                                    for target in window_targets(state) {
+                                        if !tmux_client.pane_exists(&target) {
+                                            gone.push(target);
+                                            continue;
+                                        }
+                                        if send_steps(tmux_client, &target, &input_steps(&input, false)).is_ok() {
+                                            sent.push(target);
+                                        }
+                                    }
+                                    if !sent.is_empty() {
+                                        state.flash(format!("Sent to {} agent(s) in window", sent.len()));
                                     }
+                                    state.event_log.record_action(&sent, &sent_text(&input));
+                                    audit_targets(audit.as_ref(), state, &sent, AuditAction::Send, Some(&input));
+                                    drop_gone_targets(state, &gone);
                                 }
                             }
                             Action::SendNumber(num) => {
-                                if let Some(agent) = state.selected_agent() {
+                                if let Some(agent) = cursor_agent(state) {
                                     let target = agent.target.clone();
                                     let num_str = num.to_string();
-                                    if let Err(e) = tmux_client.send_keys(&target, &num_str) {
+                                    if !tmux_client.pane_exists(&target) {
+                                        drop_gone_targets(state, &[target]);
+                                    } else if let Err(e) = tmux_client.send_keys(&target, &num_str) {
                                         state.set_error(format!("Failed to send number: {}", e));
                                     } else if let Err(e) = tmux_client.send_keys(&target, "Enter") {
                                         state.set_error(format!("Failed to send Enter: {}", e));
+                                    } else {
+                                        state.event_log.push(&target, sent_text(&num_str));
+                                        audit_targets(audit.as_ref(), state, std::slice::from_ref(&target), AuditAction::Send, Some(&num_str));
                                     }
                                 }
                             }
@@ -586,6 +990,50 @@ async fn run_loop(
                             }
                             Action::ToggleQueue => {
                                 state.toggle_queue();
+                                if !state.show_queue && state.is_queue_focused() {
+                                    state.focus_sidebar();
+                                }
+                            }
+                            Action::FocusQueue => {
+                                state.show_queue = true;
+                                state.focus_queue();
+                            }
+                            Action::QueueNext => {
+                                let listed = visible_tasks(&state.queue_tasks).len();
+                                if state.queue_selected + 1 < listed {
+                                    state.queue_selected += 1;
+                                }
+                            }
+                            Action::QueuePrev => {
+                                state.queue_selected = state.queue_selected.saturating_sub(1);
+                            }
+                            Action::CancelQueueTask => {
+                                match selected_task(state) {
+                                    Some(task) if task.status == "done" || task.status == "failed" => {
+                                        state.flash(format!("Queue: {} already {}", task.id, task.status));
+                                    }
+                                    Some(task) => {
+                                        let id = task.id.clone();
+                                        state.flash(format!("Queue: cancelling {}...", id));
+                                        let _ = factory_tx.try_send(FactoryCommand::CancelTask { id });
+                                    }
+                                    None => {}
+                                }
+                            }
+                            Action::RaiseQueuePriority | Action::LowerQueuePriority => {
+                                let raise = matches!(action, Action::RaiseQueuePriority);
+                                if let Some(task) = selected_task(state) {
+                                    match bumped_priority(task.priority, raise) {
+                                        Some(priority) => {
+                                            let id = task.id.clone();
+                                            state.flash(format!("Queue: setting {} to P{}...", id, priority));
+                                            let _ = factory_tx.try_send(FactoryCommand::SetTaskPriority { id, priority });
+                                        }
+                                        None => {
+                                            state.flash(format!("Queue: {} is already P{}", task.id, task.priority));
+                                        }
+                                    }
+                                }
                             }
                             Action::ToggleDashboard => {
                                 state.toggle_dashboard();
@@ -593,6 +1041,9 @@ async fn run_loop(
                             Action::ToggleFactory => {
                                 state.toggle_factory();
                             }
+                            Action::ToggleStatsGraph => {
+                                state.show_stats_graph = !state.show_stats_graph;
+                            }
                             Action::ToggleAnalytics => {
                                 state.toggle_analytics();
                             }
@@ -626,6 +1077,65 @@ async fn run_loop(
                             Action::PreviewScrollBottom => {
                                 state.preview_scroll_reset();
                             }
+                            Action::PreviewLineUp => {
+                                state.preview_scroll_up(1);
+                            }
+                            Action::PreviewLineDown => {
+                                state.preview_scroll_down(1);
+                            }
+                            // No further right than the widest line on screen needs;
+                            // the content may have narrowed since the last step
+                            Action::PreviewScrollLeft => {
+                                let max = PanePreviewWidget::max_hscroll(preview_area, state);
+                                state.preview_hscroll =
+                                    state.preview_hscroll.min(max).saturating_sub(PREVIEW_HSCROLL_STEP);
+                            }
+                            Action::PreviewScrollRight => {
+                                let max = PanePreviewWidget::max_hscroll(preview_area, state);
+                                state.preview_hscroll =
+                                    (state.preview_hscroll + PREVIEW_HSCROLL_STEP).min(max);
+                            }
+                            Action::TogglePreviewWrap => {
+                                state.preview_wrap = !state.preview_wrap;
+                                state.preview_hscroll = 0;
+                            }
+                            Action::TogglePreviewLineNumbers => {
+                                state.preview_line_numbers = !state.preview_line_numbers;
+                            }
+                            Action::EnterPreviewSearch => {
+                                state.preview_search = PreviewSearch {
+                                    editing: true,
+                                    ..PreviewSearch::default()
+                                };
+                            }
+                            Action::PreviewSearchChar(c) => {
+                                state.preview_search.query.push(c);
+                                jump_to_search_match(state, |search, content| {
+                                    search.jump_to_last(content)
+                                });
+                            }
+                            Action::PreviewSearchBackspace => {
+                                state.preview_search.query.pop();
+                                jump_to_search_match(state, |search, content| {
+                                    search.jump_to_last(content)
+                                });
+                            }
+                            Action::ApplyPreviewSearch => {
+                                state.preview_search.editing = false;
+                            }
+                            Action::ClearPreviewSearch => {
+                                state.preview_search = PreviewSearch::default();
+                            }
+                            Action::PreviewSearchNext => {
+                                jump_to_search_match(state, |search, content| {
+                                    search.step(content, 1)
+                                });
+                            }
+                            Action::PreviewSearchPrev => {
+                                jump_to_search_match(state, |search, content| {
+                                    search.step(content, -1)
+                                });
+                            }
                             Action::None => {}
                         }
                     }
@@ -641,12 +1151,388 @@ async fn run_loop(
     Ok(())
 }
 
-fn map_key_to_action(code: KeyCode, modifiers: KeyModifiers, state: &AppState) -> Action {
+/// Draws one frame of the whole UI and returns where the detailed preview went,
+/// which horizontal scrolling is clamped to. Kept free of terminal I/O so it
+/// can be exercised against a `TestBackend`.
+fn render(frame: &mut Frame, state: &AppState, config: &Config) -> Rect {
+    let size = frame.area();
+    if Layout::too_small(size) {
+        let notice = format!("Terminal too small (need ≥{} rows)", MIN_HEIGHT);
+        frame.render_widget(Paragraph::new(notice).wrap(Wrap { trim: true }), size);
+        return Rect::default();
+    }
+    let main_chunks = Layout::main_layout_with_stats(
+        size,
+        state.show_queue,
+        state.show_dashboard,
+        state.show_factory,
+        state.show_analytics,
+        state.show_stats_graph,
+    );
+
+    // Header
+    HeaderWidget::render(frame, main_chunks[0], state);
+
+    // Input widget is always shown in the right column (top or bottom)
+    let input_height = InputWidget::calculate_height(state.get_input(), 6);
+
+    let preview_area = if state.show_subagent_log || state.show_event_log {
+        // With a log: sidebar | summary+preview+input | subagent_log and/or event_log
+        let (left, preview, log_area) = Layout::content_layout_with_log(
+            main_chunks[1],
+            state.sidebar_width,
+            &config.layout,
+        );
+        AgentTreeWidget::render(frame, left, state);
+
+        // Split preview area for summary, preview, and input
+        let (summary, preview, input_area) =
+            Layout::right_column(preview, input_height, true, &config.layout);
+        PanePreviewWidget::render_summary(frame, summary, state);
+        PanePreviewWidget::render_detailed(frame, preview, state);
+        InputWidget::render(frame, input_area, state);
+        match (state.show_subagent_log, state.show_event_log) {
+            (true, true) => {
+                let (subagent_log, event_log) = Layout::split_logs(log_area);
+                SubagentLogWidget::render(frame, subagent_log, state);
+                EventLogWidget::render(frame, event_log, state);
+            }
+            (true, false) => SubagentLogWidget::render(frame, log_area, state),
+            _ => EventLogWidget::render(frame, log_area, state),
+        }
+        preview
+    } else {
+        // Normal: sidebar | summary+preview+input
+        let (left, summary, preview, input_area) = Layout::content_layout_with_input(
+            main_chunks[1],
+            state.sidebar_width,
+            input_height,
+            state.show_summary_detail,
+            &config.layout,
+        );
+        AgentTreeWidget::render(frame, left, state);
+        if state.show_summary_detail {
+            PanePreviewWidget::render_summary(frame, summary, state);
+        }
+        PanePreviewWidget::render_detailed(frame, preview, state);
+        InputWidget::render(frame, input_area, state);
+        preview
+    };
+
+    // Analytics panel (only when visible)
+    if state.show_analytics {
+        AnalyticsWidget::render(
+            frame,
+            main_chunks[2],
+            &state.usage_tracker,
+            &state.git_info_cache,
+        );
+    }
+
+    // Queue panel (only when visible)
+    if state.show_queue {
+        QueuePanelWidget::render(frame, main_chunks[3], state);
+    }
+
+    // Dashboard panel (only when visible)
+    if state.show_dashboard {
+        DashboardWidget::render(frame, main_chunks[4], state);
+    }
+
+    // Factory panel (only when visible)
+    if state.show_factory {
+        FactoryPanelWidget::render(frame, main_chunks[5], state);
+    }
+
+    // Stats graph panel (only when visible)
+    if state.show_stats_graph {
+        StatsGraphWidget::render(frame, main_chunks[6], state);
+    }
+
+    // Footer
+    FooterWidget::render(frame, main_chunks[7], state);
+
+    // Debug overlay
+    if state.show_debug_overlay {
+        DebugOverlayWidget::render(frame, size, state);
+    }
+
+    // Full approval detail
+    if state.show_approval_detail {
+        if let Some(agent) = cursor_agent(state) {
+            ApprovalDetailWidget::render(frame, size, agent);
+        }
+    }
+
+    // Expanded input waiting for confirmation
+    if let Some(ref pending) = state.pending_send {
+        SendPreviewWidget::render(frame, size, pending);
+    }
+
+    // Help overlay
+    if state.show_help {
+        HelpWidget::render(frame, size);
+    }
+
+    // NO_COLOR: strip whatever colors the widgets picked
+    state.theme.apply(frame.buffer_mut());
+    preview_area
+}
+
+/// Editor command for OpenEditor: `$EDITOR`, else `$VISUAL`
+fn editor_from_env() -> Option<String> {
+    pick_editor(std::env::var("EDITOR").ok(), std::env::var("VISUAL").ok())
+}
+
+fn pick_editor(editor: Option<String>, visual: Option<String>) -> Option<String> {
+    [editor, visual]
+        .into_iter()
+        .flatten()
+        .map(|cmd| cmd.trim().to_string())
+        .find(|cmd| !cmd.is_empty())
+}
+
+/// Replaces the input box content with `text`, leaving the cursor at its end
+fn replace_input(state: &mut AppState, text: &str) {
+    state.take_input();
+    for c in text.chars() {
+        state.input_char(c);
+    }
+}
+
+/// Runs a preview search move on the selected agent's content and scrolls the
+/// preview to the match it lands on; without a match the scroll stays put
+fn jump_to_search_match(
+    state: &mut AppState,
+    jump: impl FnOnce(&mut PreviewSearch, &str) -> Option<usize>,
+) {
+    let Some(content) = state.selected_agent().map(|a| a.last_content.clone()) else {
+        return;
+    };
+    if let Some(scroll) = jump(&mut state.preview_search, &content) {
+        state.preview_scroll = scroll;
+    }
+}
+
+/// Approves `agent`, then types the post-approval message if one is configured
+fn send_approval(
+    tmux_client: &TmuxClient,
+    keys: &KeysConfig,
+    post_approval: &mut PostApprovalGuard,
+    highlight: &ChoiceHighlight,
+    agent: &MonitoredAgent,
+) -> Result<()> {
+    // A numbered menu is answered with its highlighted choice instead of "y"
+    if let Some(steps) = question_steps(agent, highlight.get(agent)) {
+        return send_steps(tmux_client, &agent.target, &steps);
+    }
+    let message = post_approval.take(keys, agent);
+    send_steps(
+        tmux_client,
+        &agent.target,
+        &approval_steps(keys, &agent.agent_type, message),
+    )
+}
+
+/// Whether a batch of waiting agents is approved or rejected
+#[derive(Debug, Clone, Copy)]
+enum Answer {
+    Approve,
+    Reject,
+}
+
+/// Approves or rejects each agent at `indices` that is waiting on the user,
+/// for both the key bindings and the footer buttons.
+///
+/// Agents answered within the throttle window are skipped, so repeated
+/// keypresses or clicks don't double-send; agents whose pane is gone are
+/// dropped. Stops at the first failed send. Returns how many were answered.
+fn answer_agents(
+    state: &mut AppState,
+    tmux_client: &TmuxClient,
+    keys: &KeysConfig,
+    post_approval: &mut PostApprovalGuard,
+    audit: Option<&AuditLog>,
+    indices: &[usize],
+    answer: Answer,
+) -> usize {
+    let mut answered = Vec::new();
+    let mut gone = Vec::new();
+    for &idx in indices {
+        let Some(agent) = state.agents.get_agent(idx) else {
+            continue;
+        };
+        if !agent.status.needs_attention() {
+            continue;
+        }
+        let target = agent.target.clone();
+        if !tmux_client.pane_exists(&target) {
+            gone.push(target);
+            continue;
+        }
+        if !state.approval_throttle.allow(&target, Instant::now()) {
+            continue;
+        }
+        let sent = match answer {
+            Answer::Approve => send_approval(
+                tmux_client,
+                keys,
+                post_approval,
+                &state.choice_highlight,
+                agent,
+            ),
+            Answer::Reject => {
+                let reject = keys.rejection_keys(&agent.agent_type);
+                send_steps(
+                    tmux_client,
+                    &target,
+                    &[SendStep::Keys(reject), SendStep::Keys("Enter")],
+                )
+            }
+        };
+        if let Err(e) = sent {
+            let verb = match answer {
+                Answer::Approve => "approve",
+                Answer::Reject => "reject",
+            };
+            state.set_error(format!("Failed to {} {}: {}", verb, target, e));
+            break;
+        }
+        answered.push(target);
+    }
+
+    let (label, action) = match answer {
+        Answer::Approve => ("approved", AuditAction::Approve),
+        Answer::Reject => ("rejected", AuditAction::Reject),
+    };
+    state.event_log.record_action(&answered, label);
+    audit_targets(audit, state, &answered, action, None);
+    drop_gone_targets(state, &gone);
+    answered.len()
+}
+
+/// Dashboard data source: the hub's /api/dashboard (delivered in monitor updates)
+/// wins while the hub is connected; local state files are read only when it isn't,
+/// so the two never overwrite each other
+fn reads_local_dashboard(state: &AppState) -> bool {
+    state.show_dashboard && !state.hub_connected
+}
+
+/// Appends `action` on each of `targets` to the audit log, if it is enabled.
+/// Call right after acting, while the agents still show what was answered.
+fn audit_targets(
+    audit: Option<&AuditLog>,
+    state: &mut AppState,
+    targets: &[String],
+    action: AuditAction,
+    text: Option<&str>,
+) {
+    let Some(audit) = audit else {
+        return;
+    };
+    let result = targets
+        .iter()
+        .filter_map(|target| state.agents.root_agents.iter().find(|a| &a.target == target))
+        .try_for_each(|agent| audit.record(agent, action, text));
+    if let Err(e) = result {
+        state.set_error(format!("Failed to write audit log: {}", e));
+    }
+}
+
+/// Runs `steps` against `target`, stopping at the first failure
+fn send_steps(tmux_client: &TmuxClient, target: &str, steps: &[SendStep]) -> Result<()> {
+    for step in steps {
+        match *step {
+            SendStep::Keys(k) => tmux_client.send_keys(target, k)?,
+            SendStep::Literal(text) => tmux_client.send_keys_literal(target, text)?,
+            SendStep::Paste(text) => tmux_client.paste_text(target, text)?,
+        }
+    }
+    Ok(())
+}
+
+/// Keeps the cursor and multi-selection within the current agent list
+fn clamp_selection(state: &mut AppState) {
+    // Ensure selected index is valid
+    if state.selected_index >= state.agents.root_agents.len() {
+        state.selected_index = state.agents.root_agents.len().saturating_sub(1);
+    }
+    // Clean up invalid selections
+    let max_idx = state.agents.root_agents.len();
+    state.selected_agents.retain(|&idx| idx < max_idx);
+    // Stay within the sidebar filter
+    clamp_to_filter(state);
+}
+
+/// Drops agents whose tmux pane has gone away since the last poll.
+///
+/// Sending to a stale target makes tmux fail with "can't find pane", which is
+/// confusing when the pane was simply closed. Instead we remove the agent from
+/// the list right away (the next poll would do so anyway) and say so.
+fn drop_gone_targets(state: &mut AppState, gone: &[String]) {
+    if gone.is_empty() {
+        return;
+    }
+    state
+        .agents
+        .root_agents
+        .retain(|agent| !gone.contains(&agent.target));
+    // Indices shift after removal, so any multi-selection is no longer meaningful
+    state.clear_selection();
+    clamp_selection(state);
+
+    if let [target] = gone {
+        state.flash_critical(format!("Pane {} is gone, list refreshed", target));
+    } else {
+        state.flash_critical(format!("{} panes are gone, list refreshed", gone.len()));
+    }
+}
+
+fn map_key_to_action(
+    code: KeyCode,
+    modifiers: KeyModifiers,
+    state: &AppState,
+    keys: &KeysConfig,
+) -> Action {
     // If help is shown, any key closes it
     if state.show_help {
         return Action::HideHelp;
     }
 
+    // Any key closes the approval detail popup
+    if state.show_approval_detail {
+        return Action::HideApprovalDetail;
+    }
+
+    // Expanded input is waiting for confirmation
+    if state.pending_send.is_some() {
+        return match code {
+            KeyCode::Enter => Action::ConfirmSend,
+            KeyCode::Esc => Action::CancelSend,
+            _ => Action::None,
+        };
+    }
+
+    // F12 toggles the debug overlay regardless of focus
+    if code == KeyCode::F(12) {
+        return Action::ToggleDebugOverlay;
+    }
+
+    // Typing a sidebar filter: keys edit the filter, arrows still navigate
+    if state.filter_editing {
+        return match code {
+            KeyCode::Esc => Action::ClearFilter,
+            KeyCode::Enter => Action::ApplyFilter,
+            KeyCode::Backspace => Action::FilterBackspace,
+            KeyCode::Down => Action::NextAgent,
+            KeyCode::Up => Action::PrevAgent,
+            KeyCode::Char(c) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::FilterChar(c)
+            }
+            _ => Action::None,
+        };
+    }
+
     // If command bar is focused, handle factory command input
     if state.is_command_bar_focused() {
         return match code {
@@ -672,6 +1558,15 @@ fn map_key_to_action(code: KeyCode, modifiers: KeyModifiers, state: &AppState) -
             KeyCode::Enter if modifiers.contains(KeyModifiers::ALT) => Action::InputNewline,
             // Ctrl+Enter sends to all selected agents
             KeyCode::Enter if modifiers.contains(KeyModifiers::CONTROL) => Action::SendInputToAll,
+            // Alt+w sends to every agent in the selected agent's window; Ctrl+W
+            // is left alone since it's delete-word in readline
+            KeyCode::Char('w') if modifiers.contains(KeyModifiers::ALT) => {
+                Action::SendInputToWindow
+            }
+            // Ctrl+S pastes multi-line input as one block
+            KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => Action::SendInputBlock,
+            // Ctrl+Z undoes the last send (best-effort)
+            KeyCode::Char('z') if modifiers.contains(KeyModifiers::CONTROL) => Action::UndoSend,
             KeyCode::Enter => Action::SendInput,
             KeyCode::Backspace => Action::InputBackspace,
             // Cursor movement
@@ -679,33 +1574,117 @@ fn map_key_to_action(code: KeyCode, modifiers: KeyModifiers, state: &AppState) -
             KeyCode::Right => Action::CursorRight,
             KeyCode::Home => Action::CursorHome,
             KeyCode::End => Action::CursorEnd,
+            // Recall earlier inputs
+            KeyCode::Up => Action::HistoryPrev,
+            KeyCode::Down => Action::HistoryNext,
             KeyCode::Char(c) => Action::InputChar(c),
             _ => Action::None,
         };
     }
 
-    // Sidebar focused
+    // Typing a preview search: matches are jumped to as the query changes
+    if state.preview_search.editing {
+        return match code {
+            KeyCode::Esc => Action::ClearPreviewSearch,
+            KeyCode::Enter => Action::ApplyPreviewSearch,
+            KeyCode::Backspace => Action::PreviewSearchBackspace,
+            KeyCode::Char(c) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::PreviewSearchChar(c)
+            }
+            _ => Action::None,
+        };
+    }
+
+    // Preview focused: j/k scroll line by line, the agent selection stays put
+    if state.is_preview_focused() {
+        return match code {
+            // Esc drops an applied search before leaving the preview
+            KeyCode::Esc if state.preview_search.is_active() => Action::ClearPreviewSearch,
+            KeyCode::Esc | KeyCode::Enter => Action::FocusSidebar,
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('k') | KeyCode::Up => Action::PreviewLineUp,
+            KeyCode::Char('j') | KeyCode::Down => Action::PreviewLineDown,
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::PreviewScrollUp
+            }
+            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::PreviewScrollDown
+            }
+            KeyCode::PageUp => Action::PreviewScrollUp,
+            KeyCode::PageDown => Action::PreviewScrollDown,
+            KeyCode::Char('g') => Action::PreviewScrollBottom,
+            KeyCode::Char('h') | KeyCode::Left => Action::PreviewScrollLeft,
+            KeyCode::Char('l') | KeyCode::Right => Action::PreviewScrollRight,
+            KeyCode::Char('w') => Action::TogglePreviewWrap,
+            KeyCode::Char('#') => Action::TogglePreviewLineNumbers,
+            KeyCode::Char('/') => Action::EnterPreviewSearch,
+            KeyCode::Char('n') => Action::PreviewSearchNext,
+            KeyCode::Char('N') => Action::PreviewSearchPrev,
+            KeyCode::Char('?') => Action::ShowHelp,
+            _ => Action::None,
+        };
+    }
+
+    // Queue focused: j/k pick a task, which can be cancelled or reprioritized
+    if state.is_queue_focused() {
+        return match code {
+            KeyCode::Esc | KeyCode::Char('b') => Action::FocusSidebar,
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('j') | KeyCode::Down => Action::QueueNext,
+            KeyCode::Char('k') | KeyCode::Up => Action::QueuePrev,
+            KeyCode::Char('x') | KeyCode::Delete => Action::CancelQueueTask,
+            KeyCode::Char('+') => Action::RaiseQueuePriority,
+            KeyCode::Char('-') => Action::LowerQueuePriority,
+            KeyCode::Char('Q') => Action::ToggleQueue,
+            KeyCode::Char('?') => Action::ShowHelp,
+            _ => Action::None,
+        };
+    }
+
+    // Sidebar focused: configured approval bindings take precedence
+    if let KeyCode::Char(c) = code {
+        if !modifiers.contains(KeyModifiers::CONTROL) {
+            if keys.is_approve(c) {
+                return Action::Approve;
+            }
+            if keys.is_reject(c) {
+                return Action::Reject;
+            }
+            if keys.is_approve_all(c) {
+                return Action::ApproveAll;
+            }
+        }
+    }
+
     match code {
         KeyCode::Char('q') => Action::Quit,
         KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
 
         KeyCode::Char('j') | KeyCode::Down => Action::NextAgent,
         KeyCode::Char('k') | KeyCode::Up => Action::PrevAgent,
+        // Move the highlighted answer of a pending question
+        KeyCode::Char('J') => Action::NextChoice,
+        KeyCode::Char('K') => Action::PrevChoice,
         KeyCode::Tab => Action::NextAgent,
+        // Jump between agents waiting on approval or input
+        KeyCode::Char('.') => Action::NextAttention,
+        KeyCode::Char(',') => Action::PrevAttention,
+
+        // Shift+Left/Right scroll the unwrapped preview sideways
+        KeyCode::Left if modifiers.contains(KeyModifiers::SHIFT) => Action::PreviewScrollLeft,
+        KeyCode::Right if modifiers.contains(KeyModifiers::SHIFT) => Action::PreviewScrollRight,
 
         // Left/Right arrows for focus navigation
         KeyCode::Right => Action::FocusInput,
         KeyCode::Left => Action::None, // Already on sidebar
+        KeyCode::Enter => Action::FocusPreview,
 
         // Multi-selection
         KeyCode::Char(' ') => Action::ToggleSelection,
         KeyCode::Char('a') if modifiers.contains(KeyModifiers::CONTROL) => Action::SelectAll,
 
-        // Approval
-        KeyCode::Char('y') | KeyCode::Char('Y') => Action::Approve,
-        KeyCode::Char('n') | KeyCode::Char('N') => Action::Reject,
-        KeyCode::Char('a') | KeyCode::Char('A') => Action::ApproveAll,
-
         // Number keys for quick choice selection (1-9)
         KeyCode::Char(c @ '1'..='9') => {
             let num = c.to_digit(10).unwrap() as u8;
@@ -715,19 +1694,49 @@ fn map_key_to_action(code: KeyCode, modifiers: KeyModifiers, state: &AppState) -
         // Focus pane with 'f'
         KeyCode::Char('f') | KeyCode::Char('F') => Action::FocusPane,
 
+        // Full detail of a truncated approval
+        KeyCode::Char('i') => Action::ShowApprovalDetail,
+
+        // Interrupt (Ctrl-C) with 'x', close the pane with Alt+x
+        KeyCode::Char('x') if modifiers.contains(KeyModifiers::ALT) => {
+            Action::KillAgent { close_pane: true }
+        }
+        KeyCode::Char('x') => Action::KillAgent { close_pane: false },
+
+        // Dismiss a prompt or menu without answering it
+        KeyCode::Char('c') if !modifiers.contains(KeyModifiers::CONTROL) => Action::SendEscape,
+        KeyCode::Char('C') => Action::CopyDetails,
+
         KeyCode::Char('s') | KeyCode::Char('S') => Action::ToggleSubagentLog,
+        KeyCode::Char('L') => Action::ToggleEventLog,
+        KeyCode::Char('[') if state.show_event_log => Action::EventLogUp,
+        KeyCode::Char(']') if state.show_event_log => Action::EventLogDown,
         KeyCode::Char('t') | KeyCode::Char('T') => Action::ToggleSummaryDetail,
+        KeyCode::Char('o') => Action::ToggleSubagentsInline,
+        KeyCode::Char('v') => Action::FoldWindow,
+        KeyCode::Char('V') => Action::FoldSession,
+        KeyCode::Char('=') => Action::UnfoldAll,
+        KeyCode::Char('m') => Action::ToggleAcknowledged,
+        KeyCode::Char('O') => Action::CycleSort,
+        KeyCode::Char('p') => Action::ToggleFullPaths,
+        KeyCode::Char('e') => Action::ExportPane,
+        KeyCode::Char('u') if !modifiers.contains(KeyModifiers::CONTROL) => Action::UndoSend,
+        KeyCode::Char('w') => Action::TogglePreviewWrap,
         KeyCode::Char('r') => Action::Refresh,
+        KeyCode::Char('R') => Action::Reconnect,
 
         // Sidebar resize (only < and >)
         KeyCode::Char('<') => Action::SidebarNarrower,
         KeyCode::Char('>') => Action::SidebarWider,
 
         KeyCode::Char('Q') => Action::ToggleQueue,
+        KeyCode::Char('b') => Action::FocusQueue,
         KeyCode::Char('D') => Action::ToggleDashboard,
         KeyCode::Char('P') => Action::ToggleFactory,
         KeyCode::Char('X') => Action::ToggleAnalytics,
+        KeyCode::Char('G') => Action::ToggleStatsGraph,
         KeyCode::Char(':') => Action::EnterCommandBar,
+        KeyCode::Char('/') => Action::EnterFilter,
         KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => Action::PreviewScrollUp,
         KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
             Action::PreviewScrollDown
@@ -740,8 +1749,12 @@ fn map_key_to_action(code: KeyCode, modifiers: KeyModifiers, state: &AppState) -
         KeyCode::Esc => {
             if !state.selected_agents.is_empty() {
                 Action::ClearSelection
+            } else if state.agent_filter.is_some() {
+                Action::ClearFilter
             } else if state.show_subagent_log {
                 Action::ToggleSubagentLog
+            } else if state.show_event_log {
+                Action::ToggleEventLog
             } else {
                 Action::None
             }
@@ -750,3 +1763,337 @@ fn map_key_to_action(code: KeyCode, modifiers: KeyModifiers, state: &AppState) -
         _ => Action::None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{AgentStatus, AgentType, ApprovalType, MonitoredAgent};
+    use crate::tmux::ScriptedRunner;
+
+    #[test]
+    fn test_drop_gone_targets_refreshes_list() {
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![
+            MonitoredAgent::for_test("main:0.0"),
+            MonitoredAgent::for_test("main:0.1"),
+        ];
+        state.selected_index = 1;
+        state.selected_agents.insert(0);
+
+        drop_gone_targets(&mut state, &["main:0.1".to_string()]);
+
+        assert_eq!(state.agents.root_agents.len(), 1);
+        assert_eq!(state.agents.root_agents[0].target, "main:0.0");
+        assert_eq!(state.selected_index, 0);
+        assert!(state.selected_agents.is_empty());
+        let flash = state.flashes.current().expect("flash expected");
+        assert!(flash.text.contains("main:0.1 is gone"), "unexpected flash: {}", flash.text);
+    }
+
+    #[test]
+    fn test_answer_agents_throttles_repeats() {
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![MonitoredAgent {
+            status: AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "cargo test".to_string(),
+            },
+            ..MonitoredAgent::for_test("main:0.1")
+        }];
+        let runner = Arc::new(
+            ScriptedRunner::default()
+                .reply("display-message", true, "%1\n")
+                .reply("send-keys", true, ""),
+        );
+        let tmux_client = TmuxClient::new().with_runner(runner.clone());
+        let keys = KeysConfig::default();
+        let mut post_approval = PostApprovalGuard::default();
+
+        // A second click or keypress right after the first sends nothing
+        let mut answer = |state: &mut AppState, answer| {
+            answer_agents(
+                state,
+                &tmux_client,
+                &keys,
+                &mut post_approval,
+                None,
+                &[0],
+                answer,
+            )
+        };
+        let sends = || {
+            runner
+                .calls()
+                .iter()
+                .filter(|call| call.starts_with("send-keys"))
+                .count()
+        };
+        assert_eq!(answer(&mut state, Answer::Approve), 1);
+        let sent = sends();
+        assert!(sent > 0);
+        assert_eq!(answer(&mut state, Answer::Approve), 0);
+        assert_eq!(answer(&mut state, Answer::Reject), 0);
+        assert_eq!(sends(), sent);
+        assert_eq!(state.agents.root_agents.len(), 1);
+    }
+
+    #[test]
+    fn test_configured_approval_keys() {
+        let state = AppState::new();
+        let keys = KeysConfig {
+            approve: "a".to_string(),
+            reject: "d".to_string(),
+            approve_all: "A".to_string(),
+            ..KeysConfig::default()
+        };
+        let map = |c| map_key_to_action(KeyCode::Char(c), KeyModifiers::NONE, &state, &keys);
+
+        assert_eq!(map('a'), Action::Approve);
+        assert_eq!(map('d'), Action::Reject);
+        assert_eq!(map('A'), Action::ApproveAll);
+        assert_eq!(map('y'), Action::None);
+        assert_eq!(map('D'), Action::ToggleDashboard);
+        assert_eq!(map('c'), Action::SendEscape);
+        assert_eq!(map('C'), Action::CopyDetails);
+    }
+
+    #[test]
+    fn test_preview_focus_keys_scroll_without_moving_selection() {
+        let mut state = AppState::new();
+        let keys = KeysConfig::default();
+        let map = |code, state: &AppState| {
+            map_key_to_action(code, KeyModifiers::NONE, state, &keys)
+        };
+
+        assert_eq!(map(KeyCode::Enter, &state), Action::FocusPreview);
+        assert_eq!(map(KeyCode::Char('j'), &state), Action::NextAgent);
+
+        state.focus_preview();
+        assert_eq!(map(KeyCode::Char('j'), &state), Action::PreviewLineDown);
+        assert_eq!(map(KeyCode::Char('k'), &state), Action::PreviewLineUp);
+        assert_eq!(map(KeyCode::Char('y'), &state), Action::None);
+        assert_eq!(map(KeyCode::Esc, &state), Action::FocusSidebar);
+    }
+
+    #[test]
+    fn test_preview_search_keys() {
+        let mut state = AppState::new();
+        let keys = KeysConfig::default();
+        let map = |code, state: &AppState| {
+            map_key_to_action(code, KeyModifiers::NONE, state, &keys)
+        };
+
+        state.focus_preview();
+        assert_eq!(map(KeyCode::Char('/'), &state), Action::EnterPreviewSearch);
+        assert_eq!(map(KeyCode::Char('#'), &state), Action::TogglePreviewLineNumbers);
+
+        state.preview_search.editing = true;
+        // Preview keys are plain text while typing the query
+        assert_eq!(map(KeyCode::Char('j'), &state), Action::PreviewSearchChar('j'));
+        assert_eq!(map(KeyCode::Enter, &state), Action::ApplyPreviewSearch);
+
+        state.preview_search.editing = false;
+        state.preview_search.query = "error".to_string();
+        assert_eq!(map(KeyCode::Char('n'), &state), Action::PreviewSearchNext);
+        assert_eq!(map(KeyCode::Char('N'), &state), Action::PreviewSearchPrev);
+        assert_eq!(map(KeyCode::Esc, &state), Action::ClearPreviewSearch);
+    }
+
+    #[test]
+    fn test_command_bar_keys_edit_and_submit() {
+        let mut state = AppState::new();
+        let keys = KeysConfig::default();
+        let map = |code, state: &AppState| {
+            map_key_to_action(code, KeyModifiers::NONE, state, &keys)
+        };
+
+        assert_eq!(map(KeyCode::Char(':'), &state), Action::EnterCommandBar);
+
+        state.focus_command_bar();
+        // Sidebar keys are plain text here
+        assert_eq!(map(KeyCode::Char('q'), &state), Action::InputChar('q'));
+        assert_eq!(map(KeyCode::Char('y'), &state), Action::InputChar('y'));
+        assert_eq!(map(KeyCode::Enter, &state), Action::SubmitFactory);
+        assert_eq!(map(KeyCode::Esc, &state), Action::CancelCommandBar);
+    }
+
+    #[test]
+    fn test_queue_focus_keys() {
+        let mut state = AppState::new();
+        let keys = KeysConfig::default();
+        let map = |code, state: &AppState| {
+            map_key_to_action(code, KeyModifiers::NONE, state, &keys)
+        };
+
+        assert_eq!(map(KeyCode::Char('b'), &state), Action::FocusQueue);
+
+        state.focus_queue();
+        // j/k move within the queue, not the agent list
+        assert_eq!(map(KeyCode::Char('j'), &state), Action::QueueNext);
+        assert_eq!(map(KeyCode::Char('k'), &state), Action::QueuePrev);
+        assert_eq!(map(KeyCode::Char('x'), &state), Action::CancelQueueTask);
+        assert_eq!(map(KeyCode::Char('+'), &state), Action::RaiseQueuePriority);
+        assert_eq!(map(KeyCode::Char('-'), &state), Action::LowerQueuePriority);
+        assert_eq!(map(KeyCode::Esc, &state), Action::FocusSidebar);
+    }
+
+    #[test]
+    fn test_local_dashboard_only_without_hub() {
+        let mut state = AppState::new();
+        assert!(!reads_local_dashboard(&state));
+
+        state.show_dashboard = true;
+        assert!(reads_local_dashboard(&state));
+
+        state.hub_connected = true;
+        assert!(!reads_local_dashboard(&state));
+    }
+
+    #[test]
+    fn test_input_up_down_recall_history() {
+        let mut state = AppState::new();
+        let keys = KeysConfig::default();
+        state.focus_input();
+
+        let map = |code| map_key_to_action(code, KeyModifiers::NONE, &state, &keys);
+        assert_eq!(map(KeyCode::Up), Action::HistoryPrev);
+        assert_eq!(map(KeyCode::Down), Action::HistoryNext);
+    }
+
+    #[test]
+    fn test_pick_editor_prefers_editor_then_visual() {
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(pick_editor(some("nvim"), some("code -w")), some("nvim"));
+        assert_eq!(pick_editor(None, some("code -w")), some("code -w"));
+        assert_eq!(pick_editor(some("  "), some("hx")), some("hx"));
+        assert_eq!(pick_editor(None, None), None);
+    }
+
+    #[test]
+    fn test_replace_input_swaps_content() {
+        let mut state = AppState::new();
+        for c in "draft".chars() {
+            state.input_char(c);
+        }
+
+        replace_input(&mut state, "run the tests");
+        assert_eq!(state.get_input(), "run the tests");
+        assert_eq!(state.get_cursor_position(), "run the tests".len());
+    }
+
+    #[test]
+    fn test_drop_gone_targets_noop_when_all_alive() {
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![MonitoredAgent::for_test("main:0.0")];
+
+        drop_gone_targets(&mut state, &[]);
+
+        assert_eq!(state.agents.root_agents.len(), 1);
+        assert!(state.flashes.current().is_none());
+    }
+
+    /// A tree covering the states the widgets branch on: two sessions, every
+    /// status, a pending question, subagents and context bars
+    fn representative_state() -> AppState {
+        use crate::agents::{AgentStatus, ApprovalType, Subagent, SubagentType};
+
+        let mut idle = MonitoredAgent::for_test("main:0.0");
+        idle.context_remaining = Some(72);
+        idle.model = Some("opus 4.1".to_string());
+
+        let mut working = MonitoredAgent::for_test("main:0.1");
+        working.agent_type = AgentType::CodexCli;
+        working.status = AgentStatus::Processing {
+            activity: "Running cargo test --workspace".to_string(),
+        };
+        working.subagents = vec![
+            Subagent::new(
+                "sub-1".to_string(),
+                SubagentType::Explore,
+                "searching for render entrypoints".to_string(),
+            ),
+            Subagent::new("sub-2".to_string(), SubagentType::Bash, "cargo build".to_string()),
+        ];
+        working.context_remaining = Some(8);
+
+        let mut question = MonitoredAgent::for_test("review:1.0");
+        question.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::UserQuestion {
+                choices: vec!["Keep".to_string(), "Revert".to_string(), "Skip".to_string()],
+                multi_select: false,
+            },
+            details: "What should happen to the migration?".to_string(),
+        };
+
+        let mut shell = MonitoredAgent::for_test("review:1.1");
+        shell.agent_type = AgentType::GeminiCli;
+        shell.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "rm -rf target".to_string(),
+        };
+        shell.last_content = "? Shell rm -rf target\nAllow execution? (y/n)".to_string();
+
+        let mut failed = MonitoredAgent::for_test("review:2.0");
+        failed.status = AgentStatus::Error {
+            message: "API Error: 529 overloaded".to_string(),
+        };
+
+        let mut unknown = MonitoredAgent::for_test("scratch:0.0");
+        unknown.agent_type = AgentType::Unknown;
+
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![idle, working, question, shell, failed, unknown];
+        state.selected_index = 3;
+        state.selected_agents.insert(1);
+        state
+    }
+
+    fn render_to_string(state: &AppState, width: u16, height: u16) -> String {
+        use ratatui::backend::TestBackend;
+
+        let config = Config::default();
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| {
+            render(frame, state, &config);
+        })
+        .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn test_full_render_shows_tree_and_preview() {
+        let state = representative_state();
+        let screen = render_to_string(&state, 160, 48);
+
+        assert!(screen.contains("main"));
+        assert!(screen.contains("review"));
+        assert!(screen.contains("Waiting"));
+        assert!(screen.contains("opus 4.1"));
+        assert!(screen.contains("Allow execution? (y/n)"));
+        assert!(screen.contains("Input → "));
+    }
+
+    #[test]
+    fn test_full_render_survives_every_panel_and_size() {
+        let mut state = representative_state();
+        state.show_subagent_log = true;
+        state.show_queue = true;
+        state.show_dashboard = true;
+        state.show_factory = true;
+        state.show_analytics = true;
+        state.show_stats_graph = true;
+        state.show_debug_overlay = true;
+        state.preview_wrap = false;
+        state.preview_hscroll = 40;
+
+        for (width, height) in [(200, 60), (120, 40), (80, 24), (40, 12), (10, 5), (1, 1)] {
+            render_to_string(&state, width, height);
+        }
+        assert!(render_to_string(&state, 80, 12).contains("Terminal too small (need ≥14 rows)"));
+
+        state.show_help = true;
+        state.theme = Theme::monochrome();
+        render_to_string(&state, 80, 24);
+    }
+}
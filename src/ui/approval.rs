@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::agents::{AgentStatus, AgentType, ApprovalType, MonitoredAgent};
+
+use super::keys::KeysConfig;
+use super::send::{choice_steps, SendStep, CHOICE_KEYS};
+
+/// Approve or reject presses for the same target within this window are dropped
+pub const APPROVAL_COOLDOWN: Duration = Duration::from_millis(300);
+
+/// Keys sent to approve an agent, followed by the post-approval message if any
+pub fn approval_steps<'a>(
+    keys: &'a KeysConfig,
+    agent_type: &AgentType,
+    post_message: Option<&'a str>,
+) -> Vec<SendStep<'a>> {
+    let mut steps = vec![
+        SendStep::Keys(keys.approval_keys(agent_type)),
+        SendStep::Keys("Enter"),
+    ];
+    if let Some(message) = post_message {
+        steps.push(SendStep::Literal(message));
+        steps.push(SendStep::Keys("Enter"));
+    }
+    steps
+}
+
+/// Steps answering `agent`'s numbered question with choice `highlighted` (0-based),
+/// or None if it isn't waiting on a question. Menus take a number, not "y".
+pub fn question_steps(
+    agent: &MonitoredAgent,
+    highlighted: usize,
+) -> Option<Vec<SendStep<'static>>> {
+    question_choices(agent)?;
+    Some(choice_steps(&[highlighted + 1]))
+}
+
+/// Number of choices a key can pick in `agent`'s pending question
+fn question_choices(agent: &MonitoredAgent) -> Option<usize> {
+    match &agent.status {
+        AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::UserQuestion { choices, .. },
+            ..
+        } if !choices.is_empty() => Some(choices.len().min(CHOICE_KEYS.len())),
+        _ => None,
+    }
+}
+
+/// The highlighted choice of each agent waiting on a question, moved with J/K
+/// and sent by approve. Defaults to the first choice.
+#[derive(Debug, Clone, Default)]
+pub struct ChoiceHighlight {
+    by_target: HashMap<String, usize>,
+}
+
+impl ChoiceHighlight {
+    /// Highlighted choice (0-based) for `agent`
+    pub fn get(&self, agent: &MonitoredAgent) -> usize {
+        let max = question_choices(agent).unwrap_or(1);
+        self.by_target
+            .get(&agent.target)
+            .copied()
+            .unwrap_or(0)
+            .min(max - 1)
+    }
+
+    /// Moves the highlight to the next (or previous) choice, wrapping around
+    pub fn cycle(&mut self, agent: &MonitoredAgent, forward: bool) {
+        let Some(count) = question_choices(agent) else {
+            return;
+        };
+        let current = self.get(agent);
+        let next = if forward {
+            (current + 1) % count
+        } else {
+            (current + count - 1) % count
+        };
+        self.by_target.insert(agent.target.clone(), next);
+    }
+
+    /// Forgets agents no longer waiting on a question
+    pub fn retain_questions(&mut self, agents: &[MonitoredAgent]) {
+        self.by_target.retain(|target, _| {
+            agents
+                .iter()
+                .any(|a| &a.target == target && question_choices(a).is_some())
+        });
+    }
+}
+
+/// Makes sure the post-approval message goes out once per pending approval,
+/// even if approve is pressed again before the next poll
+#[derive(Debug, Default)]
+pub struct PostApprovalGuard {
+    /// Targets that already got the message for their current approval
+    sent: HashSet<String>,
+}
+
+impl PostApprovalGuard {
+    /// Returns the message to send after approving `agent`, or None if it is
+    /// disabled or was already sent for this approval
+    pub fn take<'a>(&mut self, keys: &'a KeysConfig, agent: &MonitoredAgent) -> Option<&'a str> {
+        let message = keys.post_approval_message(&agent.agent_type)?;
+        if self.sent.insert(agent.target.clone()) {
+            Some(message)
+        } else {
+            None
+        }
+    }
+
+    /// Forgets targets that are no longer waiting for approval
+    pub fn retain_pending(&mut self, agents: &[MonitoredAgent]) {
+        self.sent.retain(|target| {
+            agents
+                .iter()
+                .any(|a| &a.target == target && a.status.needs_attention())
+        });
+    }
+}
+
+/// Drops repeated approve/reject presses for a target until [`APPROVAL_COOLDOWN`]
+/// has passed. All queued key events are handled in one go, so a held or
+/// repeated key would otherwise answer the next prompt before it is seen.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalThrottle {
+    /// When each target was last answered
+    last: HashMap<String, Instant>,
+}
+
+impl ApprovalThrottle {
+    /// Returns true, and starts the cooldown, if `target` may be answered at `now`
+    pub fn allow(&mut self, target: &str, now: Instant) -> bool {
+        self.last
+            .retain(|_, at| now.duration_since(*at) < APPROVAL_COOLDOWN);
+        if self.last.contains_key(target) {
+            return false;
+        }
+        self.last.insert(target.to_string(), now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{AgentStatus, ApprovalType};
+    use crate::ui::keys::AgentKeys;
+
+    fn agent(target: &str, agent_type: AgentType) -> MonitoredAgent {
+        MonitoredAgent {
+            agent_type,
+            status: AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "ls".to_string(),
+            },
+            ..MonitoredAgent::for_test(target)
+        }
+    }
+
+    #[test]
+    fn test_approval_steps_without_message() {
+        let keys = KeysConfig::default();
+        assert_eq!(
+            approval_steps(&keys, &AgentType::ClaudeCode, None),
+            vec![SendStep::Keys("y"), SendStep::Keys("Enter")]
+        );
+    }
+
+    #[test]
+    fn test_approval_steps_with_message() {
+        let keys = KeysConfig::default();
+        assert_eq!(
+            approval_steps(&keys, &AgentType::ClaudeCode, Some("continue")),
+            vec![
+                SendStep::Keys("y"),
+                SendStep::Keys("Enter"),
+                SendStep::Literal("continue"),
+                SendStep::Keys("Enter"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_post_approval_off_by_default() {
+        let keys = KeysConfig::default();
+        let mut guard = PostApprovalGuard::default();
+        assert_eq!(
+            guard.take(&keys, &agent("main:0.0", AgentType::ClaudeCode)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_post_approval_sent_once_per_approval() {
+        let keys = KeysConfig {
+            post_approval: Some("continue".to_string()),
+            codex_cli: AgentKeys {
+                post_approval: Some("go on".to_string()),
+                ..AgentKeys::default()
+            },
+            ..KeysConfig::default()
+        };
+        let mut guard = PostApprovalGuard::default();
+        let mut claude = agent("main:0.0", AgentType::ClaudeCode);
+        let codex = agent("main:0.1", AgentType::CodexCli);
+
+        assert_eq!(guard.take(&keys, &claude), Some("continue"));
+        assert_eq!(guard.take(&keys, &claude), None);
+        assert_eq!(guard.take(&keys, &codex), Some("go on"));
+
+        // Still pending on the next poll: guard holds
+        guard.retain_pending(&[claude.clone(), codex.clone()]);
+        assert_eq!(guard.take(&keys, &claude), None);
+
+        // Approval consumed, then a new one shows up
+        claude.status = AgentStatus::Idle;
+        guard.retain_pending(&[claude.clone(), codex.clone()]);
+        claude.status = codex.status.clone();
+        assert_eq!(guard.take(&keys, &claude), Some("continue"));
+    }
+
+    fn question(target: &str) -> MonitoredAgent {
+        let mut agent = agent(target, AgentType::ClaudeCode);
+        agent.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::UserQuestion {
+                choices: vec!["Keep".to_string(), "Revert".to_string(), "Skip".to_string()],
+                multi_select: false,
+            },
+            details: "What should happen to the change?".to_string(),
+        };
+        agent
+    }
+
+    #[test]
+    fn test_question_answered_with_highlighted_choice() {
+        let asking = question("main:0.1");
+        let mut highlight = ChoiceHighlight::default();
+        assert_eq!(
+            question_steps(&asking, highlight.get(&asking)),
+            Some(vec![SendStep::Keys("1"), SendStep::Keys("Enter")])
+        );
+
+        highlight.cycle(&asking, false);
+        assert_eq!(highlight.get(&asking), 2);
+        highlight.cycle(&asking, true);
+        highlight.cycle(&asking, true);
+        assert_eq!(
+            question_steps(&asking, highlight.get(&asking)),
+            Some(vec![SendStep::Keys("2"), SendStep::Keys("Enter")])
+        );
+
+        // y/n prompts keep the configured approval keys
+        assert_eq!(
+            question_steps(&agent("main:0.2", AgentType::ClaudeCode), 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_highlight_forgotten_once_answered() {
+        let mut asking = question("main:0.1");
+        let mut highlight = ChoiceHighlight::default();
+        highlight.cycle(&asking, true);
+
+        highlight.retain_questions(std::slice::from_ref(&asking));
+        assert_eq!(highlight.get(&asking), 1);
+
+        asking.status = AgentStatus::Idle;
+        highlight.retain_questions(std::slice::from_ref(&asking));
+        assert!(highlight.by_target.is_empty());
+    }
+
+    #[test]
+    fn test_throttle_drops_repeats_within_cooldown() {
+        let now = Instant::now();
+        let mut throttle = ApprovalThrottle::default();
+        assert!(throttle.allow("main:0.1", now));
+        assert!(!throttle.allow("main:0.1", now + Duration::from_millis(50)));
+        // Other targets have their own cooldown
+        assert!(throttle.allow("main:0.2", now + Duration::from_millis(50)));
+        assert!(throttle.allow("main:0.1", now + APPROVAL_COOLDOWN));
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::agents::{AgentStatus, MonitoredAgent};
+
+/// File under `$HOME` the audit log is appended to unless configured otherwise
+const AUDIT_FILE: &str = ".config/agentos/tui_audit.jsonl";
+
+/// `[audit]` section of the config. Off unless `enabled = true`; `path` defaults
+/// to `~/.config/agentos/tui_audit.jsonl`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    pub enabled: bool,
+    pub path: Option<PathBuf>,
+}
+
+/// `~/.config/agentos/tui_audit.jsonl`, or a relative path if `$HOME` is unset
+pub fn default_audit_path() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(AUDIT_FILE)
+}
+
+/// What was done to an agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditAction {
+    Approve,
+    AutoApprove,
+    Reject,
+    Send,
+}
+
+/// One line of the audit log
+#[derive(Debug, Serialize)]
+struct AuditRecord<'a> {
+    timestamp: String,
+    target: &'a str,
+    agent_type: String,
+    action: AuditAction,
+    /// Kind of prompt answered ("Shell", "Edit"...), if the agent was asking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    approval: Option<&'a str>,
+    /// Prompt details at the time of the action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a str>,
+    /// Text sent, for sends
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<&'a str>,
+}
+
+impl<'a> AuditRecord<'a> {
+    fn new(
+        agent: &'a MonitoredAgent,
+        action: AuditAction,
+        text: Option<&'a str>,
+        at: DateTime<Local>,
+    ) -> Self {
+        let (approval, details) = match &agent.status {
+            AgentStatus::AwaitingApproval {
+                approval_type,
+                details,
+            } => (Some(approval_type.short_desc()), Some(details.as_str())),
+            _ => (None, None),
+        };
+        Self {
+            timestamp: at.to_rfc3339(),
+            target: &agent.target,
+            agent_type: agent.agent_type.to_string(),
+            action,
+            approval,
+            details,
+            text,
+        }
+    }
+}
+
+/// Append-only JSONL record of approve, reject and send actions, kept apart from
+/// the in-memory event log. Each record is written and synced on its own, so
+/// nothing recorded is lost if the UI crashes.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// The audit log `config` asks for, or None when auditing is off
+    pub fn from_config(config: &AuditConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            path: config.path.clone().unwrap_or_else(default_audit_path),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a record of `action` on `agent`, as it stands now
+    pub fn record(
+        &self,
+        agent: &MonitoredAgent,
+        action: AuditAction,
+        text: Option<&str>,
+    ) -> io::Result<()> {
+        let record = AuditRecord::new(agent, action, text, Local::now());
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        // One write per record keeps lines whole; sync so a crash can't lose it
+        file.write_all(line.as_bytes())?;
+        file.sync_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ApprovalType;
+
+    fn agent() -> MonitoredAgent {
+        MonitoredAgent {
+            status: AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "cargo test".to_string(),
+            },
+            ..MonitoredAgent::for_test("main:0.1")
+        }
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(AuditLog::from_config(&AuditConfig::default()).is_none());
+
+        let config: AuditConfig = serde_json::from_str(r#"{"enabled": true}"#).unwrap();
+        let log = AuditLog::from_config(&config).unwrap();
+        assert!(log.path().ends_with(".config/agentos/tui_audit.jsonl"));
+    }
+
+    #[test]
+    fn test_records_appended_as_json_lines() {
+        let dir = std::env::temp_dir().join(format!("tui-audit-test-{}", std::process::id()));
+        let path = dir.join("nested/audit.jsonl");
+        let _ = fs::remove_dir_all(&dir);
+        let log = AuditLog::from_config(&AuditConfig {
+            enabled: true,
+            path: Some(path.clone()),
+        })
+        .unwrap();
+
+        let mut agent = agent();
+        log.record(&agent, AuditAction::Approve, None).unwrap();
+        agent.status = AgentStatus::Idle;
+        log.record(&agent, AuditAction::Send, Some("run the tests"))
+            .unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["target"], "main:0.1");
+        assert_eq!(lines[0]["action"], "approve");
+        assert_eq!(lines[0]["approval"], "Shell");
+        assert_eq!(lines[0]["details"], "cargo test");
+        assert!(lines[0]["timestamp"].as_str().unwrap().contains('T'));
+        assert_eq!(lines[1]["action"], "send");
+        assert_eq!(lines[1]["text"], "run the tests");
+        assert!(lines[1].get("details").is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
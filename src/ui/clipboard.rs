@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use crate::agents::{AgentStatus, MonitoredAgent};
+
+/// Clipboard commands tried in order (macOS, Wayland, X11); the first that succeeds wins
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// The details of `agent`'s pending approval (the command, file or question),
+/// or None if it isn't waiting on one
+pub fn approval_details(agent: &MonitoredAgent) -> Option<&str> {
+    match &agent.status {
+        AgentStatus::AwaitingApproval { details, .. } if !details.trim().is_empty() => {
+            Some(details)
+        }
+        _ => None,
+    }
+}
+
+/// Copies `text` to the system clipboard through the first clipboard command that
+/// works. Fails with NotFound on headless machines where none is available.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    if copy_with(CLIPBOARD_COMMANDS, text) {
+        return Ok(());
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no clipboard available (tried pbcopy, wl-copy, xclip, xsel)",
+    ))
+}
+
+/// Pipes `text` into each of `commands` in turn until one takes it. Returns
+/// false if none did.
+fn copy_with(commands: &[(&str, &[&str])], text: &str) -> bool {
+    for (program, args) in commands {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        // Dropping stdin closes it so the command sees EOF. A command that gives
+        // up early breaks the pipe; it's still waited on before the next is tried.
+        let written = child
+            .stdin
+            .take()
+            .map_or(Ok(()), |mut stdin| stdin.write_all(text.as_bytes()));
+        // wl-copy without a Wayland display (or xclip without X) exits non-zero
+        let exited_ok = child.wait().is_ok_and(|status| status.success());
+        if written.is_ok() && exited_ok {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ApprovalType;
+
+    #[test]
+    fn test_approval_details_only_while_awaiting() {
+        let mut agent = MonitoredAgent::for_test("main:0.0");
+        assert_eq!(approval_details(&agent), None);
+
+        agent.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "rm -rf target && cargo build --release".to_string(),
+        };
+        assert_eq!(
+            approval_details(&agent),
+            Some("rm -rf target && cargo build --release")
+        );
+
+        agent.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "  ".to_string(),
+        };
+        assert_eq!(approval_details(&agent), None);
+    }
+
+    #[test]
+    fn test_copy_falls_through_broken_pipe() {
+        // `true` exits without reading, so a large write fails with EPIPE
+        let text = "x".repeat(1 << 20);
+        assert!(copy_with(&[("true", &[]), ("cat", &[])], &text));
+        assert!(!copy_with(&[("true", &[]), ("false", &[])], &text));
+        assert!(!copy_with(&[("/nonexistent/pbcopy", &[])], "x"));
+    }
+}
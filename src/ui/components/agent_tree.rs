@@ -1,7 +1,9 @@
-use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::time::Duration;
 
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, List, ListItem, ListState},
@@ -10,6 +12,15 @@ use ratatui::{
 
 use crate::agents::{AgentStatus, AgentType, ApprovalType, MonitoredAgent, SubagentStatus};
 use crate::app::AppState;
+use crate::ui::filter::{
+    hidden_count, listed_indices, session_fold_key, visible_subagents, window_fold_key,
+};
+use crate::ui::label::{agent_color, agent_label};
+use crate::ui::text::char_slice;
+use crate::ui::SortMode;
+
+/// Waiting for approval longer than this is highlighted in the sidebar
+const LONG_WAIT: Duration = Duration::from_secs(300);
 
 /// Widget for displaying agents in a tree organized by session/window
 pub struct AgentTreeWidget;
@@ -20,32 +31,86 @@ type WindowKey<'a> = (u32, &'a str);
 /// Type alias for agents in a window (index, agent reference)
 type WindowAgents<'a> = Vec<(usize, &'a MonitoredAgent)>;
 
-/// Type alias for windows map
-type WindowsMap<'a> = BTreeMap<WindowKey<'a>, WindowAgents<'a>>;
+/// Type alias for windows, in display order
+type WindowsList<'a> = Vec<(WindowKey<'a>, WindowAgents<'a>)>;
+
+/// Type alias for sessions, in display order
+type SessionsList<'a> = Vec<(&'a str, WindowsList<'a>)>;
+
+/// List rows occupied by each agent block, with the agent's index
+type RowMap = Vec<(Range<usize>, usize)>;
 
-/// Type alias for sessions map
-type SessionsMap<'a> = BTreeMap<&'a str, WindowsMap<'a>>;
+/// List row of each session and window header, with its fold key
+type HeaderRows = Vec<(usize, String)>;
+
+/// Agents idle, working, awaiting approval and in error in a session. Agents
+/// needing attention that were marked reviewed are counted apart, matching the
+/// header's pending count.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct StatusCounts {
+    idle: usize,
+    working: usize,
+    approval: usize,
+    error: usize,
+    reviewed: usize,
+}
 
 /// Represents the hierarchical structure: Session -> Window -> Agents
 struct SessionWindowTree<'a> {
-    sessions: SessionsMap<'a>,
+    sessions: SessionsList<'a>,
 }
 
 impl<'a> SessionWindowTree<'a> {
-    fn new(agents: &'a [MonitoredAgent]) -> Self {
-        let mut sessions: SessionsMap<'a> = BTreeMap::new();
-
-        for (idx, agent) in agents.iter().enumerate() {
-            sessions
-                .entry(&agent.session)
-                .or_default()
-                .entry((agent.window, &agent.window_name))
-                .or_default()
-                .push((idx, agent));
+    /// Builds the tree from the agents at `visible`, keeping their original indices.
+    /// Sessions and windows appear in the order of their first agent, so the tree
+    /// follows the sort applied to the agent list.
+    fn new(agents: &'a [MonitoredAgent], visible: &[usize]) -> Self {
+        let mut sessions: SessionsList<'a> = Vec::new();
+
+        for &idx in visible {
+            let agent = &agents[idx];
+            let windows = match sessions.iter().position(|(s, _)| *s == agent.session) {
+                Some(pos) => &mut sessions[pos].1,
+                None => {
+                    sessions.push((&agent.session, Vec::new()));
+                    &mut sessions.last_mut().unwrap().1
+                }
+            };
+            let key = (agent.window, agent.window_name.as_str());
+            let window_agents = match windows.iter().position(|(k, _)| *k == key) {
+                Some(pos) => &mut windows[pos].1,
+                None => {
+                    windows.push((key, Vec::new()));
+                    &mut windows.last_mut().unwrap().1
+                }
+            };
+            window_agents.push((idx, agent));
         }
 
         Self { sessions }
     }
+
+    /// Status counts of each session's agents, in session order
+    fn session_counts(&self, acknowledged: &HashSet<String>) -> Vec<StatusCounts> {
+        self.sessions
+            .iter()
+            .map(|(_, windows)| {
+                let mut counts = StatusCounts::default();
+                for (_, agent) in windows.iter().flat_map(|(_, agents)| agents) {
+                    let reviewed = acknowledged.contains(&agent.target);
+                    match &agent.status {
+                        AgentStatus::Idle => counts.idle += 1,
+                        AgentStatus::Processing { .. } => counts.working += 1,
+                        status if reviewed && status.needs_attention() => counts.reviewed += 1,
+                        AgentStatus::AwaitingApproval { .. } => counts.approval += 1,
+                        AgentStatus::Error { .. } => counts.error += 1,
+                        AgentStatus::Unknown => {}
+                    }
+                }
+                counts
+            })
+            .collect()
+    }
 }
 
 impl AgentTreeWidget {
@@ -56,7 +121,7 @@ impl AgentTreeWidget {
         let selected_count = state.selected_agents.len();
 
         // Build title
-        let title = if selected_count > 0 {
+        let mut title = if selected_count > 0 {
             format!(" {} sel │ {} pending ", selected_count, active_count)
         } else if subagent_count > 0 {
             format!(" {} pending │ {} subs ", active_count, subagent_count)
@@ -65,11 +130,18 @@ impl AgentTreeWidget {
         } else {
             format!(" {} agents ", agents.len())
         };
+        if state.sort_mode != SortMode::Target {
+            title.push_str(&format!("│ by {} ", state.sort_mode.label()));
+        }
+        if let Some(ref filter) = state.agent_filter {
+            let cursor = if state.filter_editing { "▏" } else { "" };
+            title = format!(" /{}{} │{}", filter, cursor, title);
+        }
 
-        let border_color = if !state.is_input_focused() {
-            Color::Cyan
+        let border_color = if !state.is_input_focused() && !state.is_preview_focused() {
+            state.theme.accent
         } else {
-            Color::Gray
+            state.theme.border
         };
 
         let block = Block::default()
@@ -79,367 +151,619 @@ impl AgentTreeWidget {
             .border_style(Style::default().fg(border_color));
 
         if agents.is_empty() {
-            let empty_text = List::new(vec![ListItem::new(Line::from(vec![Span::styled(
+            let mut empty_items = vec![ListItem::new(Line::from(vec![Span::styled(
                 "  No agents detected",
                 Style::default().fg(Color::DarkGray),
-            )]))])
-            .block(block);
+            )]))];
+            empty_items.extend(recently_closed_items(state));
+            let empty_text = List::new(empty_items).block(block);
             frame.render_widget(empty_text, area);
             return;
         }
 
-        let tree = SessionWindowTree::new(agents);
-        let mut items: Vec<ListItem> = Vec::new();
-        let available_width = area.width.saturating_sub(4) as usize;
+        let (items, rows, _) = tree_items(state, area);
+        let offset = scroll_offset(&rows, state.selected_index, area.height.saturating_sub(2));
+        let list = List::new(items).block(block);
+        let mut list_state = ListState::default().with_offset(offset);
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+
+    /// Returns the index of the agent whose rows are drawn at (`x`, `y`) when the
+    /// tree is rendered into `area`. Covers every line of the agent, including
+    /// approval details and subagents.
+    pub fn hit_test(x: u16, y: u16, area: Rect, state: &AppState) -> Option<usize> {
+        let inner = area.inner(Margin::new(1, 1));
+        if !inner.contains(Position::new(x, y)) {
+            return None;
+        }
+        let (_, rows, _) = tree_items(state, area);
+        let offset = scroll_offset(&rows, state.selected_index, inner.height);
+        let row = offset + (y - inner.y) as usize;
+        rows.iter()
+            .find(|(range, _)| range.contains(&row))
+            .map(|(_, idx)| *idx)
+    }
 
-        for (session, windows) in tree.sessions.iter() {
-            // Session header
-            let session_line = Line::from(vec![
-                Span::styled("▼ ", Style::default().fg(Color::Cyan)),
+    /// Returns the fold key of the session or window header drawn at (`x`, `y`)
+    /// when the tree is rendered into `area`
+    pub fn header_at(x: u16, y: u16, area: Rect, state: &AppState) -> Option<String> {
+        let inner = area.inner(Margin::new(1, 1));
+        if !inner.contains(Position::new(x, y)) {
+            return None;
+        }
+        let (_, rows, headers) = tree_items(state, area);
+        let offset = scroll_offset(&rows, state.selected_index, inner.height);
+        let row = offset + (y - inner.y) as usize;
+        headers
+            .into_iter()
+            .find(|(header_row, _)| *header_row == row)
+            .map(|(_, key)| key)
+    }
+}
+
+/// Builds the list items for the tree along with the rows each agent and header
+/// occupies. Agents in folded sessions and windows are summarized on their header.
+fn tree_items(state: &AppState, area: Rect) -> (Vec<ListItem<'_>>, RowMap, HeaderRows) {
+    let agents = &state.agents.root_agents;
+    let filter = state.agent_filter.as_deref().unwrap_or("");
+    let tree = SessionWindowTree::new(agents, &listed_indices(state));
+    let mut items: Vec<ListItem> = Vec::new();
+    if tree.sessions.is_empty() {
+        items.push(ListItem::new(Line::from(vec![Span::styled(
+            format!("  No agents match \"{}\"", filter),
+            Style::default().fg(Color::DarkGray),
+        )])));
+    }
+    let mut rows: RowMap = Vec::new();
+    let mut headers: HeaderRows = Vec::new();
+    let available_width = area.width.saturating_sub(4) as usize;
+
+    let session_counts = tree.session_counts(&state.acknowledged);
+
+    for ((session, windows), counts) in tree.sessions.iter().zip(session_counts) {
+        // Session header
+        let session_key = session_fold_key(session);
+        let session_folded = state.folded_groups.contains(&session_key);
+        let mut session_line = Line::from(vec![
+            Span::styled(
+                if session_folded { "▶ " } else { "▼ " },
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                *session,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        session_line.spans.extend(session_badges(state, counts));
+        if session_folded {
+            // The badges already show what's pending
+            let agent_count = windows.iter().map(|(_, agents)| agents.len()).sum();
+            session_line.spans.push(folded_count(agent_count));
+        }
+        headers.push((items.len(), session_key));
+        items.push(ListItem::new(session_line));
+        if session_folded {
+            continue;
+        }
+
+        for (window_idx, ((window_num, window_name), window_agents)) in windows.iter().enumerate() {
+            let is_last_window = window_idx == windows.len() - 1;
+            let window_prefix = if is_last_window { "└─" } else { "├─" };
+
+            // Window header
+            let window_key = window_fold_key(session, *window_num);
+            let window_folded = state.folded_groups.contains(&window_key);
+            let mut window_line = Line::from(vec![
                 Span::styled(
-                    *session,
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
+                    format!(" {} ", window_prefix),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{}: {}", window_num, window_name),
+                    Style::default().fg(Color::White),
                 ),
             ]);
-            items.push(ListItem::new(session_line));
-
-            for (window_idx, ((window_num, window_name), window_agents)) in
-                windows.iter().enumerate()
-            {
-                let is_last_window = window_idx == windows.len() - 1;
-                let window_prefix = if is_last_window { "└─" } else { "├─" };
-
-                // Window header
-                let window_line = Line::from(vec![
-                    Span::styled(
-                        format!(" {} ", window_prefix),
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                    Span::styled(
-                        format!("{}: {}", window_num, window_name),
-                        Style::default().fg(Color::White),
-                    ),
-                ]);
-                items.push(ListItem::new(window_line));
+            if window_folded {
+                let folded: Vec<&MonitoredAgent> =
+                    window_agents.iter().map(|(_, agent)| *agent).collect();
+                window_line.spans.extend(fold_summary(state, &folded));
+            }
+            headers.push((items.len(), window_key));
+            items.push(ListItem::new(window_line));
+            if window_folded {
+                continue;
+            }
 
-                for (agent_idx, (original_idx, agent)) in window_agents.iter().enumerate() {
-                    let is_cursor = *original_idx == state.selected_index;
-                    let is_selected = state.is_multi_selected(*original_idx);
-                    let is_last_agent = agent_idx == window_agents.len() - 1;
+            for (agent_idx, (original_idx, agent)) in window_agents.iter().enumerate() {
+                let is_cursor = *original_idx == state.selected_index;
+                let is_selected = state.is_multi_selected(*original_idx);
+                let is_last_agent = agent_idx == window_agents.len() - 1;
+                let subagents = visible_subagents(state, agent);
+                let first_row = items.len();
 
-                    let cont_prefix = if is_last_window { "    " } else { " │  " };
+                let cont_prefix = if is_last_window { "    " } else { " │  " };
 
-                    let tree_prefix = if is_last_window {
-                        if is_last_agent && agent.subagents.is_empty() {
-                            "    └─"
-                        } else {
-                            "    ├─"
-                        }
-                    } else if is_last_agent && agent.subagents.is_empty() {
-                        " │  └─"
+                let tree_prefix = if is_last_window {
+                    if is_last_agent && subagents.is_empty() {
+                        "    └─"
                     } else {
-                        " │  ├─"
-                    };
+                        "    ├─"
+                    }
+                } else if is_last_agent && subagents.is_empty() {
+                    " │  └─"
+                } else {
+                    " │  ├─"
+                };
 
-                    let select_indicator = if is_selected && is_cursor {
-                        "┃☑" // カーソル+選択: 縦線とチェック
-                    } else if is_selected {
-                        " ☑" // 選択のみ: チェック
-                    } else if is_cursor {
-                        "┃ " // カーソルのみ: 縦線
-                    } else {
-                        "  "
-                    };
+                let select_indicator = if is_selected && is_cursor {
+                    "┃☑" // カーソル+選択: 縦線とチェック
+                } else if is_selected {
+                    " ☑" // 選択のみ: チェック
+                } else if is_cursor {
+                    "┃ " // カーソルのみ: 縦線
+                } else {
+                    "  "
+                };
 
-                    // Status indicator and text
-                    let (status_char, status_text, status_style) = match &agent.status {
-                        AgentStatus::Idle => ("●", "Idle", Style::default().fg(Color::Green)),
-                        AgentStatus::Processing { .. } => (
-                            state.spinner_frame(),
-                            "Working",
-                            Style::default().fg(Color::Yellow),
-                        ),
-                        AgentStatus::AwaitingApproval { .. } => (
-                            "⚠",
-                            "Waiting",
-                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                        ),
-                        AgentStatus::Error { .. } => {
-                            ("✗", "Error", Style::default().fg(Color::Red))
-                        }
-                        AgentStatus::Unknown => {
-                            ("○", "Unknown", Style::default().fg(Color::DarkGray))
-                        }
-                    };
+                // Status indicator and text
+                let status_style = state.status_colors.style(&agent.status);
+                let (status_char, status_text) = match &agent.status {
+                    AgentStatus::Idle => ("●", "Idle"),
+                    AgentStatus::Processing { .. } => (state.spinner_frame(), "Working"),
+                    AgentStatus::AwaitingApproval { .. } => ("⚠", "Waiting"),
+                    AgentStatus::Error { .. } => ("✗", "Error"),
+                    AgentStatus::Unknown => ("○", "Unknown"),
+                };
 
-                    let type_style = match agent.agent_type {
-                        AgentType::ClaudeCode => Style::default().fg(Color::Magenta),
-                        AgentType::OpenCode => Style::default().fg(Color::Blue),
-                        AgentType::CodexCli => Style::default().fg(Color::Green),
-                        AgentType::GeminiCli => Style::default().fg(Color::Yellow),
-                        AgentType::Unknown => Style::default().fg(Color::DarkGray),
-                    };
+                let type_style = match agent.agent_type {
+                    AgentType::ClaudeCode => Style::default().fg(Color::Magenta),
+                    AgentType::OpenCode => Style::default().fg(Color::Blue),
+                    AgentType::CodexCli => Style::default().fg(Color::Green),
+                    AgentType::GeminiCli => Style::default().fg(Color::Yellow),
+                    AgentType::Custom(_) => Style::default().fg(Color::Cyan),
+                    AgentType::Unknown => Style::default().fg(Color::DarkGray),
+                };
+
+                let item_style = if is_cursor || is_selected {
+                    state.theme.selection(is_cursor)
+                } else if state.acknowledged.contains(&agent.target) {
+                    // Marked reviewed: de-emphasized until its status changes
+                    Style::default().add_modifier(Modifier::DIM)
+                } else {
+                    Style::default()
+                };
 
-                    let item_style = if is_cursor {
-                        Style::default().bg(Color::Rgb(50, 50, 70)) // より濃い紫がかった背景
-                    } else if is_selected {
-                        Style::default().bg(Color::Rgb(35, 35, 50)) // 薄めの選択背景
+                // Main line: status + path
+                let mut line = Line::from(vec![
+                    Span::styled(
+                        select_indicator,
+                        if is_selected {
+                            Style::default().fg(Color::Cyan)
+                        } else {
+                            Style::default().fg(Color::White)
+                        },
+                    ),
+                    Span::styled(tree_prefix, Style::default().fg(Color::DarkGray)),
+                    Span::styled(status_char, status_style),
+                    Span::raw(" "),
+                ]);
+                // How long the agent has been blocked on us; long waits are inverted
+                if let Some(waiting) = agent.awaiting_str() {
+                    let long_wait = agent
+                        .awaiting_since
+                        .is_some_and(|since| since.elapsed() >= LONG_WAIT);
+                    let style = if long_wait {
+                        status_style.add_modifier(Modifier::REVERSED)
                     } else {
-                        Style::default()
+                        status_style
                     };
+                    line.spans.push(Span::styled(waiting, style));
+                    line.spans.push(Span::raw(" "));
+                }
+                line.spans.push(Span::styled(
+                    agent_label(state, agent),
+                    Style::default().fg(agent_color(state, agent)),
+                ));
+                // Collapsed subagents: just the count
+                if subagents.is_empty() && !agent.subagents.is_empty() {
+                    line.spans.push(Span::styled(
+                        format!(" ▸{} subs", agent.subagents.len()),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                // Another agent works in the same directory
+                if state.path_collisions.iter().any(|c| c.involves(&agent.target)) {
+                    line.spans.push(Span::styled(
+                        " ⚠ shared",
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    ));
+                }
+                // Working, but the pane hasn't changed in a long time
+                if let Some(stale) = agent.stale_str(state.stale_after) {
+                    line.spans.push(Span::styled(
+                        format!(" {}", stale),
+                        Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+                    ));
+                }
+                items.push(ListItem::new(line).style(item_style));
 
-                    // Main line: status + path
-                    let line = Line::from(vec![
-                        Span::styled(
-                            select_indicator,
-                            if is_selected {
-                                Style::default().fg(Color::Cyan)
-                            } else {
-                                Style::default().fg(Color::White)
-                            },
-                        ),
-                        Span::styled(tree_prefix, Style::default().fg(Color::DarkGray)),
-                        Span::styled(status_char, status_style),
-                        Span::raw(" "),
-                        Span::styled(agent.abbreviated_path(), Style::default().fg(Color::Cyan)),
-                    ]);
-                    items.push(ListItem::new(line).style(item_style));
+                // Info line: type | status | pid | uptime | context
+                let mut info_parts = vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("{}│  ", cont_prefix),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(agent.agent_type.short_name(), type_style),
+                    Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(status_text, status_style),
+                    Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        format!("pid:{}", agent.pid),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(agent.uptime_str(), Style::default().fg(Color::DarkGray)),
+                ];
 
-                    // Info line: type | status | pid | uptime | context
-                    let mut info_parts = vec![
-                        Span::raw("  "),
-                        Span::styled(
-                            format!("{}│  ", cont_prefix),
-                            Style::default().fg(Color::DarkGray),
-                        ),
-                        Span::styled(agent.agent_type.short_name(), type_style),
-                        Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
-                        Span::styled(status_text, status_style),
-                        Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
-                        Span::styled(
-                            format!("pid:{}", agent.pid),
-                            Style::default().fg(Color::DarkGray),
-                        ),
-                        Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
-                        Span::styled(agent.uptime_str(), Style::default().fg(Color::DarkGray)),
-                    ];
-
-                    // Branch info if available
-                    if let Some(ref branch) = agent.branch {
-                        if !branch.is_empty() {
-                            info_parts.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-                            info_parts.push(Span::styled(
-                                format!("\u{2387}{}", truncate_str(branch, 16)),
-                                Style::default().fg(Color::Magenta),
-                            ));
-                        }
-                    }
+                // Model, with the expensive tier highlighted
+                if let Some(model) = agent.model.as_deref().filter(|_| state.show_model) {
+                    let model_color = if model.starts_with("opus") {
+                        Color::Yellow
+                    } else {
+                        Color::DarkGray
+                    };
+                    info_parts.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+                    info_parts.push(Span::styled(model.to_string(), Style::default().fg(model_color)));
+                }
 
-                    // Context bar if available
-                    if let Some(ctx) = agent.context_remaining {
-                        let bar_color = if ctx > 50 {
-                            Color::Green
-                        } else if ctx > 20 {
-                            Color::Yellow
-                        } else {
-                            Color::Red
-                        };
+                // Branch info if available
+                if let Some(ref branch) = agent.branch {
+                    if !branch.is_empty() {
                         info_parts.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
                         info_parts.push(Span::styled(
-                            context_bar(ctx),
-                            Style::default().fg(bar_color),
+                            format!("\u{2387}{}", truncate_str(branch, 16)),
+                            Style::default().fg(Color::Magenta),
                         ));
                     }
+                }
 
-                    items.push(ListItem::new(Line::from(info_parts)).style(item_style));
+                // Context bar if available
+                if let Some(ctx) = agent.context_remaining {
+                    let bar_color = if ctx > 50 {
+                        Color::Green
+                    } else if ctx > 20 {
+                        Color::Yellow
+                    } else {
+                        Color::Red
+                    };
+                    info_parts.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+                    info_parts.push(Span::styled(
+                        context_bar(ctx),
+                        Style::default().fg(bar_color),
+                    ));
+                }
 
-                    // Status details
-                    match &agent.status {
-                        AgentStatus::AwaitingApproval {
-                            approval_type,
-                            details,
-                        } => {
-                            let approval_line = Line::from(vec![
+                items.push(ListItem::new(Line::from(info_parts)).style(item_style));
+
+                // Status details
+                match &agent.status {
+                    AgentStatus::AwaitingApproval {
+                        approval_type,
+                        details,
+                    } => {
+                        let approval_line = Line::from(vec![
+                            Span::raw("  "),
+                            Span::styled(
+                                format!("{}│  ", cont_prefix),
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                            Span::styled("⚠ ", Style::default().fg(Color::Red)),
+                            Span::styled(
+                                format!("{}", approval_type),
+                                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                            ),
+                        ]);
+                        items.push(ListItem::new(approval_line).style(item_style));
+
+                        if !details.is_empty() {
+                            let detail_text =
+                                truncate_str(details, available_width.saturating_sub(14));
+                            let detail_line = Line::from(vec![
                                 Span::raw("  "),
                                 Span::styled(
                                     format!("{}│  ", cont_prefix),
                                     Style::default().fg(Color::DarkGray),
                                 ),
-                                Span::styled("⚠ ", Style::default().fg(Color::Red)),
-                                Span::styled(
-                                    format!("{}", approval_type),
-                                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                                ),
+                                Span::styled("  → ", Style::default().fg(Color::DarkGray)),
+                                Span::styled(detail_text, Style::default().fg(Color::White)),
                             ]);
-                            items.push(ListItem::new(approval_line).style(item_style));
+                            items.push(ListItem::new(detail_line).style(item_style));
+                        }
 
-                            if !details.is_empty() {
-                                let detail_text =
-                                    truncate_str(details, available_width.saturating_sub(14));
-                                let detail_line = Line::from(vec![
+                        if let ApprovalType::UserQuestion {
+                            choices,
+                            multi_select,
+                        } = approval_type
+                        {
+                            // Approve sends the highlighted choice (J/K move it)
+                            let highlighted = state.choice_highlight.get(agent);
+                            for (i, choice) in choices.iter().take(4).enumerate() {
+                                let choice_text =
+                                    truncate_str(choice, available_width.saturating_sub(14));
+                                let (marker, text_style) = if i == highlighted {
+                                    (
+                                        "▸ ",
+                                        Style::default()
+                                            .fg(Color::Cyan)
+                                            .add_modifier(Modifier::BOLD),
+                                    )
+                                } else {
+                                    ("  ", Style::default().fg(Color::White))
+                                };
+                                let choice_line = Line::from(vec![
                                     Span::raw("  "),
                                     Span::styled(
                                         format!("{}│  ", cont_prefix),
                                         Style::default().fg(Color::DarkGray),
                                     ),
-                                    Span::styled("  → ", Style::default().fg(Color::DarkGray)),
-                                    Span::styled(detail_text, Style::default().fg(Color::White)),
+                                    Span::styled(
+                                        format!("{}{}. ", marker, i + 1),
+                                        Style::default().fg(Color::Yellow),
+                                    ),
+                                    Span::styled(choice_text, text_style),
                                 ]);
-                                items.push(ListItem::new(detail_line).style(item_style));
+                                items.push(ListItem::new(choice_line).style(item_style));
                             }
-
-                            if let ApprovalType::UserQuestion { choices, .. } = approval_type {
-                                for (i, choice) in choices.iter().take(4).enumerate() {
-                                    let choice_text =
-                                        truncate_str(choice, available_width.saturating_sub(14));
-                                    let choice_line = Line::from(vec![
-                                        Span::raw("  "),
-                                        Span::styled(
-                                            format!("{}│  ", cont_prefix),
-                                            Style::default().fg(Color::DarkGray),
-                                        ),
-                                        Span::styled(
-                                            format!("  {}. ", i + 1),
-                                            Style::default().fg(Color::Yellow),
-                                        ),
-                                        Span::styled(
-                                            choice_text,
-                                            Style::default().fg(Color::White),
-                                        ),
-                                    ]);
-                                    items.push(ListItem::new(choice_line).style(item_style));
-                                }
-                                if choices.len() > 4 {
-                                    let more_line = Line::from(vec![
-                                        Span::raw("  "),
-                                        Span::styled(
-                                            format!("{}│  ", cont_prefix),
-                                            Style::default().fg(Color::DarkGray),
-                                        ),
-                                        Span::styled(
-                                            format!("     ...+{} more", choices.len() - 4),
-                                            Style::default().fg(Color::DarkGray),
-                                        ),
-                                    ]);
-                                    items.push(ListItem::new(more_line).style(item_style));
-                                }
+                            if choices.len() > 4 {
+                                let more_line = Line::from(vec![
+                                    Span::raw("  "),
+                                    Span::styled(
+                                        format!("{}│  ", cont_prefix),
+                                        Style::default().fg(Color::DarkGray),
+                                    ),
+                                    Span::styled(
+                                        if highlighted >= 4 {
+                                            format!(
+                                                "     ...+{} more (▸ {})",
+                                                choices.len() - 4,
+                                                highlighted + 1
+                                            )
+                                        } else {
+                                            format!("     ...+{} more", choices.len() - 4)
+                                        },
+                                        Style::default().fg(Color::DarkGray),
+                                    ),
+                                ]);
+                                items.push(ListItem::new(more_line).style(item_style));
                             }
-                        }
-                        AgentStatus::Processing { activity } => {
-                            if !activity.is_empty() {
-                                let activity_text =
-                                    truncate_str(activity, available_width.saturating_sub(14));
-                                let activity_line = Line::from(vec![
+                            if *multi_select {
+                                let hint_line = Line::from(vec![
                                     Span::raw("  "),
                                     Span::styled(
                                         format!("{}│  ", cont_prefix),
                                         Style::default().fg(Color::DarkGray),
                                     ),
                                     Span::styled(
-                                        format!("{} ", state.spinner_frame()),
-                                        Style::default().fg(Color::Yellow),
+                                        "     pick several: type \"1 3\" + Enter",
+                                        Style::default().fg(Color::DarkGray),
                                     ),
-                                    Span::styled(activity_text, Style::default().fg(Color::Yellow)),
                                 ]);
-                                items.push(ListItem::new(activity_line).style(item_style));
+                                items.push(ListItem::new(hint_line).style(item_style));
                             }
                         }
-                        AgentStatus::Error { message } => {
-                            let error_text =
-                                truncate_str(message, available_width.saturating_sub(14));
-                            let error_line = Line::from(vec![
+                    }
+                    AgentStatus::Processing { activity } => {
+                        if !activity.is_empty() {
+                            let activity_text =
+                                truncate_str(activity, available_width.saturating_sub(14));
+                            let activity_line = Line::from(vec![
                                 Span::raw("  "),
                                 Span::styled(
                                     format!("{}│  ", cont_prefix),
                                     Style::default().fg(Color::DarkGray),
                                 ),
-                                Span::styled("✗ ", Style::default().fg(Color::Red)),
-                                Span::styled(error_text, Style::default().fg(Color::Red)),
+                                Span::styled(format!("{} ", state.spinner_frame()), status_style),
+                                Span::styled(activity_text, status_style),
                             ]);
-                            items.push(ListItem::new(error_line).style(item_style));
+                            items.push(ListItem::new(activity_line).style(item_style));
                         }
-                        _ => {}
                     }
+                    AgentStatus::Error { message } => {
+                        let error_text =
+                            truncate_str(message, available_width.saturating_sub(14));
+                        let error_line = Line::from(vec![
+                            Span::raw("  "),
+                            Span::styled(
+                                format!("{}│  ", cont_prefix),
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                            Span::styled("✗ ", status_style),
+                            Span::styled(error_text, status_style),
+                        ]);
+                        items.push(ListItem::new(error_line).style(item_style));
+                    }
+                    _ => {}
+                }
 
-                    // Subagents
-                    for (sub_idx, subagent) in agent.subagents.iter().enumerate() {
-                        let is_last_sub = sub_idx == agent.subagents.len() - 1;
-                        let sub_branch = if is_last_sub { "└─" } else { "├─" };
+                // Subagents
+                for (sub_idx, subagent) in subagents.iter().enumerate() {
+                    let is_last_sub = sub_idx == subagents.len() - 1;
+                    let sub_branch = if is_last_sub { "└─" } else { "├─" };
 
-                        let (sub_char, sub_style) = match subagent.status {
-                            SubagentStatus::Running => {
-                                (state.spinner_frame(), Style::default().fg(Color::Cyan))
-                            }
-                            SubagentStatus::Completed => ("✓", Style::default().fg(Color::Green)),
-                            SubagentStatus::Failed => ("✗", Style::default().fg(Color::Red)),
-                            SubagentStatus::Unknown => ("?", Style::default().fg(Color::DarkGray)),
-                        };
+                    let (sub_char, sub_style) = match subagent.status {
+                        SubagentStatus::Running => {
+                            (state.spinner_frame(), Style::default().fg(Color::Cyan))
+                        }
+                        SubagentStatus::Completed => ("✓", Style::default().fg(Color::Green)),
+                        SubagentStatus::Failed => ("✗", Style::default().fg(Color::Red)),
+                        SubagentStatus::Unknown => ("?", Style::default().fg(Color::DarkGray)),
+                    };
 
-                        let duration = if matches!(subagent.status, SubagentStatus::Running) {
-                            format!(" ({})", subagent.duration_str())
-                        } else {
-                            String::new()
-                        };
+                    let duration = if matches!(subagent.status, SubagentStatus::Running) {
+                        format!(" ({})", subagent.duration_str())
+                    } else {
+                        String::new()
+                    };
+
+                    let sub_line = Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("{}{}", cont_prefix, sub_branch),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::styled(sub_char, sub_style),
+                        Span::raw(" "),
+                        Span::styled(
+                            subagent.subagent_type.display_name(),
+                            Style::default()
+                                .fg(Color::White)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(duration, Style::default().fg(Color::Yellow)),
+                    ]);
+                    items.push(ListItem::new(sub_line));
 
-                        let sub_line = Line::from(vec![
+                    if !subagent.description.is_empty() {
+                        let desc_prefix = if is_last_sub { "   " } else { "│  " };
+                        let desc_text = truncate_str(
+                            &subagent.description,
+                            available_width.saturating_sub(14),
+                        );
+                        let desc_line = Line::from(vec![
                             Span::raw("  "),
                             Span::styled(
-                                format!("{}{}", cont_prefix, sub_branch),
+                                format!("{}{}", cont_prefix, desc_prefix),
                                 Style::default().fg(Color::DarkGray),
                             ),
-                            Span::styled(sub_char, sub_style),
-                            Span::raw(" "),
-                            Span::styled(
-                                subagent.subagent_type.display_name(),
-                                Style::default()
-                                    .fg(Color::White)
-                                    .add_modifier(Modifier::BOLD),
-                            ),
-                            Span::styled(duration, Style::default().fg(Color::Yellow)),
+                            Span::styled("  ", Style::default()),
+                            Span::styled(desc_text, Style::default().fg(Color::DarkGray)),
                         ]);
-                        items.push(ListItem::new(sub_line));
-
-                        if !subagent.description.is_empty() {
-                            let desc_prefix = if is_last_sub { "   " } else { "│  " };
-                            let desc_text = truncate_str(
-                                &subagent.description,
-                                available_width.saturating_sub(14),
-                            );
-                            let desc_line = Line::from(vec![
-                                Span::raw("  "),
-                                Span::styled(
-                                    format!("{}{}", cont_prefix, desc_prefix),
-                                    Style::default().fg(Color::DarkGray),
-                                ),
-                                Span::styled("  ", Style::default()),
-                                Span::styled(desc_text, Style::default().fg(Color::DarkGray)),
-                            ]);
-                            items.push(ListItem::new(desc_line));
-                        }
+                        items.push(ListItem::new(desc_line));
                     }
                 }
+                rows.push((first_row..items.len(), *original_idx));
             }
         }
+    }
 
-        let list = List::new(items).block(block);
-        let mut list_state = ListState::default();
-        list_state.select(Some(state.selected_index));
-        frame.render_stateful_widget(list, area, &mut list_state);
+    // Agents left out by max_display_agents
+    let hidden = hidden_count(state);
+    if hidden > 0 {
+        items.push(ListItem::new(Line::from(vec![Span::styled(
+            format!("  +{} more (filter to see)", hidden),
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+        )])));
+    }
+
+    items.extend(recently_closed_items(state));
+    (items, rows, headers)
+}
+
+/// Dim "▸ N agents" note for a folded header, with how many of them need attention
+fn fold_summary(state: &AppState, agents: &[&MonitoredAgent]) -> Vec<Span<'static>> {
+    let mut spans = vec![folded_count(agents.len())];
+    let pending = agents
+        .iter()
+        .filter(|agent| agent.status.needs_attention())
+        .count();
+    if pending > 0 {
+        spans.push(Span::styled(
+            format!(" ⚠ {} pending", pending),
+            Style::default()
+                .fg(state.status_colors.approval)
+                .add_modifier(Modifier::BOLD),
+        ));
     }
+    spans
+}
+
+/// "▸ N agents" on a folded header
+fn folded_count(agents: usize) -> Span<'static> {
+    Span::styled(
+        format!(" ▸ {} agents", agents),
+        Style::default().fg(Color::DarkGray),
+    )
+}
+
+/// Colored "2● 1⚠ 1✗" badges after a session name, leaving out zero counts.
+/// Reviewed agents get a dim "✓" badge of their own.
+fn session_badges(state: &AppState, counts: StatusCounts) -> Vec<Span<'static>> {
+    let colors = &state.status_colors;
+    let badges = [
+        (counts.idle, "●", colors.idle),
+        (counts.working, state.spinner_frame(), colors.processing),
+        (counts.approval, "⚠", colors.approval),
+        (counts.error, "✗", colors.error),
+        (counts.reviewed, "✓", Color::DarkGray),
+    ];
+    let mut spans: Vec<Span<'static>> = badges
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, glyph, color)| {
+            Span::styled(format!(" {}{}", count, glyph), Style::default().fg(color))
+        })
+        .collect();
+    if !spans.is_empty() {
+        spans.insert(0, Span::raw(" "));
+    }
+    spans
+}
+
+/// First list row to draw so the cursor agent's block is in view,
+/// preferring its first row when the block is taller than the list
+fn scroll_offset(rows: &RowMap, cursor: usize, height: u16) -> usize {
+    rows.iter()
+        .find(|(_, idx)| *idx == cursor)
+        .map(|(range, _)| range.end.saturating_sub(height as usize).min(range.start))
+        .unwrap_or(0)
+}
+
+/// Dimmed "recently closed" section listing agents that vanished in the last few seconds
+fn recently_closed_items(state: &AppState) -> Vec<ListItem<'static>> {
+    if state.recently_closed.is_empty() {
+        return Vec::new();
+    }
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut items = vec![ListItem::new(Line::from(vec![Span::styled(
+        "▽ recently closed",
+        dim.add_modifier(Modifier::ITALIC),
+    )]))];
+
+    for closed in &state.recently_closed {
+        // Agents that went away quietly stay dim; interrupted work keeps its status color
+        let (status_char, status_color) = match &closed.final_status {
+            AgentStatus::Idle | AgentStatus::Unknown => ("○", Color::DarkGray),
+            status => ("✗", state.status_colors.color(status)),
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("    ", dim),
+            Span::styled(status_char, Style::default().fg(status_color)),
+            Span::raw(" "),
+            Span::styled(closed.path.clone(), dim),
+            Span::styled(
+                format!(
+                    " {} │ {}was {}",
+                    closed.target,
+                    if closed.exited { "exited, " } else { "" },
+                    closed.final_status.short_text()
+                ),
+                dim,
+            ),
+        ])));
+    }
+
+    items
 }
 
 fn truncate_str(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!(
-            "{}..",
-            s.chars()
-                .take(max_len.saturating_sub(2))
-                .collect::<String>()
-        )
+        format!("{}..", char_slice(s, 0, max_len.saturating_sub(2)))
     }
 }
 
@@ -454,3 +778,277 @@ fn context_bar(percent: u8) -> String {
         percent
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(state: &AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        terminal
+            .draw(|frame| AgentTreeWidget::render(frame, frame.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_truncate_str_multibyte() {
+        assert_eq!(truncate_str("feature/日本語-branch", 10), "feature/..");
+        assert_eq!(truncate_str("日本語のブランチ名", 5), "日本語..");
+        assert_eq!(truncate_str("🚀🔥✨", 3), "🚀🔥✨");
+        assert_eq!(truncate_str("🚀🔥✨💥", 3), "🚀..");
+    }
+
+    #[test]
+    fn test_render_abbreviated_vs_full_path() {
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![MonitoredAgent::for_test("main:0.0")];
+
+        let abbreviated = render_to_string(&state);
+        assert!(abbreviated.contains("/h/u/project"));
+        assert!(!abbreviated.contains("/home/user/project"));
+
+        state.show_full_paths = true;
+        let full = render_to_string(&state);
+        assert!(full.contains("/home/user/project"));
+    }
+
+    #[test]
+    fn test_render_custom_agent_name() {
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![MonitoredAgent {
+            agent_type: AgentType::Custom("acme".to_string()),
+            ..MonitoredAgent::for_test("main:0.0")
+        }];
+
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("acme"));
+        assert!(!rendered.contains("???"));
+    }
+
+    #[test]
+    fn test_render_overflow_indicator() {
+        let mut state = AppState::new();
+        state.agents.root_agents = (0..5)
+            .map(|pane| MonitoredAgent {
+                path: format!("/srv/project{}", pane),
+                ..MonitoredAgent::for_test(&format!("main:0.{}", pane))
+            })
+            .collect();
+        state.max_display_agents = Some(1);
+
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("/s/project0"));
+        assert!(!rendered.contains("/s/project1"));
+        assert!(rendered.contains("+4 more (filter to see)"));
+    }
+
+    #[test]
+    fn test_render_collapsed_subagents_show_count() {
+        use crate::agents::{Subagent, SubagentType};
+
+        let mut state = AppState::new();
+        let mut agent = MonitoredAgent {
+            path: "/srv/project".to_string(),
+            ..MonitoredAgent::for_test("main:0.0")
+        };
+        agent.subagents = vec![Subagent::new(
+            "s1".to_string(),
+            SubagentType::Explore,
+            "scan the repo".to_string(),
+        )];
+        state.agents.root_agents = vec![agent];
+
+        state.show_subagents_in_tree = false;
+        let collapsed = render_to_string(&state);
+        assert!(collapsed.contains("▸1 subs"));
+        assert!(!collapsed.contains("scan the repo"));
+
+        state.show_subagents_in_tree = true;
+        let expanded = render_to_string(&state);
+        assert!(!expanded.contains("▸1 subs"));
+        assert!(expanded.contains("scan the repo"));
+    }
+
+    fn agents_in_one_window(count: u32) -> Vec<MonitoredAgent> {
+        (0..count)
+            .map(|pane| MonitoredAgent {
+                path: format!("/srv/project{}", pane),
+                ..MonitoredAgent::for_test(&format!("main:0.{}", pane))
+            })
+            .collect()
+    }
+
+    fn row_of(rendered: &str, needle: &str) -> u16 {
+        rendered.lines().position(|l| l.contains(needle)).unwrap() as u16
+    }
+
+    #[test]
+    fn test_hit_test_covers_every_row_of_an_agent() {
+        let mut state = AppState::new();
+        state.agents.root_agents = agents_in_one_window(2);
+        state.agents.root_agents[0].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "cargo publish".to_string(),
+        };
+        let area = Rect::new(0, 0, 60, 12);
+        let rendered = render_to_string(&state);
+
+        // Session and window headers belong to no agent
+        assert_eq!(AgentTreeWidget::hit_test(5, 1, area, &state), None);
+        assert_eq!(AgentTreeWidget::hit_test(5, 2, area, &state), None);
+
+        let details = row_of(&rendered, "cargo publish");
+        assert_eq!(AgentTreeWidget::hit_test(5, details, area, &state), Some(0));
+        let second = row_of(&rendered, "/s/project1");
+        assert_eq!(AgentTreeWidget::hit_test(5, second - 1, area, &state), Some(0));
+        assert_eq!(AgentTreeWidget::hit_test(5, second, area, &state), Some(1));
+        assert_eq!(AgentTreeWidget::hit_test(5, second + 1, area, &state), Some(1));
+        // The border is not part of any row
+        assert_eq!(AgentTreeWidget::hit_test(0, second, area, &state), None);
+    }
+
+    #[test]
+    fn test_hit_test_follows_scrolled_list() {
+        let mut state = AppState::new();
+        state.agents.root_agents = agents_in_one_window(8);
+        state.selected_index = 7;
+        let area = Rect::new(0, 0, 60, 12);
+        let rendered = render_to_string(&state);
+
+        let last = row_of(&rendered, "/s/project7");
+        assert!(!rendered.contains("/s/project0"));
+        assert_eq!(AgentTreeWidget::hit_test(5, last, area, &state), Some(7));
+        assert_eq!(AgentTreeWidget::hit_test(5, last - 2, area, &state), Some(6));
+    }
+
+    #[test]
+    fn test_folded_window_summarizes_its_agents() {
+        let mut state = AppState::new();
+        state.agents.root_agents = agents_in_one_window(3);
+        state.agents.root_agents[1].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "cargo publish".to_string(),
+        };
+        state.folded_groups.insert(window_fold_key("main", 0));
+        let area = Rect::new(0, 0, 60, 12);
+
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("0: code ▸ 3 agents ⚠ 1 pending"));
+        assert!(!rendered.contains("/s/project0"));
+        let window_row = row_of(&rendered, "0: code");
+        assert_eq!(
+            AgentTreeWidget::header_at(5, window_row, area, &state),
+            Some(window_fold_key("main", 0))
+        );
+        assert_eq!(
+            AgentTreeWidget::header_at(5, window_row - 1, area, &state),
+            Some(session_fold_key("main"))
+        );
+
+        state.folded_groups.clear();
+        state.folded_groups.insert(session_fold_key("main"));
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("▶ main  1⚠ ▸ 3 agents"));
+        assert!(!rendered.contains("0: code"));
+    }
+
+    #[test]
+    fn test_session_header_counts_statuses() {
+        let mut agents = agents_in_one_window(4);
+        agents[0].status = AgentStatus::Idle;
+        agents[1].status = AgentStatus::Idle;
+        agents[2].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "cargo publish".to_string(),
+        };
+        agents[3].session = "ops".to_string();
+        agents[3].status = AgentStatus::Processing {
+            activity: "Deploying".to_string(),
+        };
+        let tree = SessionWindowTree::new(&agents, &[0, 1, 2, 3]);
+        assert_eq!(
+            tree.session_counts(&HashSet::new()),
+            vec![
+                StatusCounts {
+                    idle: 2,
+                    approval: 1,
+                    ..StatusCounts::default()
+                },
+                StatusCounts {
+                    working: 1,
+                    ..StatusCounts::default()
+                },
+            ]
+        );
+
+        let mut state = AppState::new();
+        state.agents.root_agents = agents;
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("▼ main  2● 1⚠"));
+
+        // Folded, the header still counts without repeating what's pending
+        state.folded_groups.insert(session_fold_key("main"));
+        let rendered = render_to_string(&state);
+        let header = rendered.lines().find(|l| l.contains("▶ main")).unwrap();
+        assert!(header.contains("▶ main  2● 1⚠ ▸ 3 agents"));
+        assert!(!header.contains("pending"));
+    }
+
+    #[test]
+    fn test_session_badges_split_errors_and_reviewed() {
+        let mut agents = agents_in_one_window(4);
+        for agent in &mut agents[..2] {
+            agent.status = AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "cargo publish".to_string(),
+            };
+        }
+        agents[2].status = AgentStatus::Error {
+            message: "rate limited".to_string(),
+        };
+        agents[3].status = AgentStatus::Idle;
+        let acknowledged = HashSet::from([agents[0].target.clone()]);
+        let tree = SessionWindowTree::new(&agents, &[0, 1, 2, 3]);
+        assert_eq!(
+            tree.session_counts(&acknowledged),
+            vec![StatusCounts {
+                idle: 1,
+                approval: 1,
+                error: 1,
+                reviewed: 1,
+                ..StatusCounts::default()
+            }]
+        );
+
+        let mut state = AppState::new();
+        state.agents.root_agents = agents;
+        state.acknowledged = acknowledged;
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("▼ main  1● 1⚠ 1✗ 1✓"));
+    }
+
+    #[test]
+    fn test_recently_closed_marks_exited_agents() {
+        use crate::monitor::closed_agents;
+
+        let mut agent = MonitoredAgent {
+            path: "/srv/project".to_string(),
+            ..MonitoredAgent::for_test("main:0.1")
+        };
+        agent.status = AgentStatus::Idle;
+        let mut state = AppState::new();
+        state.recently_closed = closed_agents(&[agent], &[], &["main:0.1".to_string()]);
+
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("recently closed"));
+        assert!(rendered.contains("main:0.1 │ exited, was Idle"));
+    }
+}
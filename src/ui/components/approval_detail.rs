@@ -0,0 +1,140 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::agents::{AgentStatus, ApprovalType, MonitoredAgent};
+use crate::ui::Layout;
+
+/// Full, untruncated text of `agent`'s pending approval (command, path or diff),
+/// with a question's choices listed under it. None unless the agent is waiting.
+pub fn approval_detail(agent: &MonitoredAgent) -> Option<String> {
+    let AgentStatus::AwaitingApproval {
+        approval_type,
+        details,
+    } = &agent.status
+    else {
+        return None;
+    };
+    let mut text = details.clone();
+    if let ApprovalType::UserQuestion { choices, .. } = approval_type {
+        for (i, choice) in choices.iter().enumerate() {
+            text.push_str(&format!("\n{}. {}", i + 1, choice));
+        }
+    }
+    Some(text)
+}
+
+/// Popup with the full approval detail the sidebar truncates
+pub struct ApprovalDetailWidget;
+
+impl ApprovalDetailWidget {
+    pub fn render(frame: &mut Frame, area: Rect, agent: &MonitoredAgent) {
+        let Some(detail) = approval_detail(agent) else {
+            return;
+        };
+        let popup_area = Layout::centered_popup(area, 80, 60);
+
+        // Clear the background
+        frame.render_widget(Clear, popup_area);
+
+        let kind = match &agent.status {
+            AgentStatus::AwaitingApproval { approval_type, .. } => approval_type.short_desc(),
+            _ => "",
+        };
+        let mut lines: Vec<Line> = detail
+            .lines()
+            .map(|line| {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(Color::White),
+                ))
+            })
+            .collect();
+        lines.push(Line::from(vec![]));
+        lines.push(Line::from(vec![
+            Span::styled(
+                "any key",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": close", Style::default().fg(Color::DarkGray)),
+        ]));
+
+        let block = Block::default()
+            .title(format!(" {} │ {} ", kind, agent.target))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .style(Style::default().bg(Color::Black));
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(paragraph, popup_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn agent(status: AgentStatus) -> MonitoredAgent {
+        MonitoredAgent {
+            status,
+            ..MonitoredAgent::for_test("main:0.1")
+        }
+    }
+
+    #[test]
+    fn test_full_detail_available_from_agent() {
+        let command = format!("cargo test --workspace -- {}", "--nocapture ".repeat(20));
+        let shell = agent(AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: command.clone(),
+        });
+        assert_eq!(approval_detail(&shell), Some(command));
+
+        let question = agent(AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::UserQuestion {
+                choices: vec!["Unit".to_string(), "E2E".to_string()],
+                multi_select: false,
+            },
+            details: "Which tests?".to_string(),
+        });
+        assert_eq!(
+            approval_detail(&question).as_deref(),
+            Some("Which tests?\n1. Unit\n2. E2E")
+        );
+
+        assert_eq!(approval_detail(&agent(AgentStatus::Idle)), None);
+    }
+
+    #[test]
+    fn test_popup_shows_whole_diff() {
+        let diff = "src/auth.rs\n- let token = None;\n+ let token = Some(load_token()?);";
+        let agent = agent(AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::FileEdit,
+            details: diff.to_string(),
+        });
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal
+            .draw(|frame| ApprovalDetailWidget::render(frame, frame.area(), &agent))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Edit │ main:0.1"));
+        assert!(screen.contains("+ let token = Some(load_token()?);"));
+    }
+}
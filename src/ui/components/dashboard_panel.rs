@@ -1,8 +1,13 @@
 //! Dashboard panel — shows capacity, sprint, board, MCPs, activity, session info.
+//!
+//! While the hub is connected the data comes from its /api/dashboard endpoint;
+//! otherwise it is read from local state files.
 
 use crate::hub_client::{AlertsResponse, AnalyticsDigest};
 use crate::app::AppState;
 use crate::state_reader::DashboardData;
+use crate::ui::text::char_slice;
+use crate::ui::ProjectPrefixes;
 use ratatui::{
     layout::{Constraint, Direction, Rect},
     style::{Color, Modifier, Style},
@@ -17,6 +22,18 @@ impl DashboardWidget {
     pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         let dash = &state.dashboard;
 
+        // Sections the hub sent malformed get a warning line under the panels
+        let area = if dash.parse_errors.is_empty() {
+            area
+        } else {
+            let rows = ratatui::layout::Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(area);
+            Self::render_parse_errors(frame, rows[1], dash);
+            rows[0]
+        };
+
         // Split into 5 columns
         let cols = ratatui::layout::Layout::default()
             .direction(Direction::Horizontal)
@@ -61,7 +78,7 @@ impl DashboardWidget {
             .split(cols[3]);
         Self::render_session(frame, col4[0], dash);
         Self::render_processes(frame, col4[1], dash);
-        Self::render_multi_agent(frame, col4[2], dash);
+        Self::render_multi_agent(frame, col4[2], dash, &state.project_prefixes);
         // Col 5: Analytics (digest + alerts from API)
         let analytics = ratatui::layout::Layout::default()
             .direction(Direction::Vertical)
@@ -71,6 +88,22 @@ impl DashboardWidget {
         Self::render_alerts(frame, analytics[1], &state.alerts);
     }
 
+    fn render_parse_errors(frame: &mut Frame, area: Rect, dash: &DashboardData) {
+        let line = Line::from(vec![
+            Span::styled(
+                " ⚠ Malformed hub data, shown empty: ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                dash.parse_errors.join(" │ "),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
     fn gauge_spans(used: f64, total: f64, width: usize) -> Vec<Span<'static>> {
         let pct = if total > 0.0 { used / total } else { 0.0 };
         let filled = (pct * width as f64) as usize;
@@ -351,8 +384,9 @@ impl DashboardWidget {
                         .due_date
                         .as_deref()
                         .map(|d| {
-                            if d.len() >= 10 {
-                                format!(" {}", &d[5..10])
+                            // "2026-03-14..." shows as "03-14"
+                            if d.chars().count() >= 10 {
+                                format!(" {}", char_slice(d, 5, 5))
                             } else {
                                 format!(" {}", d)
                             }
@@ -670,7 +704,12 @@ impl DashboardWidget {
         frame.render_widget(Paragraph::new(lines).block(block), area);
     }
 
-    fn render_multi_agent(frame: &mut Frame, area: Rect, dash: &DashboardData) {
+    fn render_multi_agent(
+        frame: &mut Frame,
+        area: Rect,
+        dash: &DashboardData,
+        prefixes: &ProjectPrefixes,
+    ) {
         let agents = &dash.multi_agent;
         let max_lines = (area.height as usize).saturating_sub(2);
 
@@ -704,7 +743,7 @@ impl DashboardWidget {
                             Style::default().fg(Color::Cyan),
                         ),
                         Span::styled(
-                            format!("{:<10}", truncate_dash(&a.project, 10)),
+                            format!("{:<10}", truncate_dash(prefixes.shorten(&a.project), 10)),
                             Style::default().fg(Color::White),
                         ),
                         Span::styled(
@@ -800,3 +839,68 @@ fn truncate_dash(s: &str, max: usize) -> String {
         format!("{}…", s.chars().take(max.saturating_sub(1)).collect::<String>())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(state: &AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(160, 20)).unwrap();
+        terminal
+            .draw(|frame| DashboardWidget::render(frame, frame.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_parse_errors_render_as_warning_line() {
+        let mut state = AppState::new();
+        assert!(!render_to_string(&state).contains("Malformed"));
+
+        state.dashboard.parse_errors =
+            vec!["session: invalid type: string \"x\", expected a sequence".to_string()];
+        let rendered = render_to_string(&state);
+        let last = rendered.lines().last().unwrap();
+        assert!(last.contains("Malformed hub data"));
+        assert!(last.contains("session: invalid type"));
+    }
+
+    #[test]
+    fn test_milestones_and_workflows_render() {
+        let mut state = AppState::new();
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("No milestones"));
+        assert!(rendered.contains("No active workflows"));
+
+        state.dashboard.milestones = serde_json::from_str(
+            r#"[
+                {"name": "Beta launch", "status": "open", "due_date": "2026-03-14"},
+                {"name": "Old release", "status": "closed"}
+            ]"#,
+        )
+        .unwrap();
+        state.dashboard.processes = serde_json::from_str(
+            r#"[
+                {"id": "p1", "template": "release", "status": "active",
+                 "total_steps": 4, "completed_steps": 3},
+                {"id": "p2", "template": "done-flow", "status": "completed"}
+            ]"#,
+        )
+        .unwrap();
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("Milestones (1)"));
+        assert!(rendered.contains("Beta launch 03-14"));
+        assert!(!rendered.contains("Old release"));
+        assert!(rendered.contains("Workflows (1)"));
+        assert!(rendered.contains("release"));
+        assert!(rendered.contains("3/4"));
+        assert!(!rendered.contains("done-flow"));
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+
+/// Maximum number of per-pane rows shown
+const MAX_PANE_ROWS: usize = 12;
+
+/// Diagnostic overlay (F12) with poll timing and parser match stats
+pub struct DebugOverlayWidget;
+
+impl DebugOverlayWidget {
+    pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+        let stats = &state.poll_stats;
+        let width = 56.min(area.width);
+        let rows = 11 + stats.panes.len().min(MAX_PANE_ROWS) as u16;
+        let height = rows.min(area.height.saturating_sub(1));
+        let popup_area = Rect::new(area.x + area.width - width, area.y + 1, width, height);
+
+        frame.render_widget(Clear, popup_area);
+
+        let label_style = Style::default().fg(Color::DarkGray);
+        let value_style = Style::default().fg(Color::White);
+        let section_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+
+        let api = match stats.api_latency {
+            Some(latency) => format_ms(latency),
+            None => "-".to_string(),
+        };
+        let parsers = stats
+            .parser_counts()
+            .iter()
+            .map(|(name, count)| format!("{} {}", name, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" poll     ", label_style),
+                Span::styled(format_ms(stats.poll_duration), value_style),
+            ]),
+            Line::from(vec![
+                Span::styled(" capture  ", label_style),
+                Span::styled(format_ms(stats.total_capture()), value_style),
+            ]),
+            Line::from(vec![
+                Span::styled(" api      ", label_style),
+                Span::styled(api, value_style),
+            ]),
+            Line::from(vec![
+                Span::styled(" parsers  ", label_style),
+                Span::styled(
+                    format!("{} (unmatched {})", parsers, stats.unmatched_panes),
+                    value_style,
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(" cached   ", label_style),
+                Span::styled(
+                    format!("{}/{} unchanged", stats.parse_cache_hits, stats.panes.len()),
+                    value_style,
+                ),
+            ]),
+            Line::from(vec![]),
+            Line::from(vec![Span::styled(" Panes", section_style)]),
+        ];
+
+        let slowest = stats.slowest_pane().map(|p| p.target.as_str());
+        for pane in stats.panes.iter().take(MAX_PANE_ROWS) {
+            let time_style = if Some(pane.target.as_str()) == slowest {
+                Style::default().fg(Color::Yellow)
+            } else {
+                value_style
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {:<14}", pane.target), value_style),
+                Span::styled(format!("{:>8} ", format_ms(pane.capture)), time_style),
+                Span::styled(pane.parser.clone(), label_style),
+            ]));
+        }
+        if stats.panes.len() > MAX_PANE_ROWS {
+            lines.push(Line::from(vec![Span::styled(
+                format!(" … {} more", stats.panes.len() - MAX_PANE_ROWS),
+                label_style,
+            )]));
+        }
+
+        let block = Block::default()
+            .title(" Debug (F12) ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Magenta));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+}
+
+fn format_ms(d: Duration) -> String {
+    format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,104 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem},
+    Frame,
+};
+
+use crate::app::AppState;
+
+/// Widget for the session's log of status changes and user actions
+pub struct EventLogWidget;
+
+impl EventLogWidget {
+    pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+        let log = &state.event_log;
+        let title = if log.scroll() > 0 {
+            format!(" Event Log · ↑{} ", log.scroll())
+        } else {
+            " Event Log ".to_string()
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Gray));
+
+        if log.entries().is_empty() {
+            let empty = ListItem::new(Line::from(Span::styled(
+                "  No events yet",
+                Style::default().fg(Color::DarkGray),
+            )));
+            frame.render_widget(List::new(vec![empty]).block(block), area);
+            return;
+        }
+
+        // Newest at the bottom; scrolling moves the window back in time
+        let rows = area.height.saturating_sub(2) as usize;
+        let end = log.entries().len() - log.scroll();
+        let start = end.saturating_sub(rows);
+        let items: Vec<ListItem> = log
+            .entries()
+            .range(start..end)
+            .map(|event| {
+                let text_style = if event.text.starts_with('→') {
+                    Style::default().fg(Color::Gray)
+                } else {
+                    // The user's own actions
+                    Style::default().fg(Color::Cyan)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        event.at.format(" %H:%M ").to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(event.target.clone(), Style::default().fg(Color::White)),
+                    Span::raw(" "),
+                    Span::styled(event.text.clone(), text_style),
+                ]))
+            })
+            .collect();
+        frame.render_widget(List::new(items).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(state: &AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(50, 5)).unwrap();
+        terminal
+            .draw(|frame| EventLogWidget::render(frame, frame.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_render_keeps_newest_in_view() {
+        let mut state = AppState::new();
+        assert!(render_to_string(&state).contains("No events yet"));
+
+        for i in 0..5 {
+            state.event_log.push("main:0.1", format!("event {}", i));
+        }
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("main:0.1 event 4"));
+        assert!(rendered.contains("event 2"));
+        assert!(!rendered.contains("event 1"));
+
+        state.event_log.scroll_up(2);
+        let scrolled = render_to_string(&state);
+        assert!(scrolled.contains("Event Log · ↑2"));
+        assert!(scrolled.contains("event 0"));
+        assert!(!scrolled.contains("event 3"));
+    }
+}
@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::app::AppState;
+use crate::ui::text::char_slice;
 
 pub struct FactoryPanelWidget;
 
@@ -105,9 +106,21 @@ impl FactoryPanelWidget {
 }
 
 fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}…", &s[..max_len - 1])
+        format!("{}…", char_slice(s, 0, max_len.saturating_sub(1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_str_never_splits_chars() {
+        assert_eq!(truncate_str("add login page", 60), "add login page");
+        assert_eq!(truncate_str("ログイン画面を追加する", 6), "ログイン画…");
+        assert_eq!(truncate_str("fix 🚀 launch", 6), "fix 🚀…");
     }
 }
@@ -6,7 +6,12 @@ use ratatui::{
     Frame,
 };
 
+use crate::agents::{AgentStatus, ApprovalType};
 use crate::app::AppState;
+use crate::ui::FlashKind;
+
+/// Longest file path shown in the footer's approval prompt; longer ones keep their end
+const PROMPT_PATH_CHARS: usize = 40;
 
 /// Button definitions for footer
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,7 +34,8 @@ impl FooterWidget {
         let mut buttons = Vec::new();
         let mut col: u16 = 0;
 
-        if state.is_input_focused() {
+        if state.is_input_focused() || state.is_preview_focused() || state.is_command_bar_focused()
+        {
             return buttons;
         }
 
@@ -72,18 +78,33 @@ impl FooterWidget {
         None
     }
 
+    /// Compact reminder of the sidebar keys, shown in input mode
+    fn minimal_hints(key: Style, txt: Style) -> Vec<Span<'static>> {
+        vec![
+            Span::styled(" y", key),
+            Span::styled("/", txt),
+            Span::styled("n", key),
+            Span::styled(":Approve/Reject ", txt),
+            Span::styled("?", key),
+            Span::styled(":Help ", txt),
+            Span::styled("q", key),
+            Span::styled(":Quit ", txt),
+        ]
+    }
+
     pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
-        let btn_y = Style::default().fg(Color::Black).bg(Color::Green);
-        let btn_n = Style::default().fg(Color::Black).bg(Color::Red);
-        let btn_a = Style::default().fg(Color::Black).bg(Color::Yellow);
-        let btn_sel = Style::default().fg(Color::Black).bg(Color::Cyan);
-        let btn_def = Style::default().fg(Color::Black).bg(Color::Gray);
-        let sep = Style::default().fg(Color::DarkGray);
+        let theme = &state.theme;
+        let btn_y = theme.button(Color::Green);
+        let btn_n = theme.button(Color::Red);
+        let btn_a = theme.button(Color::Yellow);
+        let btn_sel = theme.button(Color::Cyan);
+        let btn_def = theme.button(Color::Gray);
+        let sep = theme.fg(theme.muted);
         let key = Style::default().fg(Color::Yellow);
         let txt = Style::default().fg(Color::White);
 
-        let line: Line = if state.is_input_focused() {
-            Line::from(vec![
+        let mut spans = if state.is_input_focused() {
+            let mut spans = vec![
                 Span::styled(
                     " INPUT ",
                     Style::default()
@@ -99,7 +120,49 @@ impl FooterWidget {
                 Span::styled(":NL ", txt),
                 Span::styled("Esc", key),
                 Span::styled(":Back ", txt),
-            ])
+            ];
+            // Core sidebar keys stay discoverable while typing
+            if state.footer_hints {
+                spans.push(Span::styled("│", sep));
+                spans.extend(Self::minimal_hints(key, txt));
+            }
+            spans
+        } else if state.is_command_bar_focused() {
+            vec![
+                Span::styled(
+                    " FACTORY ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("│", sep),
+                Span::styled(" Enter", key),
+                Span::styled(":Submit request ", txt),
+                Span::styled("Esc", key),
+                Span::styled(":Cancel ", txt),
+            ]
+        } else if state.is_preview_focused() {
+            vec![
+                Span::styled(
+                    " PREVIEW ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("│", sep),
+                Span::styled(" j/k", key),
+                Span::styled(":Line ", txt),
+                Span::styled("C-u/C-d", key),
+                Span::styled(":Page ", txt),
+                Span::styled("g", key),
+                Span::styled(":Bottom ", txt),
+                Span::styled("/", key),
+                Span::styled(":Search ", txt),
+                Span::styled("n/N", key),
+                Span::styled(":Match ", txt),
+                Span::styled("Esc", key),
+                Span::styled(":Back ", txt),
+            ]
         } else {
             let mut spans = vec![
                 Span::styled(" Y ", btn_y),
@@ -122,33 +185,90 @@ impl FooterWidget {
                     format!(" ({}sel)", state.selected_agents.len()),
                     Style::default().fg(Color::Cyan),
                 ));
-            }
-
-            // Flash notification (takes priority over error)
-            if let Some((msg, _)) = &state.flash_message {
+            } else if let Some(prompt) = file_prompt(state) {
+                // What Y would approve for the agent under the cursor
                 spans.push(Span::styled(" │ ", sep));
                 spans.push(Span::styled(
-                    format!("✓ {}", truncate_error(msg, 40)),
+                    prompt,
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(state.status_colors.approval)
                         .add_modifier(Modifier::BOLD),
                 ));
-            } else if let Some(error) = &state.last_error {
-                spans.push(Span::styled(" │ ", sep));
-                spans.push(Span::styled(
-                    format!("✗ {}", truncate_error(error, 30)),
-                    Style::default().fg(Color::Red),
-                ));
             }
-
-            Line::from(spans)
+            spans
         };
 
+        // Flash notification (takes priority over error)
+        if let Some(flash) = state.flashes.current() {
+            let (icon, color) = match flash.kind {
+                FlashKind::Info => ("✓", Color::Green),
+                FlashKind::Critical => ("⚠", Color::Yellow),
+            };
+            // Confirmations of sends that only went to the log
+            let tag = if state.dry_run && flash.kind == FlashKind::Info {
+                "[dry-run] "
+            } else {
+                ""
+            };
+            spans.push(Span::styled(" │ ", sep));
+            spans.push(Span::styled(
+                format!("{} {}{}", icon, tag, truncate_error(&flash.text, 40)),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        } else if let Some(error) = &state.last_error {
+            spans.push(Span::styled(" │ ", sep));
+            spans.push(Span::styled(
+                format!("✗ {}", truncate_error(error, 30)),
+                Style::default().fg(Color::Red),
+            ));
+        }
+
+        let line = Line::from(spans);
         let paragraph = Paragraph::new(line);
         frame.render_widget(paragraph, area);
     }
 }
 
+/// "Edit src/main.rs?" when the agent under the cursor asks to edit, create or
+/// delete a file it named
+fn file_prompt(state: &AppState) -> Option<String> {
+    let AgentStatus::AwaitingApproval {
+        approval_type,
+        details,
+    } = &state.selected_agent()?.status
+    else {
+        return None;
+    };
+    let verb = match approval_type {
+        ApprovalType::FileEdit => "Edit",
+        ApprovalType::FileCreate => "Create",
+        ApprovalType::FileDelete => "Delete",
+        _ => return None,
+    };
+    let path = details.lines().next().unwrap_or("").trim();
+    if path.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} {}?",
+        verb,
+        truncate_path(path, PROMPT_PATH_CHARS)
+    ))
+}
+
+/// Keeps the end of a long path, where the file name is: "…/ui/components/footer.rs"
+fn truncate_path(path: &str, max_len: usize) -> String {
+    let len = path.chars().count();
+    if len <= max_len {
+        path.to_string()
+    } else {
+        format!(
+            "…{}",
+            path.chars().skip(len - (max_len - 1)).collect::<String>()
+        )
+    }
+}
+
 fn truncate_error(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
         s.to_string()
@@ -156,3 +276,117 @@ fn truncate_error(s: &str, max_len: usize) -> String {
         format!("{}…", s.chars().take(max_len - 1).collect::<String>())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::MonitoredAgent;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(state: &AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 1)).unwrap();
+        terminal
+            .draw(|frame| FooterWidget::render(frame, frame.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn test_minimal_hints_in_input_mode() {
+        let mut state = AppState::new();
+        state.focus_input();
+
+        state.footer_hints = true;
+        let line = render_to_string(&state);
+        assert!(line.contains("INPUT"));
+        assert!(line.contains(":Approve/Reject"));
+        assert!(line.contains(":Quit"));
+
+        state.footer_hints = false;
+        assert!(!render_to_string(&state).contains(":Approve/Reject"));
+    }
+
+    #[test]
+    fn test_flash_shown_in_input_mode() {
+        let mut state = AppState::new();
+        state.focus_input();
+        state.flash("Sent to /h/u/project".to_string());
+        assert!(render_to_string(&state).contains("✓ Sent to /h/u/project"));
+
+        state.dry_run = true;
+        state.footer_hints = false;
+        assert!(render_to_string(&state).contains("✓ [dry-run] Sent to /h/u/project"));
+    }
+
+    #[test]
+    fn test_file_approval_prompt_names_the_path() {
+        let mut state = AppState::new();
+        let mut agent = MonitoredAgent::for_test("main:0.0");
+        agent.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::FileEdit,
+            details: "src/main.rs".to_string(),
+        };
+        state.agents.root_agents = vec![agent];
+        assert!(render_to_string(&state).contains("Edit src/main.rs?"));
+
+        let long = format!("{}/footer.rs", "deeply/nested".repeat(5));
+        state.agents.root_agents[0].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::FileDelete,
+            details: long,
+        };
+        let line = render_to_string(&state);
+        assert!(line.contains("Delete …"));
+        assert!(line.contains("/footer.rs?"));
+
+        // Shell commands and path-less file prompts keep the plain buttons
+        state.agents.root_agents[0].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::FileCreate,
+            details: String::new(),
+        };
+        assert!(!render_to_string(&state).contains("Create"));
+        state.agents.root_agents[0].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "cargo test".to_string(),
+        };
+        assert!(!render_to_string(&state).contains("cargo test"));
+    }
+
+    #[test]
+    fn test_preview_mode_hides_buttons() {
+        let mut state = AppState::new();
+        state.focus_preview();
+        assert!(render_to_string(&state).contains("PREVIEW"));
+        assert!(FooterWidget::get_button_layout(&state).is_empty());
+    }
+
+    #[test]
+    fn test_command_bar_mode_hides_buttons() {
+        let mut state = AppState::new();
+        state.focus_command_bar();
+        let line = render_to_string(&state);
+        assert!(line.contains("FACTORY"));
+        assert!(line.contains(":Submit request"));
+        assert!(FooterWidget::get_button_layout(&state).is_empty());
+    }
+
+    #[test]
+    fn test_monochrome_buttons_use_reverse_video() {
+        let mut state = AppState::new();
+        state.theme = crate::ui::Theme::monochrome();
+        let mut terminal = Terminal::new(TestBackend::new(120, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                FooterWidget::render(frame, frame.area(), &state);
+                state.theme.apply(frame.buffer_mut());
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        assert!(buffer
+            .content()
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+        // The " Y " approve button is still distinguishable
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+    }
+}
@@ -1,4 +1,6 @@
+use crate::agents::AgentStatus;
 use crate::app::AppState;
+use crate::ui::filter::unacknowledged_pending;
 use chrono::Local;
 use ratatui::{
     layout::Rect,
@@ -14,7 +16,9 @@ impl HeaderWidget {
     pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         let total = state.agents.root_agents.len();
         let processing = state.agents.processing_count();
-        let pending = state.agents.active_count();
+        // Agents marked reviewed don't count until their status changes
+        let pending = unacknowledged_pending(state);
+        let subagents = state.agents.running_subagent_count();
         let queue_pending = state
             .queue_tasks
             .iter()
@@ -45,6 +49,20 @@ impl HeaderWidget {
             ),
         ];
 
+        // Keys are logged instead of sent, so approvals here don't reach the agents
+        if state.dry_run {
+            spans.insert(
+                1,
+                Span::styled(
+                    " DRY RUN ",
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            );
+        }
+
         // Session cost (always visible when > 0)
         if session_cost > 0.001 || today_cost > 0.001 {
             spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
@@ -66,7 +84,16 @@ impl HeaderWidget {
             spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
             spans.push(Span::styled(
                 format!(" {} {} working ", state.spinner_frame(), processing),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.status_colors.processing),
+            ));
+        }
+
+        // Task subagents running across all agents
+        if subagents > 0 {
+            spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!(" {} {} subs ", state.spinner_frame(), subagents),
+                Style::default().fg(state.status_colors.processing),
             ));
         }
 
@@ -75,10 +102,15 @@ impl HeaderWidget {
         if pending > 0 {
             spans.push(Span::styled(
                 format!(" {} pending ", pending),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(state.status_colors.approval)
+                    .add_modifier(Modifier::BOLD),
             ));
         } else {
-            spans.push(Span::styled(" ready ", Style::default().fg(Color::Green)));
+            spans.push(Span::styled(
+                " ready ",
+                Style::default().fg(state.status_colors.color(&AgentStatus::Idle)),
+            ));
         }
 
         // Queue info (if connected to hub)
@@ -113,6 +145,17 @@ impl HeaderWidget {
             ));
         }
 
+        // Agents sharing a working directory
+        if !state.path_collisions.is_empty() {
+            spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!(" ⚠ {} shared dir ", state.path_collisions.len()),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
         // ACU usage from dashboard
         let cap = &state.dashboard.capacity;
         if cap.acu_total > 0.0 {
@@ -162,6 +205,22 @@ impl HeaderWidget {
             Style::default().fg(cpu_color),
         ));
 
+        // System stats: one-minute load, colored against the core count
+        if let Some(load) = state.system_stats.load_avg() {
+            let cores = state.system_stats.core_usages().len().max(1) as f64;
+            let load_color = if load.one > cores {
+                Color::Red
+            } else if load.one > cores * 0.7 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            spans.push(Span::styled(
+                format!("load {:.2} ", load.one),
+                Style::default().fg(load_color),
+            ));
+        }
+
         // System stats: Memory
         spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
         let mem_percent = state.system_stats.memory_percent();
@@ -183,11 +242,8 @@ impl HeaderWidget {
 
         // API connection status
         spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
-        if state.hub_connected {
-            spans.push(Span::styled(" API ", Style::default().fg(Color::Green)));
-        } else {
-            spans.push(Span::styled(" API ", Style::default().fg(Color::DarkGray)));
-        }
+        let (api_text, api_color) = api_status(state);
+        spans.push(Span::styled(api_text, Style::default().fg(api_color)));
 
         // Time
         spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
@@ -206,3 +262,116 @@ impl HeaderWidget {
         frame.render_widget(paragraph, area);
     }
 }
+
+/// " API 42ms " while connected, " API retry#3 " while the hub keeps failing
+fn api_status(state: &AppState) -> (String, Color) {
+    if state.hub_connected {
+        match state.hub_latency {
+            Some(latency) => (format!(" API {}ms ", latency.as_millis()), Color::Green),
+            None => (" API ".to_string(), Color::Green),
+        }
+    } else if state.hub_fail_count > 0 {
+        (format!(" API retry#{} ", state.hub_fail_count), Color::Yellow)
+    } else {
+        (" API ".to_string(), Color::DarkGray)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{MonitoredAgent, Subagent, SubagentStatus, SubagentType};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(state: &AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(200, 3)).unwrap();
+        terminal
+            .draw(|frame| HeaderWidget::render(frame, frame.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_load_colored_against_core_count() {
+        use crate::monitor::{LoadAverage, SystemStats};
+
+        let load = |one| LoadAverage {
+            one,
+            five: 0.0,
+            fifteen: 0.0,
+        };
+        let load_color = |state: &AppState| {
+            let mut terminal = Terminal::new(TestBackend::new(200, 3)).unwrap();
+            terminal
+                .draw(|frame| HeaderWidget::render(frame, frame.area(), state))
+                .unwrap();
+            let cells = terminal.backend().buffer().content();
+            let at = cells
+                .windows(5)
+                .position(|w| w.iter().map(|cell| cell.symbol()).collect::<String>() == "load ")?;
+            Some(cells[at].fg)
+        };
+
+        let mut state = AppState::new();
+        assert_eq!(load_color(&state), None);
+
+        state.system_stats = SystemStats::with_load(Some(load(1.0)), vec![0.0; 4]);
+        assert!(render_to_string(&state).contains("load 1.00"));
+        assert_eq!(load_color(&state), Some(Color::Green));
+        state.system_stats = SystemStats::with_load(Some(load(3.5)), vec![0.0; 4]);
+        assert_eq!(load_color(&state), Some(Color::Yellow));
+        state.system_stats = SystemStats::with_load(Some(load(4.5)), vec![0.0; 4]);
+        assert_eq!(load_color(&state), Some(Color::Red));
+        // Without a core count the load is judged against one core
+        state.system_stats = SystemStats::with_load(Some(load(1.5)), Vec::new());
+        assert_eq!(load_color(&state), Some(Color::Red));
+    }
+
+    #[test]
+    fn test_subs_segment_counts_running_subagents() {
+        let mut state = AppState::new();
+        assert!(!render_to_string(&state).contains("subs"));
+
+        let mut agent = MonitoredAgent::for_test("main:0.0");
+        agent.subagents = vec![
+            Subagent::new("1".into(), SubagentType::Explore, "a".into()),
+            Subagent::new("2".into(), SubagentType::Plan, "b".into()),
+            Subagent::new("3".into(), SubagentType::Bash, "c".into())
+                .with_status(SubagentStatus::Completed),
+        ];
+        state.agents.root_agents = vec![agent];
+
+        assert!(render_to_string(&state).contains(" 2 subs "));
+    }
+
+    #[test]
+    fn test_api_segment_shows_latency_or_retries() {
+        let mut state = AppState::new();
+        state.hub_connected = true;
+        state.hub_latency = Some(std::time::Duration::from_millis(42));
+        assert!(render_to_string(&state).contains(" API 42ms "));
+
+        state.hub_connected = false;
+        state.hub_fail_count = 3;
+        assert!(render_to_string(&state).contains(" API retry#3 "));
+
+        // No hub configured, or not tried yet
+        state.hub_fail_count = 0;
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains(" API "));
+        assert!(!rendered.contains("retry"));
+    }
+
+    #[test]
+    fn test_dry_run_badge() {
+        let mut state = AppState::new();
+        assert!(!render_to_string(&state).contains("DRY RUN"));
+        state.dry_run = true;
+        assert!(render_to_string(&state).contains(" DX  DRY RUN │"));
+    }
+}
@@ -41,6 +41,14 @@ impl HelpWidget {
                 Span::styled("  Tab      ", key_style),
                 Span::styled("Next agent (cycle)", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  . / ,    ", key_style),
+                Span::styled("Next / previous agent needing attention", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  /        ", key_style),
+                Span::styled("Filter agents (Enter keep, Esc clear)", desc_style),
+            ]),
             Line::from(vec![]),
             Line::from(vec![Span::styled("Selection", section_style)]),
             Line::from(vec![]),
@@ -54,7 +62,7 @@ impl HelpWidget {
             ]),
             Line::from(vec![
                 Span::styled("  Esc      ", key_style),
-                Span::styled("Clear selection / Close subagent log", desc_style),
+                Span::styled("Clear selection / Close log panel", desc_style),
             ]),
             Line::from(vec![]),
             Line::from(vec![Span::styled("Actions", section_style)]),
@@ -75,6 +83,14 @@ impl HelpWidget {
                 Span::styled("  1-9      ", key_style),
                 Span::styled("Send number choice to agent", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  J / K    ", key_style),
+                Span::styled("Highlight next/prev answer to a question", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  m        ", key_style),
+                Span::styled("Mark reviewed (dimmed until status changes)", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  ← / →    ", key_style),
                 Span::styled("Switch focus (Sidebar / Input)", desc_style),
@@ -83,10 +99,66 @@ impl HelpWidget {
                 Span::styled("  C-Enter  ", key_style),
                 Span::styled("Send input to all selected agents", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  Alt+w    ", key_style),
+                Span::styled("Send input to every agent in the window", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  C-s      ", key_style),
+                Span::styled("Send input as one pasted block", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  {Tab}    ", key_style),
+                Span::styled("Send a key: {Up} {Esc} {C-c} {F1}...", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  ↑ / ↓    ", key_style),
+                Span::styled("Recall earlier inputs (in input)", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  u / C-z  ", key_style),
+                Span::styled("Undo last send, within 10s (best-effort)", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  f / F    ", key_style),
                 Span::styled("Focus on selected pane in tmux", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  i        ", key_style),
+                Span::styled("Show full approval detail", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  v / V    ", key_style),
+                Span::styled("Fold selected window / session (click header to unfold)", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  =        ", key_style),
+                Span::styled("Unfold all sessions and windows", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  z        ", key_style),
+                Span::styled("Zoom / unzoom selected pane in tmux", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  E        ", key_style),
+                Span::styled("Open $EDITOR on agent's directory (new window)", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  x        ", key_style),
+                Span::styled("Interrupt agent(s) with Ctrl-C", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  Alt+x    ", key_style),
+                Span::styled("Close agent pane(s) (press twice)", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  c        ", key_style),
+                Span::styled("Send Escape to dismiss a prompt", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  C        ", key_style),
+                Span::styled("Copy approval details to clipboard", desc_style),
+            ]),
             Line::from(vec![]),
             Line::from(vec![Span::styled("View", section_style)]),
             Line::from(vec![]),
@@ -94,18 +166,58 @@ impl HelpWidget {
                 Span::styled("  s / S    ", key_style),
                 Span::styled("Toggle subagent log", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  L        ", key_style),
+                Span::styled("Toggle event log", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  [ / ]    ", key_style),
+                Span::styled("Scroll event log back / forward", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  t / T    ", key_style),
                 Span::styled("Toggle TODO/Tools display", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  o        ", key_style),
+                Span::styled("Expand / collapse subagents of agent", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  O        ", key_style),
+                Span::styled("Cycle sort: target / attention / uptime", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  p        ", key_style),
+                Span::styled("Toggle full / abbreviated paths", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  e        ", key_style),
+                Span::styled("Export pane scrollback to ~/agentos-tui-exports", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  Q        ", key_style),
                 Span::styled("Toggle queue panel", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  b        ", key_style),
+                Span::styled("Focus queue: j/k select, x cancel, +/- priority", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  D        ", key_style),
                 Span::styled("Toggle dashboard panel", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  P        ", key_style),
+                Span::styled("Toggle factory panel", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  G        ", key_style),
+                Span::styled("Toggle CPU / memory / load graph", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  :        ", key_style),
+                Span::styled("Submit a factory request (Enter sends)", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  C-u/C-d  ", key_style),
                 Span::styled("Scroll preview up/down", desc_style),
@@ -114,6 +226,26 @@ impl HelpWidget {
                 Span::styled("  PgUp/Dn  ", key_style),
                 Span::styled("Scroll preview up/down", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  Enter    ", key_style),
+                Span::styled("Focus preview (j/k scroll, Esc back)", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  S-←/→    ", key_style),
+                Span::styled("Scroll preview left/right (no wrap)", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  w        ", key_style),
+                Span::styled("Toggle preview line wrapping", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  / n N    ", key_style),
+                Span::styled("In preview: search, next/previous match", desc_style),
+            ]),
+            Line::from(vec![
+                Span::styled("  #        ", key_style),
+                Span::styled("In preview: toggle line numbers", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  g        ", key_style),
                 Span::styled("Scroll to bottom (latest)", desc_style),
@@ -126,6 +258,10 @@ impl HelpWidget {
                 Span::styled("  r        ", key_style),
                 Span::styled("Refresh / clear error", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  R        ", key_style),
+                Span::styled("Reconnect to AgentOS now (skip backoff)", desc_style),
+            ]),
             Line::from(vec![]),
             Line::from(vec![Span::styled("General", section_style)]),
             Line::from(vec![]),
@@ -133,6 +269,10 @@ impl HelpWidget {
                 Span::styled("  h / ?    ", key_style),
                 Span::styled("Toggle this help", desc_style),
             ]),
+            Line::from(vec![
+                Span::styled("  F12      ", key_style),
+                Span::styled("Toggle debug overlay (poll timing)", desc_style),
+            ]),
             Line::from(vec![
                 Span::styled("  q        ", key_style),
                 Span::styled("Quit", desc_style),
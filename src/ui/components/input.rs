@@ -1,6 +1,6 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph, Wrap},
     Frame,
@@ -8,6 +8,9 @@ use ratatui::{
 use unicode_width::UnicodeWidthStr;
 
 use crate::app::AppState;
+use crate::ui::filter::{cursor_agent, operation_indices};
+use crate::ui::label::{agent_color, agent_label};
+use crate::ui::text::char_slice;
 
 /// Input widget for text entry at the bottom of the right column
 pub struct InputWidget;
@@ -18,15 +21,37 @@ impl InputWidget {
         let cursor_pos = state.get_cursor_position();
         let is_focused = state.is_input_focused() || state.is_command_bar_focused();
 
+        // Ctrl+Enter goes to every selected agent; say so before it's pressed
+        let operation = operation_indices(state);
+        let broadcast = operation.len() > 1 && !state.is_command_bar_focused();
+
         let (title, border_color) = if state.is_command_bar_focused() {
-            (" Factory > ".to_string(), Color::Yellow)
+            (Line::from(" Factory > "), Color::Yellow)
+        } else if broadcast {
+            (
+                Line::from(Span::styled(
+                    format!(" Input → {} agents ", operation.len()),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                if state.is_input_focused() {
+                    Color::Yellow
+                } else {
+                    Color::DarkGray
+                },
+            )
         } else {
-            let target_name = state
-                .selected_agent()
-                .map(|a| a.abbreviated_path())
-                .unwrap_or_else(|| "None".to_string());
+            // The target keeps its tree color so a labelled agent is recognizable
+            let target = match cursor_agent(state) {
+                Some(agent) => Span::styled(
+                    agent_label(state, agent),
+                    Style::default().fg(agent_color(state, agent)),
+                ),
+                None => Span::raw("None"),
+            };
             (
-                format!(" Input → {} ", target_name),
+                Line::from(vec![Span::raw(" Input → "), target, Span::raw(" ")]),
                 if state.is_input_focused() {
                     Color::Green
                 } else {
@@ -35,11 +60,23 @@ impl InputWidget {
             )
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(border_color));
+        if broadcast {
+            let labels: Vec<String> = operation
+                .iter()
+                .filter_map(|&i| state.agents.get_agent(i))
+                .map(|agent| agent_label(state, agent))
+                .collect();
+            let width = (area.width as usize).saturating_sub(4);
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {} ", target_list(&labels, width.saturating_sub(2))),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
 
         // Build content with cursor (only show cursor when focused)
         let lines: Vec<Line> = Self::build_lines_with_cursor(buffer, cursor_pos, is_focused);
@@ -184,3 +221,79 @@ impl InputWidget {
         line_count.max(1).min(max_height)
     }
 }
+
+/// `labels` joined with ", " in at most `width` characters, ending in "+N more"
+/// when some don't fit
+fn target_list(labels: &[String], width: usize) -> String {
+    let all = labels.join(", ");
+    if all.chars().count() <= width {
+        return all;
+    }
+    (1..labels.len())
+        .rev()
+        .map(|shown| {
+            format!(
+                "{}, +{} more",
+                labels[..shown].join(", "),
+                labels.len() - shown
+            )
+        })
+        .find(|list| list.chars().count() <= width)
+        .unwrap_or_else(|| char_slice(&format!("+{} more", labels.len()), 0, width).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::MonitoredAgent;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn agent(pane: u32) -> MonitoredAgent {
+        MonitoredAgent {
+            path: format!("/home/user/project{}", pane),
+            ..MonitoredAgent::for_test(&format!("main:0.{}", pane))
+        }
+    }
+
+    fn render_to_string(state: &AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 4)).unwrap();
+        terminal
+            .draw(|frame| InputWidget::render(frame, frame.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_title_shows_broadcast_targets() {
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![agent(0), agent(1), agent(2)];
+        assert!(!render_to_string(&state).contains("agents"));
+
+        state.selected_agents = [0, 2].into_iter().collect();
+        let rendered = render_to_string(&state);
+        let first = rendered.lines().next().unwrap();
+        let last = rendered.lines().last().unwrap();
+        assert!(first.contains("Input → 2 agents"), "{}", first);
+        assert!(last.contains("project0"), "{}", last);
+        assert!(last.contains("project2"), "{}", last);
+        assert!(!last.contains("project1"), "{}", last);
+    }
+
+    #[test]
+    fn test_target_list_fits_width() {
+        let labels: Vec<String> = ["api", "web", "worker"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(target_list(&labels, 40), "api, web, worker");
+        assert_eq!(target_list(&labels, 16), "api, web, worker");
+        assert_eq!(target_list(&labels, 15), "api, +2 more");
+        assert_eq!(target_list(&labels, 4), "+3 m");
+    }
+}
@@ -1,6 +1,9 @@
 mod agent_tree;
 mod analytics_panel;
+mod approval_detail;
 mod dashboard_panel;
+mod debug_overlay;
+mod event_log;
 mod factory_panel;
 mod footer;
 mod header;
@@ -8,16 +11,23 @@ mod help;
 mod input;
 mod pane_preview;
 mod queue_panel;
+mod send_preview;
+mod stats_graph;
 mod subagent_log;
 
 pub use agent_tree::AgentTreeWidget;
 pub use analytics_panel::AnalyticsWidget;
+pub use approval_detail::{approval_detail, ApprovalDetailWidget};
 pub use dashboard_panel::DashboardWidget;
+pub use debug_overlay::DebugOverlayWidget;
+pub use event_log::EventLogWidget;
 pub use factory_panel::FactoryPanelWidget;
 pub use footer::{FooterButton, FooterWidget};
 pub use header::HeaderWidget;
 pub use help::HelpWidget;
 pub use input::InputWidget;
-pub use pane_preview::PanePreviewWidget;
-pub use queue_panel::QueuePanelWidget;
+pub use pane_preview::{ClaudeCodeSummary, PanePreviewWidget, SummaryCache, PREVIEW_HSCROLL_STEP};
+pub use queue_panel::{bumped_priority, selected_task, visible_tasks, QueuePanelWidget};
+pub use send_preview::SendPreviewWidget;
+pub use stats_graph::StatsGraphWidget;
 pub use subagent_log::SubagentLogWidget;
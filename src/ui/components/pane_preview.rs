@@ -1,3 +1,8 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -8,9 +13,16 @@ use ratatui::{
 
 use crate::agents::AgentStatus;
 use crate::app::AppState;
+use crate::parsers::parse_activity_line;
+use crate::ui::preview_search::highlight_matches;
+use crate::ui::text::char_slice;
+
+/// Columns moved per horizontal scroll step in the detailed preview
+pub const PREVIEW_HSCROLL_STEP: usize = 8;
 
 /// Parsed summary info from Claude Code content
-struct ClaudeCodeSummary {
+#[derive(Debug)]
+pub struct ClaudeCodeSummary {
     /// Current status/activity line (✽ ...)
     current_activity: Option<String>,
     /// TODO items: (is_completed, text)
@@ -29,16 +41,8 @@ impl ClaudeCodeSummary {
             let trimmed = line.trim();
 
             // Current activity: ✽ text... or · text...
-            if trimmed.starts_with('✽') || trimmed.starts_with('·') {
-                let activity = trimmed
-                    .trim_start_matches('✽')
-                    .trim_start_matches('·')
-                    .trim();
-                // Extract just the main part (before parentheses with timing info)
-                let main_part = activity.split('(').next().unwrap_or(activity).trim();
-                if !main_part.is_empty() {
-                    current_activity = Some(main_part.to_string());
-                }
+            if let Some(activity) = parse_activity_line(trimmed) {
+                current_activity = Some(activity);
             }
 
             // TODOs: ☐ (pending) or ☑/✓ (completed)
@@ -82,6 +86,60 @@ impl ClaudeCodeSummary {
     }
 }
 
+/// Parsed summaries keyed by target, reused while the pane content is unchanged
+#[derive(Debug, Default)]
+pub struct SummaryCache {
+    /// target -> (content hash, parsed summary)
+    entries: HashMap<String, (u64, ClaudeCodeSummary)>,
+}
+
+impl SummaryCache {
+    /// Returns the cached summary if `content` is what it was parsed from
+    pub fn get(&self, target: &str, content: &str) -> Option<&ClaudeCodeSummary> {
+        self.entries
+            .get(target)
+            .filter(|(hash, _)| *hash == content_hash(content))
+            .map(|(_, summary)| summary)
+    }
+
+    /// Parses `content` unless the cached summary for `target` is still current
+    pub fn refresh(&mut self, target: &str, content: &str) {
+        self.refresh_with(target, content, ClaudeCodeSummary::parse);
+    }
+
+    fn refresh_with(
+        &mut self,
+        target: &str,
+        content: &str,
+        parse: impl FnOnce(&str) -> ClaudeCodeSummary,
+    ) {
+        let hash = content_hash(content);
+        if self.entries.get(target).is_some_and(|(h, _)| *h == hash) {
+            return;
+        }
+        self.entries.insert(target.to_string(), (hash, parse(content)));
+    }
+
+    /// Drops entries for targets that are no longer monitored
+    pub fn retain_targets<'a>(&mut self, targets: impl Iterator<Item = &'a str>) {
+        let live: Vec<&str> = targets.collect();
+        self.entries.retain(|target, _| live.contains(&target.as_str()));
+    }
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Rows of a `total_lines` long capture shown in a preview `available_lines`
+/// tall, scrolled `scroll` lines up from the bottom
+fn visible_rows(total_lines: usize, scroll: usize, available_lines: usize) -> Range<usize> {
+    let end = total_lines.saturating_sub(scroll);
+    end.saturating_sub(available_lines)..end
+}
+
 /// Widget for previewing the selected pane content
 pub struct PanePreviewWidget;
 
@@ -91,7 +149,15 @@ impl PanePreviewWidget {
         let agent = state.selected_agent();
 
         if let Some(agent) = agent {
-            let summary = ClaudeCodeSummary::parse(&agent.last_content);
+            // Normally parsed once per content change by the main loop
+            let parsed;
+            let summary = match state.summary_cache.get(&agent.target, &agent.last_content) {
+                Some(summary) => summary,
+                None => {
+                    parsed = ClaudeCodeSummary::parse(&agent.last_content);
+                    &parsed
+                }
+            };
 
             // Outer block for the entire summary area
             let outer_block = Block::default()
@@ -178,12 +244,10 @@ impl PanePreviewWidget {
                     AgentStatus::Idle => "Ready for input",
                     AgentStatus::Processing { activity } => activity.as_str(),
                     AgentStatus::AwaitingApproval { approval_type, .. } => {
+                        let style = state.status_colors.style(&agent.status);
                         activity_lines.push(Line::from(vec![
-                            Span::styled("⚠ ", Style::default().fg(Color::Red)),
-                            Span::styled(
-                                format!("Waiting: {}", approval_type),
-                                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                            ),
+                            Span::styled("⚠ ", style),
+                            Span::styled(format!("Waiting: {}", approval_type), style),
                         ]));
                         ""
                     }
@@ -218,6 +282,32 @@ impl PanePreviewWidget {
         }
     }
 
+    /// How far the detailed preview drawn in `area` can scroll right: until the
+    /// end of the widest line on screen meets the right border
+    pub fn max_hscroll(area: Rect, state: &AppState) -> usize {
+        let Some(agent) = state.selected_agent() else {
+            return 0;
+        };
+        let content_lines: Vec<&str> = agent.last_content.lines().collect();
+        let rows = visible_rows(
+            content_lines.len(),
+            state.preview_scroll,
+            area.height.saturating_sub(2) as usize,
+        );
+        let gutter = if state.preview_line_numbers {
+            content_lines.len().to_string().len() + 1
+        } else {
+            0
+        };
+        let width = (area.width.saturating_sub(2) as usize).saturating_sub(gutter);
+        content_lines[rows]
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+            .saturating_sub(width)
+    }
+
     /// Renders a detailed preview with syntax highlighting for diffs
     pub fn render_detailed(frame: &mut Frame, area: Rect, state: &AppState) {
         let agent = state.selected_agent();
@@ -231,11 +321,10 @@ impl PanePreviewWidget {
             let scroll = state.preview_scroll;
 
             // Calculate visible window with scroll offset
-            let end = total_lines.saturating_sub(scroll);
-            let start = end.saturating_sub(available_lines);
+            let Range { start, end } = visible_rows(total_lines, scroll, available_lines);
 
             // Build title with scroll indicator
-            let title = if scroll > 0 {
+            let mut title = if scroll > 0 {
                 format!(
                     " {} ({}) [{}-{}/{}] ",
                     agent.target,
@@ -247,26 +336,62 @@ impl PanePreviewWidget {
             } else {
                 format!(" {} ({}) ", agent.target, agent.agent_type)
             };
+            // Horizontal scrolling replaces wrapping
+            let hscroll = if state.preview_wrap {
+                0
+            } else {
+                title.push_str(&format!("[col {}] ", state.preview_hscroll + 1));
+                state.preview_hscroll
+            };
+
+            let search = &state.preview_search;
+            if search.is_active() || search.editing {
+                title.push_str(search.title(&agent.last_content).trim_start());
+            }
+            let current_match = search
+                .match_lines(&agent.last_content)
+                .get(search.current)
+                .copied();
+            let gutter_width = total_lines.to_string().len();
 
             let mut styled_lines: Vec<Line> = Vec::new();
 
-            for line in &content_lines[start..end] {
-                let spans = if line.starts_with('+') && !line.starts_with("+++") {
-                    vec![Span::styled(*line, Style::default().fg(Color::Green))]
-                } else if line.starts_with('-') && !line.starts_with("---") {
-                    vec![Span::styled(*line, Style::default().fg(Color::Red))]
-                } else if line.starts_with("@@") {
-                    vec![Span::styled(*line, Style::default().fg(Color::Cyan))]
-                } else if line.contains("[y/n]") || line.contains("[Y/n]") {
-                    vec![Span::styled(*line, Style::default().fg(Color::Yellow))]
-                } else if line.contains("⚠") || line.contains("Error") || line.contains("error") {
-                    vec![Span::styled(*line, Style::default().fg(Color::Red))]
-                } else if line.starts_with("❯") || line.starts_with(">") {
-                    vec![Span::styled(*line, Style::default().fg(Color::Cyan))]
+            for (index, &full_line) in content_lines.iter().enumerate().take(end).skip(start) {
+                // Styling is decided on the whole line, then the visible part is shown
+                let line = char_slice(full_line, hscroll, usize::MAX);
+                let base = if full_line.starts_with('+') && !full_line.starts_with("+++") {
+                    Style::default().fg(Color::Green)
+                } else if full_line.starts_with('-') && !full_line.starts_with("---") {
+                    Style::default().fg(Color::Red)
+                } else if full_line.starts_with("@@") {
+                    Style::default().fg(Color::Cyan)
+                } else if full_line.contains("[y/n]") || full_line.contains("[Y/n]") {
+                    Style::default().fg(Color::Yellow)
+                } else if full_line.contains("⚠")
+                    || full_line.contains("Error")
+                    || full_line.contains("error")
+                {
+                    Style::default().fg(Color::Red)
+                } else if full_line.starts_with("❯") || full_line.starts_with(">") {
+                    Style::default().fg(Color::Cyan)
                 } else {
-                    vec![Span::raw(*line)]
+                    Style::default()
                 };
 
+                let mut spans = Vec::new();
+                if state.preview_line_numbers {
+                    spans.push(Span::styled(
+                        format!("{:>width$} ", index + 1, width = gutter_width),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                spans.extend(highlight_matches(
+                    line,
+                    base,
+                    &search.query,
+                    current_match == Some(index),
+                ));
+
                 styled_lines.push(Line::from(spans));
             }
 
@@ -281,10 +406,12 @@ impl PanePreviewWidget {
             )
         };
 
-        let border_color = if state.preview_scroll > 0 {
+        let border_color = if state.is_preview_focused() {
+            state.theme.accent
+        } else if state.preview_scroll > 0 {
             Color::Yellow
         } else {
-            Color::Gray
+            state.theme.border
         };
 
         let block = Block::default()
@@ -293,10 +420,109 @@ impl PanePreviewWidget {
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(border_color));
 
-        let paragraph = Paragraph::new(lines)
-            .block(block)
-            .wrap(Wrap { trim: false });
+        let mut paragraph = Paragraph::new(lines).block(block);
+        if state.preview_wrap {
+            paragraph = paragraph.wrap(Wrap { trim: false });
+        }
 
         frame.render_widget(paragraph, area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_cache_hit_on_identical_content() {
+        let mut cache = SummaryCache::default();
+        let content = "✽ Reading files (3s)\n☐ write tests";
+        let mut parses = 0;
+
+        for _ in 0..3 {
+            cache.refresh_with("main:0.0", content, |c| {
+                parses += 1;
+                ClaudeCodeSummary::parse(c)
+            });
+        }
+
+        assert_eq!(parses, 1);
+        let summary = cache.get("main:0.0", content).unwrap();
+        assert_eq!(summary.current_activity.as_deref(), Some("Reading files"));
+        assert_eq!(summary.todos.len(), 1);
+    }
+
+    #[test]
+    fn test_summary_cache_reparses_changed_content() {
+        let mut cache = SummaryCache::default();
+        cache.refresh("main:0.0", "✽ Reading files");
+        assert!(cache.get("main:0.0", "✽ Writing files").is_none());
+
+        cache.refresh("main:0.0", "✽ Writing files");
+        let summary = cache.get("main:0.0", "✽ Writing files").unwrap();
+        assert_eq!(summary.current_activity.as_deref(), Some("Writing files"));
+    }
+
+    #[test]
+    fn test_summary_cache_retain_targets() {
+        let mut cache = SummaryCache::default();
+        cache.refresh("main:0.0", "a");
+        cache.refresh("main:0.1", "b");
+        cache.retain_targets(["main:0.1"].into_iter());
+        assert!(cache.get("main:0.0", "a").is_none());
+        assert!(cache.get("main:0.1", "b").is_some());
+    }
+
+    #[test]
+    fn test_detailed_line_numbers_and_search_title() {
+        use crate::agents::MonitoredAgent;
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut state = AppState::new();
+        let mut agent = MonitoredAgent::for_test("main:0.0");
+        agent.last_content = (1..=12)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        state.agents.root_agents = vec![agent];
+        state.preview_line_numbers = true;
+        state.preview_search.query = "line 1".to_string();
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        terminal
+            .draw(|frame| PanePreviewWidget::render_detailed(frame, frame.area(), &state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let rendered: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+
+        // "line 1", "line 10", "line 11" and "line 12" match
+        assert!(rendered.contains("/line 1 [1/4]"));
+        // The gutter is as wide as the largest line number
+        assert!(rendered.contains("12 line 12"));
+        assert!(rendered.contains(" 8 line 8"));
+    }
+
+    #[test]
+    fn test_max_hscroll_stops_at_widest_visible_line() {
+        use crate::agents::MonitoredAgent;
+
+        let mut agent = MonitoredAgent::for_test("main:0.0");
+        // The 60-column line is scrolled out of a 3-row preview
+        agent.last_content = format!("{}\nshort\n{}\nend", "x".repeat(60), "y".repeat(30));
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![agent];
+        let area = Rect::new(0, 0, 22, 5);
+
+        // 20 columns inside the border, so the 30-column line needs 10
+        assert_eq!(PanePreviewWidget::max_hscroll(area, &state), 10);
+        state.preview_line_numbers = true;
+        assert_eq!(PanePreviewWidget::max_hscroll(area, &state), 12);
+        state.preview_scroll = 1;
+        assert_eq!(PanePreviewWidget::max_hscroll(area, &state), 42);
+        // Not drawn (terminal too small): nothing to scroll to
+        assert_eq!(PanePreviewWidget::max_hscroll(Rect::default(), &state), 0);
+
+        state.agents.root_agents.clear();
+        assert_eq!(PanePreviewWidget::max_hscroll(area, &state), 0);
+    }
+}
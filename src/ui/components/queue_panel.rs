@@ -11,25 +11,54 @@ use ratatui::{
 
 pub struct QueuePanelWidget;
 
+/// Highest and lowest queue priorities; 1 runs first
+const TOP_PRIORITY: u8 = 1;
+const BOTTOM_PRIORITY: u8 = 5;
+
+/// Tasks the panel lists, in order: everything not finished, plus tasks that
+/// finished or failed within the last hour
+pub fn visible_tasks(tasks: &[QueueTask]) -> Vec<&QueueTask> {
+    tasks
+        .iter()
+        .filter(|t| {
+            if t.status == "done" || t.status == "failed" {
+                // Keep if completed_at is within the last hour
+                t.completed_at
+                    .as_ref()
+                    .and_then(|ts| NaiveDateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S").ok())
+                    .map(|dt| Utc::now().naive_utc().signed_duration_since(dt).num_hours() < 1)
+                    .unwrap_or(false)
+            } else {
+                true // Always show running/pending/blocked
+            }
+        })
+        .collect()
+}
+
+/// The selected task while the queue panel is focused
+pub fn selected_task(state: &AppState) -> Option<&QueueTask> {
+    visible_tasks(&state.queue_tasks)
+        .get(state.queue_selected)
+        .copied()
+}
+
+/// `priority` moved one step toward running sooner (`raise`) or later, or None
+/// when it is already at that end
+pub fn bumped_priority(priority: u8, raise: bool) -> Option<u8> {
+    let current = priority.clamp(TOP_PRIORITY, BOTTOM_PRIORITY);
+    let bumped = if raise {
+        current.saturating_sub(1).max(TOP_PRIORITY)
+    } else {
+        (current + 1).min(BOTTOM_PRIORITY)
+    };
+    (bumped != priority).then_some(bumped)
+}
+
 impl QueuePanelWidget {
     pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         // Filter out stale completed/failed tasks (older than 1 hour)
-        let tasks: Vec<&QueueTask> = state
-            .queue_tasks
-            .iter()
-            .filter(|t| {
-                if t.status == "done" || t.status == "failed" {
-                    // Keep if completed_at is within the last hour
-                    t.completed_at
-                        .as_ref()
-                        .and_then(|ts| NaiveDateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S").ok())
-                        .map(|dt| Utc::now().naive_utc().signed_duration_since(dt).num_hours() < 1)
-                        .unwrap_or(false)
-                } else {
-                    true // Always show running/pending/blocked
-                }
-            })
-            .collect();
+        let tasks = visible_tasks(&state.queue_tasks);
+        let focused = state.is_queue_focused();
 
         let pending = tasks.iter().filter(|t| t.status == "pending").count();
         let running = tasks.iter().filter(|t| t.status == "running").count();
@@ -51,7 +80,11 @@ impl QueuePanelWidget {
             .title(title)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Magenta));
+            .border_style(if focused {
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Magenta)
+            });
 
         if tasks.is_empty() {
             let msg = if state.hub_connected {
@@ -71,8 +104,15 @@ impl QueuePanelWidget {
         let max_lines = (area.height as usize).saturating_sub(2); // borders
         let mut lines: Vec<Line> = Vec::new();
 
-        for (i, &task) in tasks.iter().enumerate() {
-            if i >= max_lines {
+        // Scroll so the selected task stays in view
+        let skip = if focused {
+            state.queue_selected.saturating_sub(max_lines.saturating_sub(1))
+        } else {
+            0
+        };
+
+        for (i, &task) in tasks.iter().enumerate().skip(skip) {
+            if i - skip >= max_lines {
                 break;
             }
 
@@ -93,13 +133,7 @@ impl QueuePanelWidget {
             };
 
             // Truncate project and task to fit
-            let project = truncate(
-                &task
-                    .project
-                    .replace("/tmp/", "")
-                    .replace("/Users/pran/Projects/", ""),
-                15,
-            );
+            let project = truncate(state.project_prefixes.shorten(&task.project), 15);
             let task_name = truncate(&task.task, 30);
 
             let spans = vec![
@@ -157,7 +191,11 @@ impl QueuePanelWidget {
                 }
             }
 
-            lines.push(Line::from(all_spans));
+            let mut line = Line::from(all_spans);
+            if focused && i == state.queue_selected {
+                line = line.style(Style::default().bg(Color::DarkGray));
+            }
+            lines.push(line);
         }
 
         let paragraph = Paragraph::new(lines).block(block);
@@ -172,3 +210,19 @@ fn truncate(s: &str, max: usize) -> String {
         format!("{}…", s.chars().take(max - 1).collect::<String>())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bumped_priority_stays_in_range() {
+        assert_eq!(bumped_priority(3, true), Some(2));
+        assert_eq!(bumped_priority(3, false), Some(4));
+        assert_eq!(bumped_priority(1, true), None);
+        assert_eq!(bumped_priority(5, false), None);
+        // Out-of-range priorities from the hub move back into range
+        assert_eq!(bumped_priority(0, false), Some(2));
+        assert_eq!(bumped_priority(9, true), Some(4));
+    }
+}
@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::ui::{Layout, PendingSend};
+
+/// Confirmation popup showing expanded input before it is sent
+pub struct SendPreviewWidget;
+
+impl SendPreviewWidget {
+    pub fn render(frame: &mut Frame, area: Rect, pending: &PendingSend) {
+        let popup_area = Layout::centered_popup(area, 60, 40);
+
+        // Clear the background
+        frame.render_widget(Clear, popup_area);
+
+        let key_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let desc_style = Style::default().fg(Color::DarkGray);
+
+        let mut lines: Vec<Line> = pending
+            .text
+            .lines()
+            .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::White))))
+            .collect();
+        lines.push(Line::from(vec![]));
+        lines.push(Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::styled(": send  ", desc_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": cancel", desc_style),
+        ]));
+
+        let block = Block::default()
+            .title(format!(" Send to {}? ", pending.label))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .style(Style::default().bg(Color::Black));
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(paragraph, popup_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{AgentType, MonitoredAgent};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(pending: &PendingSend) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal
+            .draw(|frame| SendPreviewWidget::render(frame, frame.area(), pending))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn test_popup_shows_expanded_text() {
+        let agent = MonitoredAgent {
+            window_name: "api".to_string(),
+            path: "/srv/api".to_string(),
+            agent_type: AgentType::CodexCli,
+            ..MonitoredAgent::for_test("review:2.0")
+        };
+        let pending =
+            PendingSend::for_agent(&agent, "/srv/api".to_string(), "check {window_name} in {path}", false)
+                .unwrap();
+
+        let screen = render_to_string(&pending);
+        assert!(screen.contains("Send to /srv/api?"));
+        assert!(screen.contains("check api in /srv/api"));
+        assert!(!screen.contains("{path}"));
+    }
+}
@@ -0,0 +1,147 @@
+use ratatui::{
+    layout::{Constraint, Direction, Rect},
+    style::{Color, Style},
+    widgets::{Block, BorderType, Borders, Paragraph, Sparkline},
+    Frame,
+};
+
+use crate::app::AppState;
+
+/// Toggleable panel with CPU, memory and load history side by side
+pub struct StatsGraphWidget;
+
+impl StatsGraphWidget {
+    pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+        let history = &state.stats_history;
+        let block = Block::default()
+            .title(format!(" System · last {} samples ", history.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(state.theme.border));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if history.is_empty() {
+            frame.render_widget(
+                Paragraph::new("  Collecting samples...")
+                    .style(Style::default().fg(Color::DarkGray)),
+                inner,
+            );
+            return;
+        }
+
+        let columns = ratatui::layout::Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+            ])
+            .split(inner);
+        let latest = history[history.len() - 1];
+
+        // Percentages are drawn against a fixed 0-100 scale; load scales to its own peak
+        let cpu: Vec<u64> = history.iter().map(|s| s.cpu.round() as u64).collect();
+        let memory: Vec<u64> = history
+            .iter()
+            .map(|s| s.memory_percent.round() as u64)
+            .collect();
+        let load: Vec<u64> = history.iter().map(|s| (s.load * 100.0) as u64).collect();
+
+        render_graph(
+            frame,
+            columns[0],
+            format!(" CPU {:.0}% ", latest.cpu),
+            &cpu,
+            Some(100),
+            Color::Cyan,
+        );
+        render_graph(
+            frame,
+            columns[1],
+            format!(" MEM {:.0}% ", latest.memory_percent),
+            &memory,
+            Some(100),
+            Color::Magenta,
+        );
+        render_graph(
+            frame,
+            columns[2],
+            format!(" LOAD {:.2} ", latest.load),
+            &load,
+            None,
+            Color::Yellow,
+        );
+    }
+}
+
+/// Draws one sparkline, keeping only the newest samples that fit the width
+fn render_graph(
+    frame: &mut Frame,
+    area: Rect,
+    title: String,
+    data: &[u64],
+    max: Option<u64>,
+    color: Color,
+) {
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let width = block.inner(area).width as usize;
+    let visible = data[data.len().saturating_sub(width)..].to_vec();
+    let mut sparkline = Sparkline::default()
+        .block(block)
+        .data(visible)
+        .style(Style::default().fg(color));
+    if let Some(max) = max {
+        sparkline = sparkline.max(max);
+    }
+    frame.render_widget(sparkline, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::StatsSample;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(state: &AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(90, 8)).unwrap();
+        terminal
+            .draw(|frame| StatsGraphWidget::render(frame, frame.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_render_shows_latest_values() {
+        let mut state = AppState::new();
+        state.stats_history = (0..60)
+            .map(|i| StatsSample {
+                cpu: i as f32,
+                memory_percent: 40.0,
+                load: 1.5,
+            })
+            .collect();
+
+        let rendered = render_to_string(&state);
+        assert!(rendered.contains("last 60 samples"));
+        assert!(rendered.contains("CPU 59%"));
+        assert!(rendered.contains("MEM 40%"));
+        assert!(rendered.contains("LOAD 1.50"));
+        assert!(rendered.contains('█'));
+    }
+
+    #[test]
+    fn test_render_without_samples() {
+        let state = AppState::new();
+        assert!(render_to_string(&state).contains("Collecting samples"));
+    }
+}
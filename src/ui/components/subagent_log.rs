@@ -1,12 +1,12 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, List, ListItem},
     Frame,
 };
 
-use crate::agents::SubagentStatus;
+use crate::agents::{Subagent, SubagentStatus};
 use crate::app::AppState;
 
 /// Widget for displaying subagent activity log
@@ -35,39 +35,66 @@ impl SubagentLogWidget {
                     Style::default().fg(Color::DarkGray),
                 )]))]
             } else {
-                agent
-                    .subagents
-                    .iter()
-                    .map(|subagent| {
-                        let (indicator, style) = match subagent.status {
-                            SubagentStatus::Running => ("▶", Style::default().fg(Color::Cyan)),
-                            SubagentStatus::Completed => ("✓", Style::default().fg(Color::Green)),
-                            SubagentStatus::Failed => ("✗", Style::default().fg(Color::Red)),
-                            SubagentStatus::Unknown => ("?", Style::default().fg(Color::DarkGray)),
-                        };
-
-                        let duration = subagent.duration_str();
-
-                        let line = Line::from(vec![
-                            Span::raw("  "),
-                            Span::styled(indicator, style),
-                            Span::raw(" "),
-                            Span::styled(
-                                subagent.subagent_type.display_name(),
-                                Style::default().fg(Color::White),
-                            ),
-                            Span::raw("  "),
-                            Span::styled(&subagent.description, Style::default().fg(Color::Gray)),
-                            Span::raw("  "),
-                            Span::styled(
-                                format!("[{}]", duration),
-                                Style::default().fg(Color::DarkGray),
-                            ),
-                        ]);
-
-                        ListItem::new(line)
-                    })
-                    .collect()
+                // Oldest first
+                let mut subagents: Vec<&Subagent> = agent.subagents.iter().collect();
+                subagents.sort_by_key(|s| s.started_at);
+
+                // Rows left after borders and the count header; keep the newest in view
+                let rows = area.height.saturating_sub(3) as usize;
+                let start = visible_start(subagents.len(), rows);
+
+                let (running, completed, failed) = status_counts(&subagents);
+                let mut header = vec![
+                    Span::styled(
+                        format!("  ▶ {} running", running),
+                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("  ✓ {} done", completed),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::styled(format!("  ✗ {} failed", failed), Style::default().fg(Color::Red)),
+                ];
+                if start > 0 {
+                    header.push(Span::styled(
+                        format!("  ↑ {} earlier", start),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                let mut items = vec![ListItem::new(Line::from(header))];
+
+                items.extend(subagents[start..].iter().map(|subagent| {
+                    let (indicator, style) = match subagent.status {
+                        SubagentStatus::Running => {
+                            (state.spinner_frame(), Style::default().fg(Color::Cyan))
+                        }
+                        SubagentStatus::Completed => ("✓", Style::default().fg(Color::Green)),
+                        SubagentStatus::Failed => ("✗", Style::default().fg(Color::Red)),
+                        SubagentStatus::Unknown => ("?", Style::default().fg(Color::DarkGray)),
+                    };
+
+                    let duration = subagent.duration_str();
+
+                    let line = Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(indicator, style),
+                        Span::raw(" "),
+                        Span::styled(
+                            subagent.subagent_type.display_name(),
+                            Style::default().fg(Color::White),
+                        ),
+                        Span::raw("  "),
+                        Span::styled(&subagent.description, Style::default().fg(Color::Gray)),
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("[{}]", duration),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]);
+
+                    ListItem::new(line)
+                }));
+                items
             }
         } else {
             vec![ListItem::new(Line::from(vec![Span::styled(
@@ -80,3 +107,48 @@ impl SubagentLogWidget {
         frame.render_widget(list, area);
     }
 }
+
+/// Counts (running, completed, failed) subagents
+fn status_counts(subagents: &[&Subagent]) -> (usize, usize, usize) {
+    subagents
+        .iter()
+        .fold((0, 0, 0), |(r, c, f), s| match s.status {
+            SubagentStatus::Running => (r + 1, c, f),
+            SubagentStatus::Completed => (r, c + 1, f),
+            SubagentStatus::Failed => (r, c, f + 1),
+            SubagentStatus::Unknown => (r, c, f),
+        })
+}
+
+/// First subagent to show so the last `rows` entries are visible
+fn visible_start(total: usize, rows: usize) -> usize {
+    total.saturating_sub(rows.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::SubagentType;
+
+    #[test]
+    fn test_status_counts() {
+        let subagents = [
+            Subagent::new("1".into(), SubagentType::Explore, "a".into()),
+            Subagent::new("2".into(), SubagentType::Plan, "b".into())
+                .with_status(SubagentStatus::Completed),
+            Subagent::new("3".into(), SubagentType::Bash, "c".into())
+                .with_status(SubagentStatus::Failed),
+            Subagent::new("4".into(), SubagentType::General, "d".into())
+                .with_status(SubagentStatus::Completed),
+        ];
+        let refs: Vec<&Subagent> = subagents.iter().collect();
+        assert_eq!(status_counts(&refs), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_visible_start_keeps_newest() {
+        assert_eq!(visible_start(3, 10), 0);
+        assert_eq!(visible_start(12, 10), 2);
+        assert_eq!(visible_start(5, 0), 4);
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::{Duration, Instant};
+
+/// How long a destructive action stays armed after the first key press
+pub const CONFIRM_WINDOW: Duration = Duration::from_secs(3);
+
+/// Two-step confirmation for destructive actions (press the key twice)
+#[derive(Debug, Default)]
+pub struct ConfirmGuard {
+    /// Armed action key and when it was armed
+    pending: Option<(String, Instant)>,
+}
+
+impl ConfirmGuard {
+    /// Returns true if `key` was armed within the confirmation window and disarms it.
+    /// Otherwise arms `key` and returns false.
+    pub fn confirm(&mut self, key: &str) -> bool {
+        self.confirm_at(key, Instant::now())
+    }
+
+    fn confirm_at(&mut self, key: &str, now: Instant) -> bool {
+        let confirmed = matches!(
+            &self.pending,
+            Some((armed, at)) if armed == key && now.duration_since(*at) < CONFIRM_WINDOW
+        );
+        self.pending = if confirmed {
+            None
+        } else {
+            Some((key.to_string(), now))
+        };
+        confirmed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_press_confirms() {
+        let mut guard = ConfirmGuard::default();
+        let now = Instant::now();
+        assert!(!guard.confirm_at("kill main:0.0", now));
+        assert!(guard.confirm_at("kill main:0.0", now + Duration::from_secs(1)));
+        // Disarmed after confirming
+        assert!(!guard.confirm_at("kill main:0.0", now + Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_expired_or_different_key_rearms() {
+        let mut guard = ConfirmGuard::default();
+        let now = Instant::now();
+        assert!(!guard.confirm_at("kill main:0.0", now));
+        assert!(!guard.confirm_at("kill main:0.1", now + Duration::from_secs(1)));
+        assert!(!guard.confirm_at("kill main:0.1", now + Duration::from_secs(5)));
+        assert!(guard.confirm_at("kill main:0.1", now + Duration::from_secs(6)));
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+use crate::agents::AgentStatus;
+use crate::monitor::StatusTransition;
+
+/// Entries kept in the event log; the oldest are dropped beyond this
+pub const EVENT_LOG_LEN: usize = 500;
+
+/// Entries moved per `[` / `]` press
+pub const EVENT_LOG_SCROLL_STEP: usize = 5;
+
+/// Longest excerpt of sent input recorded in the log
+const SENT_EXCERPT_CHARS: usize = 40;
+
+/// One line of the event log
+#[derive(Debug, Clone)]
+pub struct LogEvent {
+    pub at: DateTime<Local>,
+    /// Tmux target the event is about
+    pub target: String,
+    pub text: String,
+}
+
+/// Status changes and the user's own approvals, rejections and sends during this
+/// TUI session, newest last. Unlike the hub's activity feed it lives only in memory.
+#[derive(Debug, Clone, Default)]
+pub struct EventLog {
+    entries: VecDeque<LogEvent>,
+    /// Entries scrolled up from the newest; 0 follows new entries
+    scroll: usize,
+}
+
+impl EventLog {
+    /// Appends an event stamped now
+    pub fn push(&mut self, target: &str, text: String) {
+        self.push_at(Local::now(), target, text);
+    }
+
+    fn push_at(&mut self, at: DateTime<Local>, target: &str, text: String) {
+        if self.entries.len() == EVENT_LOG_LEN {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEvent {
+            at,
+            target: target.to_string(),
+            text,
+        });
+        // Keep a scrolled-up view on the same entries
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.entries.len() - 1);
+        }
+    }
+
+    /// Records a status change reported by the monitor, e.g. "→ AwaitingApproval(Shell)"
+    pub fn record_transition(&mut self, transition: &StatusTransition) {
+        self.push_at(
+            transition.at.with_timezone(&Local),
+            &transition.target,
+            format!("→ {}", status_name(&transition.to)),
+        );
+    }
+
+    /// Records the same user action, e.g. "approved", for each of `targets`
+    pub fn record_action(&mut self, targets: &[String], text: &str) {
+        for target in targets {
+            self.push(target, text.to_string());
+        }
+    }
+
+    pub fn entries(&self) -> &VecDeque<LogEvent> {
+        &self.entries
+    }
+
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    /// Scrolls `lines` entries back in time
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Scrolls `lines` entries towards the newest
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+}
+
+/// "sent \"…\"" with the first line of `input`, shortened
+pub fn sent_text(input: &str) -> String {
+    let first_line = input.lines().next().unwrap_or("");
+    let mut excerpt: String = first_line.chars().take(SENT_EXCERPT_CHARS).collect();
+    if first_line.chars().count() > SENT_EXCERPT_CHARS || input.lines().nth(1).is_some() {
+        excerpt.push('…');
+    }
+    format!("sent \"{}\"", excerpt)
+}
+
+/// Compact status name for the log
+fn status_name(status: &AgentStatus) -> String {
+    match status {
+        AgentStatus::Idle => "Idle".to_string(),
+        AgentStatus::Processing { .. } => "Processing".to_string(),
+        AgentStatus::AwaitingApproval { approval_type, .. } => {
+            format!("AwaitingApproval({})", approval_type.short_desc())
+        }
+        AgentStatus::Error { .. } => "Error".to_string(),
+        AgentStatus::Unknown => "Unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ApprovalType;
+    use chrono::Utc;
+
+    #[test]
+    fn test_record_transition_text() {
+        let mut log = EventLog::default();
+        log.record_transition(&StatusTransition {
+            target: "main:0.1".to_string(),
+            from: AgentStatus::Idle,
+            to: AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "rm -rf target".to_string(),
+            },
+            at: Utc::now(),
+        });
+        log.record_action(&["main:0.1".to_string()], "approved");
+
+        let texts: Vec<&str> = log.entries().iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["→ AwaitingApproval(Shell)", "approved"]);
+        assert_eq!(log.entries()[0].target, "main:0.1");
+    }
+
+    #[test]
+    fn test_bounded_and_scroll_stays_on_entries() {
+        let mut log = EventLog::default();
+        for i in 0..EVENT_LOG_LEN + 3 {
+            log.push("main:0.0", format!("event {}", i));
+        }
+        assert_eq!(log.entries().len(), EVENT_LOG_LEN);
+        assert_eq!(log.entries()[0].text, "event 3");
+
+        log.scroll_up(5);
+        log.push("main:0.0", "newer".to_string());
+        assert_eq!(log.scroll(), 6);
+        log.scroll_down(10);
+        assert_eq!(log.scroll(), 0);
+        log.scroll_up(usize::MAX / 2);
+        assert_eq!(log.scroll(), EVENT_LOG_LEN - 1);
+    }
+
+    #[test]
+    fn test_sent_text_excerpt() {
+        assert_eq!(sent_text("run the tests"), "sent \"run the tests\"");
+        assert_eq!(sent_text("first\nsecond"), "sent \"first…\"");
+        let long = "x".repeat(60);
+        assert_eq!(sent_text(&long), format!("sent \"{}…\"", "x".repeat(40)));
+    }
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+
+use crate::agents::MonitoredAgent;
+
+/// Directory under `$HOME` that pane exports are written to
+const EXPORT_DIR: &str = "agentos-tui-exports";
+
+/// `~/agentos-tui-exports`, or a relative directory if `$HOME` is unset
+pub fn export_dir() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(EXPORT_DIR)
+}
+
+/// File name for an export of `agent` taken at `at`: `<session>-<window>-<pane>-<timestamp>.txt`
+pub fn export_file_name(agent: &MonitoredAgent, at: DateTime<Local>) -> String {
+    // Session names may contain anything tmux allows, including '/'
+    let session: String = agent
+        .session
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!(
+        "{}-{}-{}-{}.txt",
+        session,
+        agent.window,
+        agent.pane,
+        at.format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Writes `content` to a new export file for `agent` in `dir`, creating `dir` if
+/// needed, and returns the file's path
+pub fn write_export(dir: &Path, agent: &MonitoredAgent, content: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(export_file_name(agent, Local::now()));
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn agent(session: &str) -> MonitoredAgent {
+        MonitoredAgent::for_test(&format!("{}:2.1", session))
+    }
+
+    #[test]
+    fn test_export_file_name() {
+        let at = Local.with_ymd_and_hms(2026, 3, 4, 5, 6, 7).unwrap();
+        assert_eq!(
+            export_file_name(&agent("main"), at),
+            "main-2-1-20260304-050607.txt"
+        );
+        assert_eq!(
+            export_file_name(&agent("a/b c"), at),
+            "a_b_c-2-1-20260304-050607.txt"
+        );
+    }
+
+    #[test]
+    fn test_write_export_creates_dir() {
+        let dir = std::env::temp_dir()
+            .join(format!("tmuxcc-export-test-{}", std::process::id()))
+            .join("nested");
+        let path = write_export(&dir, &agent("main"), "line 1\nline 2\n").unwrap();
+        assert!(path.starts_with(&dir));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "line 1\nline 2\n");
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,556 @@
+use std::mem::discriminant;
+
+use crate::agents::{AgentStatus, MonitoredAgent, Subagent};
+use crate::app::AppState;
+
+/// Returns true if `agent` matches the sidebar filter (case-insensitive substring
+/// of the abbreviated path, session or window name). An empty filter matches all.
+pub fn agent_matches(agent: &MonitoredAgent, filter: &str) -> bool {
+    if filter.is_empty() {
+        return true;
+    }
+    let filter = filter.to_lowercase();
+    [
+        agent.abbreviated_path(),
+        agent.session.clone(),
+        agent.window_name.clone(),
+    ]
+    .iter()
+    .any(|field| field.to_lowercase().contains(&filter))
+}
+
+/// Indices into `root_agents` of the agents matching the current filter
+fn matching_indices(state: &AppState) -> Vec<usize> {
+    let filter = state.agent_filter.as_deref().unwrap_or("");
+    state
+        .agents
+        .root_agents
+        .iter()
+        .enumerate()
+        .filter(|(_, agent)| agent_matches(agent, filter))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Indices into `root_agents` of the agents listed in the sidebar tree: the filter
+/// matches, cut to `max_display_agents` if set. Includes agents inside folded
+/// sessions and windows, whose headers still show.
+pub fn listed_indices(state: &AppState) -> Vec<usize> {
+    let mut indices = matching_indices(state);
+    if let Some(max) = state.max_display_agents {
+        indices.truncate(max);
+    }
+    indices
+}
+
+/// Indices of the agents shown in the sidebar: the listed ones outside folded
+/// sessions and windows. Navigation and "all visible" actions use these.
+pub fn visible_indices(state: &AppState) -> Vec<usize> {
+    let mut indices = listed_indices(state);
+    indices.retain(|&idx| !is_folded(state, &state.agents.root_agents[idx]));
+    indices
+}
+
+/// The agent under the cursor, or None when the filter or a fold hides it (say
+/// nothing matches the filter). Actions go through this, so they never reach an
+/// agent that isn't on screen.
+pub fn cursor_agent(state: &AppState) -> Option<&MonitoredAgent> {
+    state
+        .selected_agent()
+        .filter(|_| visible_indices(state).contains(&state.selected_index))
+}
+
+/// Agents an action applies to: the multi-selection, else the agent under the
+/// cursor, leaving out any the sidebar hides
+pub fn operation_indices(state: &AppState) -> Vec<usize> {
+    let visible = visible_indices(state);
+    state
+        .get_operation_indices()
+        .into_iter()
+        .filter(|idx| visible.contains(idx))
+        .collect()
+}
+
+/// Fold key of a session header
+pub fn session_fold_key(session: &str) -> String {
+    session.to_string()
+}
+
+/// Fold key of a window header. tmux doesn't allow ':' in session names, so
+/// this can't collide with a session's key.
+pub fn window_fold_key(session: &str, window: u32) -> String {
+    format!("{}:{}", session, window)
+}
+
+/// Targets of every agent in the selected agent's tmux window, the selected one
+/// included, in tree order; empty when nothing is selected
+pub fn window_targets(state: &AppState) -> Vec<String> {
+    let Some(selected) = cursor_agent(state) else {
+        return Vec::new();
+    };
+    state
+        .agents
+        .root_agents
+        .iter()
+        .filter(|a| a.session == selected.session && a.window == selected.window)
+        .map(|a| a.target.clone())
+        .collect()
+}
+
+/// Returns true if `agent`'s session or window is folded in the sidebar
+pub fn is_folded(state: &AppState, agent: &MonitoredAgent) -> bool {
+    state.folded_groups.contains(&session_fold_key(&agent.session))
+        || state
+            .folded_groups
+            .contains(&window_fold_key(&agent.session, agent.window))
+}
+
+/// Folds or unfolds the session or window with `key`, keeping the cursor on a
+/// visible agent if any is left (see `clamp_to_filter`)
+pub fn toggle_fold(state: &mut AppState, key: String) {
+    if !state.folded_groups.remove(&key) {
+        state.folded_groups.insert(key);
+    }
+    clamp_to_filter(state);
+}
+
+/// Number of matching agents left out by `max_display_agents`
+pub fn hidden_count(state: &AppState) -> usize {
+    let matching = matching_indices(state).len();
+    state
+        .max_display_agents
+        .map_or(0, |max| matching.saturating_sub(max))
+}
+
+/// Returns true if `agent`'s subagents are listed under it in the tree.
+/// Toggling an agent flips the configured default for that agent only.
+pub fn subagents_expanded(state: &AppState, agent: &MonitoredAgent) -> bool {
+    state.show_subagents_in_tree != state.toggled_subagents.contains(&agent.target)
+}
+
+/// Subagents listed under `agent` in the tree (empty while collapsed)
+pub fn visible_subagents<'a>(state: &AppState, agent: &'a MonitoredAgent) -> &'a [Subagent] {
+    if subagents_expanded(state, agent) {
+        &agent.subagents
+    } else {
+        &[]
+    }
+}
+
+/// Expands or collapses the subagents of the agent under the cursor
+pub fn toggle_subagents(state: &mut AppState) {
+    if let Some(target) = cursor_agent(state).map(|a| a.target.clone()) {
+        if !state.toggled_subagents.remove(&target) {
+            state.toggled_subagents.insert(target);
+        }
+    }
+}
+
+/// Marks the selected agents (or the one under the cursor) as reviewed, or
+/// unmarks them if they all already are. Reviewed agents are dimmed and left out
+/// of the header's pending count until their status changes.
+pub fn toggle_acknowledged(state: &mut AppState) {
+    let targets: Vec<String> = operation_indices(state)
+        .into_iter()
+        .filter_map(|idx| state.agents.get_agent(idx))
+        .map(|agent| agent.target.clone())
+        .collect();
+    if targets.iter().all(|t| state.acknowledged.contains(t)) {
+        for target in &targets {
+            state.acknowledged.remove(target);
+        }
+    } else {
+        state.acknowledged.extend(targets);
+    }
+}
+
+/// Drops the reviewed mark of agents that are gone or whose status changed
+/// since `previous`: a different kind of status, or a different approval prompt
+/// (type or details). Processing activity text changing keeps the mark.
+pub fn retain_acknowledged(state: &mut AppState, previous: &[MonitoredAgent]) {
+    let agents = &state.agents.root_agents;
+    state.acknowledged.retain(|target| {
+        let status = |agents: &[MonitoredAgent]| {
+            agents
+                .iter()
+                .find(|a| &a.target == target)
+                .map(|a| a.status.clone())
+        };
+        match (status(previous), status(agents)) {
+            (Some(before), Some(now)) => same_for_review(&before, &now),
+            _ => false,
+        }
+    });
+}
+
+fn same_for_review(before: &AgentStatus, now: &AgentStatus) -> bool {
+    match (before, now) {
+        (AgentStatus::AwaitingApproval { .. }, AgentStatus::AwaitingApproval { .. }) => {
+            before == now
+        }
+        _ => discriminant(before) == discriminant(now),
+    }
+}
+
+/// Agents needing attention that haven't been marked reviewed
+pub fn unacknowledged_pending(state: &AppState) -> usize {
+    state
+        .agents
+        .root_agents
+        .iter()
+        .filter(|a| a.status.needs_attention() && !state.acknowledged.contains(&a.target))
+        .count()
+}
+
+/// Moves the cursor to the next visible agent, wrapping around
+pub fn select_next_visible(state: &mut AppState) {
+    let visible = visible_indices(state);
+    if let Some(&next) = visible
+        .iter()
+        .find(|&&idx| idx > state.selected_index)
+        .or(visible.first())
+    {
+        state.select_agent(next);
+    }
+}
+
+/// Moves the cursor to the previous visible agent, wrapping around
+pub fn select_prev_visible(state: &mut AppState) {
+    let visible = visible_indices(state);
+    if let Some(&prev) = visible
+        .iter()
+        .rev()
+        .find(|&&idx| idx < state.selected_index)
+        .or(visible.last())
+    {
+        state.select_agent(prev);
+    }
+}
+
+/// Moves the cursor to the next (or previous) listed agent needing attention,
+/// wrapping around, and unfolds its session and window so it shows. Returns
+/// false if no listed agent needs attention.
+pub fn select_attention(state: &mut AppState, forward: bool) -> bool {
+    let pending: Vec<usize> = listed_indices(state)
+        .into_iter()
+        .filter(|&idx| state.agents.root_agents[idx].status.needs_attention())
+        .collect();
+    let current = state.selected_index;
+    let found = if forward {
+        pending.iter().find(|&&idx| idx > current).or(pending.first())
+    } else {
+        pending.iter().rev().find(|&&idx| idx < current).or(pending.last())
+    };
+    let Some(&idx) = found else {
+        return false;
+    };
+    let agent = &state.agents.root_agents[idx];
+    let session = session_fold_key(&agent.session);
+    let window = window_fold_key(&agent.session, agent.window);
+    state.folded_groups.remove(&session);
+    state.folded_groups.remove(&window);
+    state.select_agent(idx);
+    true
+}
+
+/// Keeps the cursor on a visible agent and drops hidden agents (filtered out or
+/// folded away) from the multi-selection. With nothing visible the cursor stays
+/// where it is and `cursor_agent` reports no selection.
+pub fn clamp_to_filter(state: &mut AppState) {
+    let visible = visible_indices(state);
+    if !visible.contains(&state.selected_index) {
+        if let Some(&first) = visible.first() {
+            state.select_agent(first);
+        }
+    }
+    state.selected_agents.retain(|idx| visible.contains(idx));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::SubagentType;
+
+    fn agent(session: &str, window_name: &str, path: &str) -> MonitoredAgent {
+        MonitoredAgent {
+            window_name: window_name.to_string(),
+            path: path.to_string(),
+            ..MonitoredAgent::for_test(&format!("{}:0.0", session))
+        }
+    }
+
+    fn state_with_filter(filter: &str) -> AppState {
+        let mut state = AppState::new();
+        state.agents.root_agents = vec![
+            agent("main", "api", "/home/user/backend"),
+            agent("main", "web", "/home/user/frontend"),
+            agent("ops", "deploy", "/srv/infra"),
+            agent("main", "docs", "/home/user/Backend-docs"),
+        ];
+        state.agent_filter = Some(filter.to_string());
+        state
+    }
+
+    #[test]
+    fn test_agent_matches_fields() {
+        let a = agent("main", "api", "/home/user/backend");
+        assert!(agent_matches(&a, "BACK"));
+        assert!(agent_matches(&a, "/h/u/"));
+        assert!(agent_matches(&a, "mai"));
+        assert!(agent_matches(&a, "api"));
+        assert!(agent_matches(&a, ""));
+        assert!(!agent_matches(&a, "frontend"));
+    }
+
+    #[test]
+    fn test_visible_indices() {
+        assert_eq!(visible_indices(&state_with_filter("backend")), vec![0, 3]);
+        assert_eq!(visible_indices(&state_with_filter("")), vec![0, 1, 2, 3]);
+        assert!(visible_indices(&state_with_filter("nope")).is_empty());
+    }
+
+    #[test]
+    fn test_navigation_skips_hidden_agents() {
+        let mut state = state_with_filter("backend");
+        state.selected_index = 0;
+        select_next_visible(&mut state);
+        assert_eq!(state.selected_index, 3);
+        select_next_visible(&mut state);
+        assert_eq!(state.selected_index, 0);
+        select_prev_visible(&mut state);
+        assert_eq!(state.selected_index, 3);
+    }
+
+    #[test]
+    fn test_clamp_to_filter() {
+        let mut state = state_with_filter("ops");
+        state.selected_index = 1;
+        state.selected_agents.extend([1, 2]);
+        clamp_to_filter(&mut state);
+        assert_eq!(state.selected_index, 2);
+        assert_eq!(state.selected_agents.len(), 1);
+        assert!(state.selected_agents.contains(&2));
+        assert_eq!(cursor_agent(&state).unwrap().session, "ops");
+        assert_eq!(operation_indices(&state), vec![2]);
+    }
+
+    #[test]
+    fn test_no_match_leaves_nothing_to_act_on() {
+        let mut state = state_with_filter("nothing-matches");
+        state.selected_index = 1;
+        state.selected_agents.insert(1);
+        clamp_to_filter(&mut state);
+
+        // The cursor index stays, but approve/reject/send must not reach agent 1
+        assert_eq!(state.selected_index, 1);
+        assert!(state.selected_agents.is_empty());
+        assert!(cursor_agent(&state).is_none());
+        assert!(operation_indices(&state).is_empty());
+        assert!(window_targets(&state).is_empty());
+
+        state.agent_filter = None;
+        assert_eq!(cursor_agent(&state).unwrap().window_name, "web");
+        assert_eq!(operation_indices(&state), vec![1]);
+    }
+
+    #[test]
+    fn test_display_cap() {
+        let mut state = state_with_filter("");
+        state.max_display_agents = Some(2);
+        assert_eq!(visible_indices(&state), vec![0, 1]);
+        assert_eq!(hidden_count(&state), 2);
+
+        // Filtering reaches agents beyond the cap
+        state.agent_filter = Some("infra".to_string());
+        assert_eq!(visible_indices(&state), vec![2]);
+        assert_eq!(hidden_count(&state), 0);
+
+        state.agent_filter = None;
+        state.max_display_agents = None;
+        assert_eq!(visible_indices(&state).len(), 4);
+        assert_eq!(hidden_count(&state), 0);
+    }
+
+    #[test]
+    fn test_folded_groups_hidden_from_navigation() {
+        let mut state = state_with_filter("");
+        state.agents.root_agents[2].window = 1;
+        state.selected_index = 0;
+        state.selected_agents.extend([0, 2]);
+
+        // Folding the cursor's session moves it out of the fold
+        toggle_fold(&mut state, session_fold_key("main"));
+        assert_eq!(visible_indices(&state), vec![2]);
+        assert_eq!(listed_indices(&state), vec![0, 1, 2, 3]);
+        assert_eq!(state.selected_index, 2);
+        assert_eq!(state.selected_agents.len(), 1);
+        select_next_visible(&mut state);
+        assert_eq!(state.selected_index, 2);
+
+        toggle_fold(&mut state, window_fold_key("ops", 1));
+        assert!(visible_indices(&state).is_empty());
+        // Everything folded: the cursor index is left on agent 2, but actions
+        // can't reach it
+        assert_eq!(state.selected_index, 2);
+        assert!(state.selected_agents.is_empty());
+        assert!(cursor_agent(&state).is_none());
+        assert!(operation_indices(&state).is_empty());
+        assert!(window_targets(&state).is_empty());
+
+        toggle_fold(&mut state, session_fold_key("main"));
+        assert_eq!(visible_indices(&state), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_window_targets() {
+        let mut state = state_with_filter("");
+        state.agent_filter = None;
+        for (i, agent) in state.agents.root_agents.iter_mut().enumerate() {
+            agent.target = format!("{}:{}.{}", agent.session, agent.window, i);
+        }
+        state.agents.root_agents[1].window = 1;
+
+        state.selected_index = 3;
+        assert_eq!(window_targets(&state), vec!["main:0.0", "main:0.3"]);
+        state.selected_index = 2;
+        assert_eq!(window_targets(&state), vec!["ops:0.2"]);
+    }
+
+    #[test]
+    fn test_acknowledged_left_out_of_pending() {
+        use crate::agents::{AgentStatus, ApprovalType};
+
+        let mut state = state_with_filter("");
+        state.agent_filter = None;
+        for (i, agent) in state.agents.root_agents.iter_mut().enumerate() {
+            agent.target = format!("main:0.{}", i);
+            agent.status = AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "ls".to_string(),
+            };
+        }
+        assert_eq!(unacknowledged_pending(&state), 4);
+
+        state.selected_index = 1;
+        toggle_acknowledged(&mut state);
+        assert!(state.acknowledged.contains("main:0.1"));
+        assert_eq!(unacknowledged_pending(&state), 3);
+
+        // A mixed selection is marked as a whole, then unmarked as a whole
+        state.selected_agents.extend([1, 2]);
+        toggle_acknowledged(&mut state);
+        assert_eq!(unacknowledged_pending(&state), 2);
+        toggle_acknowledged(&mut state);
+        assert!(state.acknowledged.is_empty());
+    }
+
+    #[test]
+    fn test_acknowledged_cleared_by_new_prompt() {
+        use crate::agents::{AgentStatus, ApprovalType};
+
+        let mut state = state_with_filter("");
+        for (i, agent) in state.agents.root_agents.iter_mut().enumerate() {
+            agent.target = format!("main:0.{}", i);
+            agent.status = AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "ls".to_string(),
+            };
+        }
+        state.acknowledged.extend((0..4).map(|i| format!("main:0.{}", i)));
+        let previous = state.agents.root_agents.clone();
+
+        // Same prompt stays reviewed; new details, a new type or a new status don't
+        state.agents.root_agents[1].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "rm -rf target".to_string(),
+        };
+        state.agents.root_agents[2].status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::FileEdit,
+            details: "ls".to_string(),
+        };
+        state.agents.root_agents[3].status = AgentStatus::Idle;
+        retain_acknowledged(&mut state, &previous);
+        assert_eq!(state.acknowledged.len(), 1);
+        assert!(state.acknowledged.contains("main:0.0"));
+
+        // Gone from the tree
+        state.agents.root_agents.remove(0);
+        retain_acknowledged(&mut state, &previous);
+        assert!(state.acknowledged.is_empty());
+    }
+
+    #[test]
+    fn test_select_attention_cycles_pending_agents() {
+        use crate::agents::{AgentStatus, ApprovalType};
+
+        let mut state = state_with_filter("");
+        state.agent_filter = None;
+        for idx in [1, 3] {
+            state.agents.root_agents[idx].status = AgentStatus::AwaitingApproval {
+                approval_type: ApprovalType::ShellCommand,
+                details: "ls".to_string(),
+            };
+        }
+        state.selected_index = 0;
+
+        assert!(select_attention(&mut state, true));
+        assert_eq!(state.selected_index, 1);
+        assert!(select_attention(&mut state, true));
+        assert_eq!(state.selected_index, 3);
+        // Wraps around both ways
+        assert!(select_attention(&mut state, true));
+        assert_eq!(state.selected_index, 1);
+        assert!(select_attention(&mut state, false));
+        assert_eq!(state.selected_index, 3);
+
+        // A pending agent in a folded session is unfolded to show it
+        toggle_fold(&mut state, session_fold_key("main"));
+        assert_eq!(state.selected_index, 2);
+        assert!(select_attention(&mut state, true));
+        assert_eq!(state.selected_index, 3);
+        assert!(visible_indices(&state).contains(&3));
+
+        for agent in state.agents.root_agents.iter_mut() {
+            agent.status = AgentStatus::Idle;
+        }
+        assert!(!select_attention(&mut state, true));
+        assert_eq!(state.selected_index, 3);
+    }
+
+    #[test]
+    fn test_subagents_collapsed_by_default() {
+        let mut state = state_with_filter("");
+        state.agents.root_agents[1].subagents = vec![
+            Subagent::new("s1".to_string(), SubagentType::Explore, "scan".to_string()),
+            Subagent::new("s2".to_string(), SubagentType::Plan, "plan".to_string()),
+        ];
+        state.show_subagents_in_tree = false;
+        let agent = state.agents.root_agents[1].clone();
+        assert!(visible_subagents(&state, &agent).is_empty());
+
+        // Expanding the agent under the cursor shows only its subagents
+        state.selected_index = 1;
+        toggle_subagents(&mut state);
+        assert_eq!(visible_subagents(&state, &agent).len(), 2);
+        toggle_subagents(&mut state);
+        assert!(visible_subagents(&state, &agent).is_empty());
+    }
+
+    #[test]
+    fn test_subagents_shown_when_configured() {
+        let mut state = state_with_filter("");
+        state.agents.root_agents[0].subagents = vec![Subagent::new(
+            "s1".to_string(),
+            SubagentType::Bash,
+            "build".to_string(),
+        )];
+        state.show_subagents_in_tree = true;
+        let agent = state.agents.root_agents[0].clone();
+        assert_eq!(visible_subagents(&state, &agent).len(), 1);
+
+        // Toggling collapses an agent that is expanded by default
+        state.selected_index = 0;
+        toggle_subagents(&mut state);
+        assert!(visible_subagents(&state, &agent).is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a flash message stays in the footer
+pub const FLASH_DURATION: Duration = Duration::from_secs(3);
+
+/// Critical messages waiting behind the current one; older ones are dropped beyond this
+const MAX_QUEUED: usize = 4;
+
+/// Priority of a flash message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlashKind {
+    /// Routine confirmations ("Sent to X"); a newer one replaces it right away
+    Info,
+    /// Connection changes and warnings; shown for the full duration even if
+    /// routine flashes follow
+    Critical,
+}
+
+#[derive(Debug, Clone)]
+pub struct Flash {
+    pub text: String,
+    pub kind: FlashKind,
+    pub shown_at: Instant,
+}
+
+impl Flash {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.shown_at) >= FLASH_DURATION
+    }
+}
+
+/// The flash message in the footer plus the ones waiting for it to expire.
+///
+/// Routine flashes replace each other, but never a critical one still on screen:
+/// they wait behind it, and only the latest routine flash is kept.
+#[derive(Debug, Clone, Default)]
+pub struct FlashQueue {
+    current: Option<Flash>,
+    queued: VecDeque<(String, FlashKind)>,
+}
+
+impl FlashQueue {
+    pub fn push(&mut self, text: String, kind: FlashKind, now: Instant) {
+        let critical_showing = self
+            .current
+            .as_ref()
+            .is_some_and(|f| f.kind == FlashKind::Critical && !f.is_expired(now));
+        if !critical_showing {
+            self.current = Some(Flash {
+                text,
+                kind,
+                shown_at: now,
+            });
+            return;
+        }
+
+        match kind {
+            FlashKind::Info => {
+                // A newer routine flash supersedes a waiting one
+                self.queued.retain(|(_, k)| *k == FlashKind::Critical);
+                self.queued.push_back((text, kind));
+            }
+            FlashKind::Critical => {
+                // Critical messages go ahead of a waiting routine one
+                let at = self
+                    .queued
+                    .iter()
+                    .position(|(_, k)| *k == FlashKind::Info)
+                    .unwrap_or(self.queued.len());
+                self.queued.insert(at, (text, kind));
+                if self.queued.len() > MAX_QUEUED {
+                    self.queued.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Expires the current flash and shows the next queued one; call once per frame
+    pub fn tick(&mut self, now: Instant) {
+        if !self.current.as_ref().is_some_and(|f| f.is_expired(now)) {
+            return;
+        }
+        self.current = self.queued.pop_front().map(|(text, kind)| Flash {
+            text,
+            kind,
+            shown_at: now,
+        });
+    }
+
+    /// The flash to show now, if any
+    pub fn current(&self) -> Option<&Flash> {
+        self.current.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(queue: &FlashQueue) -> Option<&str> {
+        queue.current().map(|f| f.text.as_str())
+    }
+
+    #[test]
+    fn test_routine_flashes_replace_each_other() {
+        let now = Instant::now();
+        let mut queue = FlashQueue::default();
+        queue.push("Sent to a".to_string(), FlashKind::Info, now);
+        queue.push("Sent to b".to_string(), FlashKind::Info, now);
+        assert_eq!(text(&queue), Some("Sent to b"));
+
+        queue.tick(now + FLASH_DURATION);
+        assert_eq!(text(&queue), None);
+    }
+
+    #[test]
+    fn test_critical_flash_holds_its_full_duration() {
+        let now = Instant::now();
+        let mut queue = FlashQueue::default();
+        queue.push("Hub disconnected".to_string(), FlashKind::Critical, now);
+        queue.push("Sent to a".to_string(), FlashKind::Info, now);
+        queue.push("Sent to b".to_string(), FlashKind::Info, now);
+
+        queue.tick(now + Duration::from_secs(2));
+        assert_eq!(text(&queue), Some("Hub disconnected"));
+
+        // The latest routine flash follows once the critical one has had its time
+        let later = now + FLASH_DURATION;
+        queue.tick(later);
+        assert_eq!(text(&queue), Some("Sent to b"));
+        queue.tick(later + FLASH_DURATION);
+        assert_eq!(text(&queue), None);
+    }
+
+    #[test]
+    fn test_critical_flashes_queue_ahead_of_routine() {
+        let now = Instant::now();
+        let mut queue = FlashQueue::default();
+        queue.push("Sent to a".to_string(), FlashKind::Info, now);
+        // A critical flash replaces a routine one on screen
+        queue.push("Hub disconnected".to_string(), FlashKind::Critical, now);
+        queue.push("Sent to b".to_string(), FlashKind::Info, now);
+        queue.push("Hub connected".to_string(), FlashKind::Critical, now);
+
+        let mut shown = Vec::new();
+        let mut at = now;
+        while let Some(flash) = queue.current() {
+            shown.push(flash.text.clone());
+            at += FLASH_DURATION;
+            queue.tick(at);
+        }
+        assert_eq!(
+            shown,
+            vec!["Hub disconnected", "Hub connected", "Sent to b"]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Sent inputs kept for recall, and across restarts
+pub const INPUT_HISTORY_LEN: usize = 100;
+
+/// File under `$HOME` the history is saved to between runs
+const HISTORY_FILE: &str = ".config/agentos-tui/input_history.json";
+
+/// `~/.config/agentos-tui/input_history.json`, or a relative path if `$HOME` is unset
+pub fn history_path() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(HISTORY_FILE)
+}
+
+/// Inputs sent from the input box, newest last, browsed with Up/Down
+#[derive(Debug, Clone, Default)]
+pub struct InputHistory {
+    entries: VecDeque<String>,
+    /// Entry currently shown while browsing, None when not browsing
+    position: Option<usize>,
+    /// What was in the input box before browsing started, restored past the newest entry
+    draft: String,
+}
+
+impl InputHistory {
+    /// Records a sent input, skipping blanks and a repeat of the newest entry
+    pub fn push(&mut self, input: &str) {
+        self.position = None;
+        if input.trim().is_empty() || self.entries.back().is_some_and(|last| last == input) {
+            return;
+        }
+        if self.entries.len() == INPUT_HISTORY_LEN {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(input.to_string());
+    }
+
+    /// Steps to the next older entry. `current` is the input box content, kept as the
+    /// draft when browsing starts. Returns None when there is nothing older.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let next = match self.position {
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+            Some(pos) => pos.checked_sub(1)?,
+        };
+        self.position = Some(next);
+        self.entries.get(next).map(String::as_str)
+    }
+
+    /// Steps to the next newer entry; past the newest, returns the saved draft and
+    /// stops browsing. Returns None when not browsing.
+    pub fn newer(&mut self) -> Option<&str> {
+        let pos = self.position?;
+        if pos + 1 < self.entries.len() {
+            self.position = Some(pos + 1);
+            self.entries.get(pos + 1).map(String::as_str)
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Stops browsing, e.g. once the recalled text is edited
+    pub fn reset_position(&mut self) {
+        self.position = None;
+    }
+
+    /// Loads the history saved at `path`; a missing or unreadable file gives an empty one
+    pub fn load(path: &Path) -> Self {
+        let entries: Vec<String> = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        let skip = entries.len().saturating_sub(INPUT_HISTORY_LEN);
+        Self {
+            entries: entries.into_iter().skip(skip).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Writes the history to `path`, creating its directory if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(&self.entries)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(entries: &[&str]) -> InputHistory {
+        let mut history = InputHistory::default();
+        for entry in entries {
+            history.push(entry);
+        }
+        history
+    }
+
+    #[test]
+    fn test_browse_and_restore_draft() {
+        let mut history = history(&["first", "second"]);
+
+        assert_eq!(history.older("half typed"), Some("second"));
+        assert_eq!(history.older("second"), Some("first"));
+        assert_eq!(history.older("first"), None);
+        assert_eq!(history.newer(), Some("second"));
+        // Past the newest entry the draft comes back
+        assert_eq!(history.newer(), Some("half typed"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn test_push_skips_blanks_and_repeats() {
+        let mut history = history(&["run tests", "run tests", "  ", "fix it"]);
+        assert_eq!(history.older(""), Some("fix it"));
+        assert_eq!(history.older(""), Some("run tests"));
+        assert_eq!(history.older(""), None);
+    }
+
+    #[test]
+    fn test_push_is_bounded() {
+        let mut history = InputHistory::default();
+        for i in 0..INPUT_HISTORY_LEN + 5 {
+            history.push(&format!("prompt {}", i));
+        }
+        assert_eq!(history.entries.len(), INPUT_HISTORY_LEN);
+        assert_eq!(history.entries.front().unwrap(), "prompt 5");
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("agentos-history-test-{}", std::process::id()));
+        let path = dir.join("nested").join("input_history.json");
+        let saved = history(&["one", "two\nlines"]);
+        saved.save(&path).unwrap();
+
+        let mut loaded = InputHistory::load(&path);
+        assert_eq!(loaded.older(""), Some("two\nlines"));
+        assert_eq!(loaded.older(""), Some("one"));
+
+        assert!(InputHistory::load(&dir.join("missing.json")).older("").is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+use serde::Deserialize;
+
+use crate::agents::AgentType;
+
+/// `[keys]` section of the config: approval bindings and the keys sent to agents
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    /// Characters that approve the selected agents (any of them)
+    pub approve: String,
+    /// Characters that reject the selected agents
+    pub reject: String,
+    /// Characters that approve every agent waiting for approval
+    pub approve_all: String,
+    /// Keys sent to Claude Code panes
+    pub claude_code: AgentKeys,
+    /// Keys sent to OpenCode panes
+    pub opencode: AgentKeys,
+    /// Keys sent to Codex CLI panes
+    pub codex_cli: AgentKeys,
+    /// Keys sent to Gemini CLI panes
+    pub gemini_cli: AgentKeys,
+    /// Message typed into the pane after an approval (e.g. "continue"); off by default
+    pub post_approval: Option<String>,
+    /// Tmux key names sent to undo the last input, separated by spaces (default "C-u")
+    pub undo_send: String,
+}
+
+/// Literal keys sent to an agent's pane on approve/reject
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AgentKeys {
+    pub approve: Option<String>,
+    pub reject: Option<String>,
+    /// Overrides the global post-approval message for this agent type
+    pub post_approval: Option<String>,
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            approve: "yY".to_string(),
+            reject: "nN".to_string(),
+            approve_all: "aA".to_string(),
+            claude_code: AgentKeys::default(),
+            opencode: AgentKeys::default(),
+            codex_cli: AgentKeys::default(),
+            gemini_cli: AgentKeys::default(),
+            post_approval: None,
+            undo_send: "C-u".to_string(),
+        }
+    }
+}
+
+impl KeysConfig {
+    /// Returns true if `c` is bound to approve
+    pub fn is_approve(&self, c: char) -> bool {
+        self.approve.contains(c)
+    }
+
+    /// Returns true if `c` is bound to reject
+    pub fn is_reject(&self, c: char) -> bool {
+        self.reject.contains(c)
+    }
+
+    /// Returns true if `c` is bound to approve all
+    pub fn is_approve_all(&self, c: char) -> bool {
+        self.approve_all.contains(c)
+    }
+
+    /// Keys sent to a pane of `agent_type` to approve
+    pub fn approval_keys(&self, agent_type: &AgentType) -> &str {
+        self.agent_keys(agent_type)
+            .and_then(|keys| keys.approve.as_deref())
+            .unwrap_or("y")
+    }
+
+    /// Keys sent to a pane of `agent_type` to reject
+    pub fn rejection_keys(&self, agent_type: &AgentType) -> &str {
+        self.agent_keys(agent_type)
+            .and_then(|keys| keys.reject.as_deref())
+            .unwrap_or("n")
+    }
+
+    /// Message sent after approving `agent_type`, if configured
+    pub fn post_approval_message(&self, agent_type: &AgentType) -> Option<&str> {
+        self.agent_keys(agent_type)
+            .and_then(|keys| keys.post_approval.as_deref())
+            .or(self.post_approval.as_deref())
+    }
+
+    fn agent_keys(&self, agent_type: &AgentType) -> Option<&AgentKeys> {
+        match agent_type {
+            AgentType::ClaudeCode => Some(&self.claude_code),
+            AgentType::OpenCode => Some(&self.opencode),
+            AgentType::CodexCli => Some(&self.codex_cli),
+            AgentType::GeminiCli => Some(&self.gemini_cli),
+            AgentType::Custom(_) | AgentType::Unknown => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings() {
+        let keys = KeysConfig::default();
+        assert!(keys.is_approve('y') && keys.is_approve('Y'));
+        assert!(keys.is_reject('n') && keys.is_reject('N'));
+        assert!(keys.is_approve_all('a'));
+        assert!(!keys.is_approve('a'));
+    }
+
+    #[test]
+    fn test_sent_keys_per_agent_type() {
+        let keys = KeysConfig {
+            codex_cli: AgentKeys {
+                approve: Some("1".to_string()),
+                reject: Some("Escape".to_string()),
+                post_approval: None,
+            },
+            gemini_cli: AgentKeys {
+                approve: Some("a".to_string()),
+                reject: None,
+                post_approval: None,
+            },
+            ..KeysConfig::default()
+        };
+
+        assert_eq!(keys.approval_keys(&AgentType::CodexCli), "1");
+        assert_eq!(keys.rejection_keys(&AgentType::CodexCli), "Escape");
+        assert_eq!(keys.approval_keys(&AgentType::GeminiCli), "a");
+        assert_eq!(keys.rejection_keys(&AgentType::GeminiCli), "n");
+        assert_eq!(keys.approval_keys(&AgentType::ClaudeCode), "y");
+        assert_eq!(keys.rejection_keys(&AgentType::Unknown), "n");
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::agents::MonitoredAgent;
+use crate::app::AppState;
+
+use super::status_colors::deserialize_optional_color;
+
+/// Color of agent labels without an override
+const DEFAULT_LABEL_COLOR: Color = Color::Cyan;
+
+/// One entry of the `[agent_overrides]` config section
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct AgentOverride {
+    /// Shown instead of the configured label
+    pub label: Option<String>,
+    /// Color of the label in the tree and the input box title
+    #[serde(deserialize_with = "deserialize_optional_color")]
+    pub color: Option<Color>,
+}
+
+/// `[agent_overrides]` config section: custom labels and colors keyed by tmux
+/// target (`"main:0.1"`) or working directory (`"/home/me/api"`). A target entry
+/// wins over a path entry for the same agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct AgentOverrides(pub HashMap<String, AgentOverride>);
+
+impl AgentOverrides {
+    /// The override for `agent`, if one is configured
+    pub fn get(&self, agent: &MonitoredAgent) -> Option<&AgentOverride> {
+        self.0
+            .get(&agent.target)
+            .or_else(|| self.0.get(agent.path.trim_end_matches('/')))
+    }
+}
+
+/// Label for `agent`: its configured override, or else the configured label,
+/// path style and full-path toggle
+pub(crate) fn agent_label(state: &AppState, agent: &MonitoredAgent) -> String {
+    if let Some(label) = state
+        .agent_overrides
+        .get(agent)
+        .and_then(|o| o.label.clone())
+    {
+        return label;
+    }
+    let path = agent.display_path(state.show_full_paths, state.path_display);
+    agent.display_label(&state.agent_label, &path)
+}
+
+/// Color of `agent`'s label
+pub(crate) fn agent_color(state: &AppState, agent: &MonitoredAgent) -> Color {
+    state
+        .agent_overrides
+        .get(agent)
+        .and_then(|o| o.color)
+        .unwrap_or(DEFAULT_LABEL_COLOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(target: &str, path: &str) -> MonitoredAgent {
+        MonitoredAgent {
+            path: path.to_string(),
+            ..MonitoredAgent::for_test(target)
+        }
+    }
+
+    #[test]
+    fn test_overrides_by_target_then_path() {
+        let mut state = AppState::new();
+        state.agent_overrides = serde_json::from_str(
+            r#"{
+                "main:0.1": {"label": "frontend", "color": "cyan"},
+                "/srv/api": {"label": "backend", "color": "green"},
+                "/srv/docs": {"color": "magenta"}
+            }"#,
+        )
+        .unwrap();
+
+        let frontend = agent("main:0.1", "/srv/web");
+        assert_eq!(agent_label(&state, &frontend), "frontend");
+        assert_eq!(agent_color(&state, &frontend), Color::Cyan);
+
+        let backend = agent("main:0.2", "/srv/api/");
+        assert_eq!(agent_label(&state, &backend), "backend");
+        assert_eq!(agent_color(&state, &backend), Color::Green);
+
+        // A color-only override keeps the configured label
+        let docs = agent("main:0.3", "/srv/docs");
+        assert_eq!(agent_label(&state, &docs), "/s/docs");
+        assert_eq!(agent_color(&state, &docs), Color::Magenta);
+
+        let plain = agent("main:0.4", "/srv/other");
+        assert_eq!(agent_color(&state, &plain), DEFAULT_LABEL_COLOR);
+    }
+
+    #[test]
+    fn test_unknown_override_color_is_rejected() {
+        let result: Result<AgentOverrides, _> =
+            serde_json::from_str(r#"{"main:0.1": {"color": "not-a-color"}}"#);
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,44 @@
 use ratatui::layout::{Constraint, Direction, Rect};
+use serde::Deserialize;
+
+/// Where the input panel sits in the right column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputPosition {
+    /// Above the summary
+    Top,
+    /// Below the preview
+    #[default]
+    Bottom,
+}
+
+/// Sizes of the right-column panels, configurable for tall or short terminals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// Height of the summary (TODO + activity) panel, in rows
+    pub summary_height: u16,
+    /// Minimum height of the pane preview, in rows
+    pub min_preview: u16,
+    /// Share of the right column given to the subagent log, in percent
+    pub subagent_log_ratio: u16,
+    /// Whether the input panel sits above or below the preview
+    pub input_position: InputPosition,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            summary_height: 15,
+            min_preview: 5,
+            subagent_log_ratio: 40,
+            input_position: InputPosition::default(),
+        }
+    }
+}
+
+/// Fewest rows the full layout needs: header, the content area's minimum and the footer
+pub const MIN_HEIGHT: u16 = 3 + 10 + 1;
 
 /// Layout manager for the application
 pub struct Layout;
@@ -29,7 +69,7 @@ impl Layout {
         Self::main_layout_all_with_analytics(area, show_queue, show_dashboard, show_factory, false)
     }
 
-    /// Creates the main layout with all optional panels
+    /// Creates the main layout with all optional panels except the stats graph
     pub fn main_layout_all_with_analytics(
         area: Rect,
         show_queue: bool,
@@ -37,10 +77,48 @@ impl Layout {
         show_factory: bool,
         show_analytics: bool,
     ) -> Vec<Rect> {
-        let queue_height = if show_queue { 8 } else { 0 };
-        let dashboard_height = if show_dashboard { 12 } else { 0 };
-        let factory_height = if show_factory { 10 } else { 0 };
-        let analytics_height = if show_analytics { 10 } else { 0 };
+        let mut chunks = Self::main_layout_with_stats(
+            area,
+            show_queue,
+            show_dashboard,
+            show_factory,
+            show_analytics,
+            false,
+        );
+        chunks.remove(6); // zero-height stats graph slot
+        chunks
+    }
+
+    /// Returns true if `area` is too short for the layout; a notice is shown instead
+    pub fn too_small(area: Rect) -> bool {
+        area.height < MIN_HEIGHT
+    }
+
+    /// Creates the main layout with all optional panels; the footer is at [7].
+    /// Panels that don't fit above the content area's minimum get zero height,
+    /// claiming rows in the order queue, dashboard, analytics, factory, stats graph.
+    pub fn main_layout_with_stats(
+        area: Rect,
+        show_queue: bool,
+        show_dashboard: bool,
+        show_factory: bool,
+        show_analytics: bool,
+        show_stats_graph: bool,
+    ) -> Vec<Rect> {
+        let mut spare = area.height.saturating_sub(MIN_HEIGHT);
+        let mut fit = |shown: bool, height: u16| {
+            if shown && height <= spare {
+                spare -= height;
+                height
+            } else {
+                0
+            }
+        };
+        let queue_height = fit(show_queue, 8);
+        let dashboard_height = fit(show_dashboard, 12);
+        let analytics_height = fit(show_analytics, 10);
+        let factory_height = fit(show_factory, 10);
+        let stats_height = fit(show_stats_graph, 9);
         ratatui::layout::Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -50,7 +128,8 @@ impl Layout {
                 Constraint::Length(queue_height),       // [3] Queue panel
                 Constraint::Length(dashboard_height),   // [4] Dashboard panel
                 Constraint::Length(factory_height),     // [5] Factory panel
-                Constraint::Length(1),                  // [6] Footer
+                Constraint::Length(stats_height),       // [6] Stats graph panel
+                Constraint::Length(1),                  // [7] Footer
             ])
             .split(area)
             .to_vec()
@@ -75,48 +154,79 @@ impl Layout {
         sidebar_width: u16,
         input_height: u16,
         show_summary: bool,
+        sizes: &LayoutConfig,
     ) -> (Rect, Rect, Rect, Rect) {
-        let columns = ratatui::layout::Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(sidebar_width),
-                Constraint::Percentage(100 - sidebar_width),
-            ])
-            .split(area);
+        let (sidebar, right) = Self::content_layout(area, sidebar_width);
+        let (summary, preview, input) =
+            Self::right_column(right, input_height, show_summary, sizes);
+        (sidebar, summary, preview, input)
+    }
 
-        let summary_height = if show_summary { 15 } else { 0 };
+    /// Splits a right column into summary, preview, and input
+    /// Returns (summary, preview, input)
+    pub fn right_column(
+        area: Rect,
+        input_height: u16,
+        show_summary: bool,
+        sizes: &LayoutConfig,
+    ) -> (Rect, Rect, Rect) {
+        // The summary gives way first so the preview keeps its minimum height
+        let summary_height = if show_summary {
+            let room = area.height.saturating_sub(sizes.min_preview + input_height + 2);
+            sizes.summary_height.min(room)
+        } else {
+            0
+        };
 
-        let right_side = ratatui::layout::Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(summary_height), // Summary (TODO + activity) - 2 columns
-                Constraint::Min(5),                 // Preview (pane content)
-                Constraint::Length(input_height + 2), // Input area (+ border)
-            ])
-            .split(columns[1]);
+        let summary = Constraint::Length(summary_height); // Summary (TODO + activity) - 2 columns
+        let preview = Constraint::Min(sizes.min_preview); // Preview (pane content)
+        let input = Constraint::Length(input_height + 2); // Input area (+ border)
 
-        (columns[0], right_side[0], right_side[1], right_side[2])
+        match sizes.input_position {
+            InputPosition::Top => {
+                let chunks = ratatui::layout::Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([input, summary, preview])
+                    .split(area);
+                (chunks[1], chunks[2], chunks[0])
+            }
+            InputPosition::Bottom => {
+                let chunks = ratatui::layout::Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([summary, preview, input])
+                    .split(area);
+                (chunks[0], chunks[1], chunks[2])
+            }
+        }
     }
 
     /// Splits the content area with subagent log (2 columns, right side split vertically)
-    pub fn content_layout_with_log(area: Rect, sidebar_width: u16) -> (Rect, Rect, Rect) {
-        let columns = ratatui::layout::Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(sidebar_width),
-                Constraint::Percentage(100 - sidebar_width),
-            ])
-            .split(area);
+    pub fn content_layout_with_log(
+        area: Rect,
+        sidebar_width: u16,
+        sizes: &LayoutConfig,
+    ) -> (Rect, Rect, Rect) {
+        let (sidebar, right) = Self::content_layout(area, sidebar_width);
+        let log_ratio = sizes.subagent_log_ratio.min(90);
 
         let right_side = ratatui::layout::Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(60), // Preview
-                Constraint::Percentage(40), // Subagent log
+                Constraint::Percentage(100 - log_ratio), // Preview
+                Constraint::Percentage(log_ratio),       // Subagent log
             ])
-            .split(columns[1]);
+            .split(right);
 
-        (columns[0], right_side[0], right_side[1])
+        (sidebar, right_side[0], right_side[1])
+    }
+
+    /// Splits the log area side by side when the subagent and event logs are both shown
+    pub fn split_logs(area: Rect) -> (Rect, Rect) {
+        let halves = ratatui::layout::Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        (halves[0], halves[1])
     }
 
     /// Creates a centered popup area
@@ -140,3 +250,119 @@ impl Layout {
             .split(vertical[1])[1]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 100,
+        height: 60,
+    };
+
+    #[test]
+    fn test_default_sizes() {
+        let sizes = LayoutConfig::default();
+        let (_, summary, preview, input) =
+            Layout::content_layout_with_input(AREA, 30, 1, true, &sizes);
+        assert_eq!(summary.height, 15);
+        assert_eq!(input.height, 3);
+        assert_eq!(preview.height, 60 - 15 - 3);
+    }
+
+    #[test]
+    fn test_configured_summary_height() {
+        let sizes = LayoutConfig {
+            summary_height: 8,
+            ..Default::default()
+        };
+        let (_, summary, preview, _) =
+            Layout::content_layout_with_input(AREA, 30, 1, true, &sizes);
+        assert_eq!(summary.height, 8);
+        assert_eq!(preview.height, 60 - 8 - 3);
+
+        // Hidden summary ignores the configured height
+        let (_, summary, _, _) = Layout::content_layout_with_input(AREA, 30, 1, false, &sizes);
+        assert_eq!(summary.height, 0);
+    }
+
+    #[test]
+    fn test_min_preview_wins_over_summary() {
+        let sizes = LayoutConfig {
+            summary_height: 15,
+            min_preview: 20,
+            ..Default::default()
+        };
+        let short = Rect { height: 30, ..AREA };
+        let (_, summary, preview, input) =
+            Layout::content_layout_with_input(short, 30, 1, true, &sizes);
+        assert_eq!(preview.height, 20);
+        assert_eq!(input.height, 3);
+        assert_eq!(summary.height, 30 - 20 - 3);
+    }
+
+    #[test]
+    fn test_subagent_log_ratio() {
+        let sizes = LayoutConfig {
+            subagent_log_ratio: 25,
+            ..Default::default()
+        };
+        let (_, preview, log) = Layout::content_layout_with_log(AREA, 30, &sizes);
+        assert_eq!(log.height, 15);
+        assert_eq!(preview.height, 45);
+    }
+
+    #[test]
+    fn test_input_position_ordering() {
+        let bottom = LayoutConfig::default();
+        let (_, summary, preview, input) =
+            Layout::content_layout_with_input(AREA, 30, 1, true, &bottom);
+        assert!(summary.y < preview.y && preview.y < input.y);
+        assert_eq!(input.bottom(), AREA.bottom());
+
+        let top = LayoutConfig {
+            input_position: InputPosition::Top,
+            ..Default::default()
+        };
+        let (_, summary, preview, input) =
+            Layout::content_layout_with_input(AREA, 30, 1, true, &top);
+        assert_eq!(input.y, AREA.y);
+        assert!(input.y < summary.y && summary.y < preview.y);
+        assert_eq!(input.height, 3);
+        assert_eq!(preview.bottom(), AREA.bottom());
+    }
+
+    #[test]
+    fn test_stats_graph_sits_above_footer() {
+        let chunks = Layout::main_layout_with_stats(AREA, false, false, false, false, true);
+        assert_eq!(chunks[6].height, 9);
+        assert_eq!(chunks[7].height, 1);
+        assert_eq!(chunks[6].bottom(), chunks[7].y);
+
+        // Without the graph the old seven-chunk layout is unchanged
+        let chunks = Layout::main_layout_all_with_analytics(AREA, false, false, false, false);
+        assert_eq!(chunks.len(), 7);
+        assert_eq!(chunks[6].y, AREA.height - 1);
+    }
+
+    #[test]
+    fn test_panels_hidden_when_short() {
+        // 30 rows leave 16 beside the minimum: the queue fits, the dashboard doesn't
+        let short = Rect { height: 30, ..AREA };
+        let chunks = Layout::main_layout_with_stats(short, true, true, true, false, false);
+        assert_eq!(chunks[3].height, 8);
+        assert_eq!(chunks[4].height, 0);
+        assert_eq!(chunks[5].height, 0);
+        assert_eq!(chunks[1].height, 30 - 3 - 8 - 1);
+
+        // Every panel on 60 rows: the stats graph, last in line, is what gives way
+        let chunks = Layout::main_layout_with_stats(AREA, true, true, true, true, true);
+        assert_eq!(chunks[6].height, 0);
+        assert_eq!(chunks[1].height, 60 - 3 - 8 - 12 - 10 - 10 - 1);
+
+        assert!(Layout::too_small(Rect { height: MIN_HEIGHT - 1, ..AREA }));
+        assert!(!Layout::too_small(Rect { height: MIN_HEIGHT, ..AREA }));
+    }
+}
@@ -1,6 +1,41 @@
 mod app;
+mod approval;
+mod audit;
+mod auto_approve;
+mod clipboard;
 pub mod components;
+mod confirm;
+mod event_log;
+mod export;
+mod flash;
+pub(crate) mod filter;
+mod history;
+mod keys;
+mod label;
 mod layout;
+pub(crate) mod preview_search;
+mod project_path;
+mod send;
+mod sort;
+mod spinner;
+mod status_colors;
+pub(crate) mod text;
+mod theme;
 
 pub use app::run_app;
-pub use layout::Layout;
+pub use approval::{ApprovalThrottle, ChoiceHighlight};
+pub use audit::{AuditAction, AuditConfig, AuditLog};
+pub use auto_approve::{AutoApproveKind, AutoApproveRule, AutoApproveRules};
+pub use event_log::EventLog;
+pub use flash::{FlashKind, FlashQueue};
+pub use history::InputHistory;
+pub use keys::{AgentKeys, KeysConfig};
+pub use label::{AgentOverride, AgentOverrides};
+pub use layout::{InputPosition, Layout, LayoutConfig};
+pub use preview_search::PreviewSearch;
+pub use project_path::ProjectPrefixes;
+pub use send::PendingSend;
+pub use sort::SortMode;
+pub use spinner::{Spinner, SpinnerStyle, DEFAULT_SPINNER_TICK_MS};
+pub use status_colors::StatusColors;
+pub use theme::Theme;
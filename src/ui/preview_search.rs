@@ -0,0 +1,192 @@
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+/// Lines kept visible below a search match the preview jumps to
+const MATCH_CONTEXT_LINES: usize = 3;
+
+/// `/` search in the focused preview. Matching ignores ASCII case; the match
+/// index points into the matching lines of the selected agent's content, oldest
+/// first.
+#[derive(Debug, Clone, Default)]
+pub struct PreviewSearch {
+    pub query: String,
+    /// The query is being typed
+    pub editing: bool,
+    /// Index of the match the preview last jumped to
+    pub current: usize,
+}
+
+impl PreviewSearch {
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Indexes of the lines of `content` containing the query
+    pub fn match_lines(&self, content: &str) -> Vec<usize> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !match_ranges(line, &self.query).is_empty())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Moves to the newest match and returns the preview scroll showing it
+    pub fn jump_to_last(&mut self, content: &str) -> Option<usize> {
+        let matches = self.match_lines(content);
+        self.current = matches.len().checked_sub(1)?;
+        Some(scroll_to_line(
+            content.lines().count(),
+            matches[self.current],
+        ))
+    }
+
+    /// Moves `delta` matches forward (down) or back, wrapping around, and
+    /// returns the preview scroll showing the new match
+    pub fn step(&mut self, content: &str, delta: isize) -> Option<usize> {
+        let matches = self.match_lines(content);
+        if matches.is_empty() {
+            return None;
+        }
+        let len = matches.len() as isize;
+        let current = self.current.min(matches.len() - 1) as isize;
+        self.current = (current + delta).rem_euclid(len) as usize;
+        Some(scroll_to_line(
+            content.lines().count(),
+            matches[self.current],
+        ))
+    }
+
+    /// " /query [2/5] " for the preview title
+    pub fn title(&self, content: &str) -> String {
+        let cursor = if self.editing { "▏" } else { "" };
+        if self.query.is_empty() {
+            return format!(" /{} ", cursor);
+        }
+        let matches = self.match_lines(content);
+        let position = if matches.is_empty() {
+            "no match".to_string()
+        } else {
+            format!(
+                "{}/{}",
+                self.current.min(matches.len() - 1) + 1,
+                matches.len()
+            )
+        };
+        format!(" /{}{} [{}] ", self.query, cursor, position)
+    }
+}
+
+/// Preview scroll (lines up from the bottom) that shows line `line` with a few
+/// lines of context below it
+fn scroll_to_line(total_lines: usize, line: usize) -> usize {
+    let end = (line + 1 + MATCH_CONTEXT_LINES).min(total_lines);
+    total_lines - end
+}
+
+/// Byte ranges of `query` in `line`, ignoring ASCII case
+fn match_ranges(line: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // ASCII lowercasing keeps byte offsets valid in the original line
+    let haystack = line.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect()
+}
+
+/// Splits `line` into spans in `base` style with each occurrence of `query`
+/// highlighted; `current` marks the line of the match jumped to
+pub fn highlight_matches<'a>(
+    line: &'a str,
+    base: Style,
+    query: &str,
+    current: bool,
+) -> Vec<Span<'a>> {
+    let ranges = match_ranges(line, query);
+    if ranges.is_empty() {
+        return vec![Span::styled(line, base)];
+    }
+    let highlight =
+        Style::default()
+            .fg(Color::Black)
+            .bg(if current { Color::Cyan } else { Color::Yellow });
+
+    let mut spans = Vec::new();
+    let mut at = 0;
+    for (start, end) in ranges {
+        if start > at {
+            spans.push(Span::styled(&line[at..start], base));
+        }
+        spans.push(Span::styled(&line[start..end], highlight));
+        at = end;
+    }
+    if at < line.len() {
+        spans.push(Span::styled(&line[at..], base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "cargo build\nerror: oops\nok\nERROR again\nok\nok\nok\nok";
+
+    fn search(query: &str) -> PreviewSearch {
+        PreviewSearch {
+            query: query.to_string(),
+            ..PreviewSearch::default()
+        }
+    }
+
+    #[test]
+    fn test_match_lines_ignore_case() {
+        assert_eq!(search("error").match_lines(CONTENT), vec![1, 3]);
+        assert!(search("").match_lines(CONTENT).is_empty());
+        assert!(search("missing").match_lines(CONTENT).is_empty());
+    }
+
+    #[test]
+    fn test_jumps_scroll_matches_into_view() {
+        let mut found = search("error");
+        // 8 lines; line 3 plus three below ends at line 7, one up from the bottom
+        assert_eq!(found.jump_to_last(CONTENT), Some(1));
+        assert_eq!(found.title(CONTENT), " /error [2/2] ");
+
+        assert_eq!(found.step(CONTENT, -1), Some(3));
+        assert_eq!(found.current, 0);
+        // Wraps around past the last match
+        assert_eq!(found.step(CONTENT, -1), Some(1));
+        assert_eq!(found.step(CONTENT, 1), Some(3));
+
+        let mut none = search("missing");
+        assert_eq!(none.jump_to_last(CONTENT), None);
+        assert_eq!(none.step(CONTENT, 1), None);
+        assert_eq!(none.title(CONTENT), " /missing [no match] ");
+    }
+
+    #[test]
+    fn test_highlight_matches_splits_spans() {
+        let base = Style::default().fg(Color::Red);
+        let spans = highlight_matches("Error: error", base, "error", false);
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["Error", ": ", "error"]);
+        assert_eq!(spans[0].style.bg, Some(Color::Yellow));
+        assert_eq!(spans[1].style, base);
+
+        let current = highlight_matches("the error", base, "error", true);
+        assert_eq!(current[1].style.bg, Some(Color::Cyan));
+
+        let plain = highlight_matches("fine", base, "error", false);
+        assert_eq!(plain.len(), 1);
+    }
+}
@@ -0,0 +1,88 @@
+use serde::Deserialize;
+
+/// `project_prefixes` in the config: leading directories stripped from project
+/// paths in the queue and dashboard panels, tried in order. A leading `~` stands
+/// for `$HOME`, which is looked up once when the config is read.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "Vec<String>")]
+pub struct ProjectPrefixes(Vec<String>);
+
+impl Default for ProjectPrefixes {
+    fn default() -> Self {
+        Self::from(vec!["~".to_string(), "/tmp".to_string()])
+    }
+}
+
+impl From<Vec<String>> for ProjectPrefixes {
+    fn from(prefixes: Vec<String>) -> Self {
+        let home = std::env::var("HOME").ok();
+        Self::with_home(prefixes, home.as_deref())
+    }
+}
+
+impl ProjectPrefixes {
+    /// Expands `~` in `prefixes` to `home` and drops trailing slashes. `~`
+    /// prefixes are left out without a home, as are empty ones.
+    fn with_home(prefixes: Vec<String>, home: Option<&str>) -> Self {
+        Self(
+            prefixes
+                .into_iter()
+                .filter_map(|prefix| match (prefix.strip_prefix('~'), home) {
+                    (Some(rest), Some(home)) => Some(format!("{}{}", home, rest)),
+                    (Some(_), None) => None,
+                    (None, _) => Some(prefix),
+                })
+                .map(|prefix| prefix.trim_end_matches('/').to_string())
+                .filter(|prefix| !prefix.is_empty())
+                .collect(),
+        )
+    }
+
+    /// `path` without the first configured prefix it starts with, e.g.
+    /// `/home/me/Projects/api` → `Projects/api`. Paths matching no prefix, or
+    /// nothing but a prefix, are returned unchanged.
+    pub fn shorten<'a>(&self, path: &'a str) -> &'a str {
+        for prefix in &self.0 {
+            // Only whole directory names: `/tmp` must not eat `/tmpfiles`
+            if let Some(rest) = path
+                .strip_prefix(prefix.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                let rest = rest.trim_start_matches('/');
+                if !rest.is_empty() {
+                    return rest;
+                }
+            }
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorten_strips_first_matching_prefix() {
+        let home = Some("/home/me");
+        let prefixes = ProjectPrefixes::with_home(vec!["~".to_string(), "/tmp".to_string()], home);
+        assert_eq!(prefixes.shorten("/home/me/Projects/api"), "Projects/api");
+        assert_eq!(prefixes.shorten("/tmp/scratch"), "scratch");
+        assert_eq!(prefixes.shorten("/tmpfiles/x"), "/tmpfiles/x");
+        assert_eq!(prefixes.shorten("/home/me"), "/home/me");
+        assert_eq!(prefixes.shorten("api"), "api");
+        // Without $HOME only the literal prefixes apply
+        let homeless = ProjectPrefixes::with_home(vec!["~".to_string(), "/tmp".to_string()], None);
+        assert_eq!(homeless.shorten("/home/me/api"), "/home/me/api");
+        assert_eq!(homeless.shorten("/tmp/scratch"), "scratch");
+
+        let custom =
+            ProjectPrefixes::with_home(vec!["/srv/work/".to_string(), "~/code".to_string()], home);
+        assert_eq!(custom.shorten("/srv/work/site"), "site");
+        assert_eq!(custom.shorten("/home/me/code/cli"), "cli");
+        assert_eq!(custom.shorten("/home/me/other"), "/home/me/other");
+
+        let configured: ProjectPrefixes = serde_json::from_str(r#"["/srv/work/"]"#).unwrap();
+        assert_eq!(configured.shorten("/srv/work/site"), "site");
+    }
+}
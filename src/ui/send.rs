@@ -0,0 +1,363 @@
+use std::time::{Duration, Instant};
+
+use crate::agents::{AgentStatus, ApprovalType, MonitoredAgent};
+
+/// One call into tmux when sending to an agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendStep<'a> {
+    /// Key names, sent as-is (e.g. "y", "Enter")
+    Keys(&'a str),
+    /// Text sent literally (`send-keys -l`)
+    Literal(&'a str),
+    /// Text pasted in one go through a tmux buffer (`paste-buffer -p`), so
+    /// embedded newlines don't submit early
+    Paste(&'a str),
+}
+
+/// Steps to send typed input followed by Enter.
+///
+/// With `as_block`, multi-line input is pasted atomically so agents that submit
+/// on newline receive the whole block; single-line input is typed either way.
+///
+/// Typed input may contain named keys as `{Name}` tokens (see `key_token`),
+/// e.g. `src/ma{Tab}` or `{Esc}:wq`. Everything else, backslashes included, is
+/// typed literally, and the input is always submitted with Enter. Pasted blocks
+/// are sent verbatim.
+pub fn input_steps(input: &str, as_block: bool) -> Vec<SendStep<'_>> {
+    if as_block && input.contains('\n') {
+        return vec![SendStep::Paste(input), SendStep::Keys("Enter")];
+    }
+    let mut steps = special_key_steps(input);
+    steps.push(SendStep::Keys("Enter"));
+    steps
+}
+
+/// Splits `input` into literal text and the `{Name}` keys written in it
+fn special_key_steps(input: &str) -> Vec<SendStep<'_>> {
+    let mut steps = Vec::new();
+    // Start of the literal text not yet pushed
+    let mut start = 0;
+    let mut search = 0;
+    while let Some(open) = input[search..].find('{').map(|i| search + i) {
+        let Some(close) = input[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        let Some(key) = key_token(&input[open + 1..close]) else {
+            search = open + 1;
+            continue;
+        };
+        if open > start {
+            steps.push(SendStep::Literal(&input[start..open]));
+        }
+        steps.push(SendStep::Keys(key));
+        start = close + 1;
+        search = start;
+    }
+    if start < input.len() {
+        steps.push(SendStep::Literal(&input[start..]));
+    }
+    steps
+}
+
+/// The tmux key name for `{name}` in typed input: arrows, Esc, Tab, Enter,
+/// BSpace, Home, End, PageUp/PageDown, Space, F1-F12 and `C-x` for Ctrl+x.
+/// Unknown names return None and stay literal text.
+fn key_token(name: &str) -> Option<&str> {
+    let key = match name {
+        "Up" | "Down" | "Left" | "Right" | "Tab" | "Enter" | "Home" | "End" | "Space" => name,
+        "Esc" | "Escape" => "Escape",
+        "BSpace" | "Backspace" => "BSpace",
+        "PageUp" | "PgUp" => "PPage",
+        "PageDown" | "PgDn" => "NPage",
+        _ => {
+            let ctrl = name
+                .strip_prefix("C-")
+                .is_some_and(|c| c.len() == 1 && c.bytes().all(|b| b.is_ascii_alphanumeric()));
+            let function = name
+                .strip_prefix('F')
+                .filter(|n| !n.starts_with('0'))
+                .and_then(|n| n.parse::<u8>().ok())
+                .is_some_and(|n| (1..=12).contains(&n));
+            if !(ctrl || function) {
+                return None;
+            }
+            name
+        }
+    };
+    Some(key)
+}
+
+/// Key names for choices 1-9; a question prompt takes one digit per choice
+pub(crate) const CHOICE_KEYS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// Parses `input` as an answer to `agent`'s multi-select question: choice numbers
+/// separated by spaces or commas ("1 3", "2,4"). Returns None unless the agent is
+/// asking one and every number names a choice, so other input is sent as text.
+pub fn multi_select_answer(agent: &MonitoredAgent, input: &str) -> Option<Vec<usize>> {
+    let AgentStatus::AwaitingApproval {
+        approval_type:
+            ApprovalType::UserQuestion {
+                choices,
+                multi_select: true,
+            },
+        ..
+    } = &agent.status
+    else {
+        return None;
+    };
+    let max = choices.len().min(CHOICE_KEYS.len());
+    let mut numbers = Vec::new();
+    for token in input.split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() {
+            continue;
+        }
+        let n: usize = token.parse().ok()?;
+        if n == 0 || n > max {
+            return None;
+        }
+        if !numbers.contains(&n) {
+            numbers.push(n);
+        }
+    }
+    (!numbers.is_empty()).then_some(numbers)
+}
+
+/// Steps to toggle each of `numbers` (1-9) in a multi-select prompt, then submit
+pub fn choice_steps(numbers: &[usize]) -> Vec<SendStep<'static>> {
+    numbers
+        .iter()
+        .filter_map(|&n| CHOICE_KEYS.get(n.wrapping_sub(1)))
+        .map(|key| SendStep::Keys(key))
+        .chain(std::iter::once(SendStep::Keys("Enter")))
+        .collect()
+}
+
+/// Input whose placeholders were expanded, shown for confirmation before sending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSend {
+    pub target: String,
+    /// What the UI calls the agent, for the popup title
+    pub label: String,
+    /// Input with placeholders expanded for this agent
+    pub text: String,
+    pub as_block: bool,
+}
+
+impl PendingSend {
+    /// Returns a pending send when `input` contains placeholders that expand for
+    /// `agent`; plain input returns None and is sent right away
+    pub fn for_agent(
+        agent: &MonitoredAgent,
+        label: String,
+        input: &str,
+        as_block: bool,
+    ) -> Option<Self> {
+        let text = agent.expand_placeholders(input);
+        if text == input {
+            return None;
+        }
+        Some(Self {
+            target: agent.target.clone(),
+            label,
+            text,
+            as_block,
+        })
+    }
+}
+
+/// How long after sending the undo key still applies
+pub const UNDO_SEND_WINDOW: Duration = Duration::from_secs(10);
+
+/// The most recent input sent to a single agent, kept for undo
+#[derive(Debug, Clone)]
+pub struct LastSend {
+    pub target: String,
+    pub at: Instant,
+}
+
+impl LastSend {
+    pub fn new(target: String) -> Self {
+        Self {
+            target,
+            at: Instant::now(),
+        }
+    }
+
+    /// Returns true while `now` is within UNDO_SEND_WINDOW of the send
+    pub fn is_undoable(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.at) <= UNDO_SEND_WINDOW
+    }
+}
+
+/// Steps to undo a send: each space-separated key name from `undo_keys`.
+///
+/// Best-effort only: this clears whatever the agent hasn't consumed yet, it cannot
+/// take back a prompt the agent already started working on.
+pub fn undo_steps(undo_keys: &str) -> Vec<SendStep<'_>> {
+    undo_keys.split_whitespace().map(SendStep::Keys).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_send_pastes_multiline_input() {
+        let code = "fn main() {\n    println!(\"hi\");\n}";
+        assert_eq!(
+            input_steps(code, true),
+            vec![SendStep::Paste(code), SendStep::Keys("Enter")]
+        );
+    }
+
+    #[test]
+    fn test_plain_send_types_input() {
+        let code = "line one\nline two";
+        assert_eq!(
+            input_steps(code, false),
+            vec![SendStep::Literal(code), SendStep::Keys("Enter")]
+        );
+        // Nothing to protect on a single line
+        assert_eq!(
+            input_steps("hello", true),
+            vec![SendStep::Literal("hello"), SendStep::Keys("Enter")]
+        );
+    }
+
+    #[test]
+    fn test_special_keys_in_typed_input() {
+        assert_eq!(
+            input_steps("src/ma{Tab}", false),
+            vec![
+                SendStep::Literal("src/ma"),
+                SendStep::Keys("Tab"),
+                SendStep::Keys("Enter")
+            ]
+        );
+        assert_eq!(
+            input_steps("{Esc}:wq", false),
+            vec![
+                SendStep::Keys("Escape"),
+                SendStep::Literal(":wq"),
+                SendStep::Keys("Enter")
+            ]
+        );
+        assert_eq!(
+            input_steps("a{Up}{C-c}", false),
+            vec![
+                SendStep::Literal("a"),
+                SendStep::Keys("Up"),
+                SendStep::Keys("C-c"),
+                SendStep::Keys("Enter")
+            ]
+        );
+        // Backslashes, unknown names and unclosed braces stay text
+        for text in [
+            "printf 'a\\tb\\n'",
+            "C:\\\\temp\\",
+            "{json} {F13} {Tab",
+            "match x { Some(y) => y }",
+        ] {
+            assert_eq!(
+                input_steps(text, false),
+                vec![SendStep::Literal(text), SendStep::Keys("Enter")]
+            );
+        }
+        assert_eq!(
+            input_steps("{{Esc}}", false),
+            vec![
+                SendStep::Literal("{"),
+                SendStep::Keys("Escape"),
+                SendStep::Literal("}"),
+                SendStep::Keys("Enter")
+            ]
+        );
+        assert_eq!(
+            input_steps("{F5}{PageUp}", false),
+            vec![
+                SendStep::Keys("F5"),
+                SendStep::Keys("PPage"),
+                SendStep::Keys("Enter")
+            ]
+        );
+        // Pasted blocks are sent verbatim
+        assert_eq!(
+            input_steps("a{Tab}\nb", true),
+            vec![SendStep::Paste("a{Tab}\nb"), SendStep::Keys("Enter")]
+        );
+    }
+
+    #[test]
+    fn test_pending_send_only_for_templates() {
+        let agent = MonitoredAgent::for_test("main:0.1");
+        let label = || "/h/u/project".to_string();
+
+        assert_eq!(PendingSend::for_agent(&agent, label(), "run the tests", false), None);
+        // Unknown placeholders are left alone, so nothing to confirm
+        assert_eq!(PendingSend::for_agent(&agent, label(), "fix {bug}", false), None);
+
+        let pending =
+            PendingSend::for_agent(&agent, label(), "cd {path} && ls # {target}", true).unwrap();
+        assert_eq!(pending.text, "cd /home/user/project && ls # main:0.1");
+        assert_eq!(pending.target, "main:0.1");
+        assert!(pending.as_block);
+    }
+
+    #[test]
+    fn test_undo_window() {
+        let last = LastSend::new("main:0.1".to_string());
+        assert!(last.is_undoable(last.at));
+        assert!(last.is_undoable(last.at + UNDO_SEND_WINDOW));
+        assert!(!last.is_undoable(last.at + UNDO_SEND_WINDOW + Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn test_undo_sequence() {
+        assert_eq!(undo_steps("C-u"), vec![SendStep::Keys("C-u")]);
+        assert_eq!(
+            undo_steps(" Escape  C-u "),
+            vec![SendStep::Keys("Escape"), SendStep::Keys("C-u")]
+        );
+        assert!(undo_steps("").is_empty());
+    }
+
+    fn question(multi_select: bool) -> MonitoredAgent {
+        let mut agent = MonitoredAgent::for_test("main:0.0");
+        agent.status = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::UserQuestion {
+                choices: vec!["Auth".into(), "Billing".into(), "Search".into()],
+                multi_select,
+            },
+            details: "Which features?".to_string(),
+        };
+        agent
+    }
+
+    #[test]
+    fn test_multi_select_answer_parses_choice_numbers() {
+        let agent = question(true);
+        assert_eq!(multi_select_answer(&agent, "1 3"), Some(vec![1, 3]));
+        assert_eq!(multi_select_answer(&agent, " 2,3 , 2"), Some(vec![2, 3]));
+        assert_eq!(multi_select_answer(&agent, "3"), Some(vec![3]));
+        // Out of range or not numbers: sent as plain text instead
+        assert_eq!(multi_select_answer(&agent, "1 4"), None);
+        assert_eq!(multi_select_answer(&agent, "0"), None);
+        assert_eq!(multi_select_answer(&agent, "both please"), None);
+        assert_eq!(multi_select_answer(&agent, "  "), None);
+        // Single-select questions keep the number keys
+        assert_eq!(multi_select_answer(&question(false), "1 3"), None);
+    }
+
+    #[test]
+    fn test_choice_steps_toggle_then_submit() {
+        assert_eq!(
+            choice_steps(&[1, 3]),
+            vec![
+                SendStep::Keys("1"),
+                SendStep::Keys("3"),
+                SendStep::Keys("Enter")
+            ]
+        );
+        assert_eq!(choice_steps(&[]), vec![SendStep::Keys("Enter")]);
+    }
+}
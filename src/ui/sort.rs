@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use serde::Deserialize;
+
+use crate::agents::{AgentStatus, MonitoredAgent};
+use crate::app::AppState;
+
+/// Order of agents in the sidebar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortMode {
+    /// By session, window and pane
+    #[default]
+    Target,
+    /// Approvals and errors first, then working, then idle
+    Attention,
+    /// Longest running first
+    Uptime,
+}
+
+impl SortMode {
+    /// The mode after this one, for the cycle key
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Target => SortMode::Attention,
+            SortMode::Attention => SortMode::Uptime,
+            SortMode::Uptime => SortMode::Target,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Target => "target",
+            SortMode::Attention => "attention",
+            SortMode::Uptime => "uptime",
+        }
+    }
+
+    /// Lower sorts first; every agent ranks the same in Target mode
+    fn rank(self, agent: &MonitoredAgent, now: Instant) -> u64 {
+        match self {
+            SortMode::Target => 0,
+            SortMode::Attention => match agent.status {
+                AgentStatus::AwaitingApproval { .. } | AgentStatus::Error { .. } => 0,
+                AgentStatus::Processing { .. } => 1,
+                AgentStatus::Idle => 2,
+                AgentStatus::Unknown => 3,
+            },
+            SortMode::Uptime => {
+                u64::MAX - now.saturating_duration_since(agent.started_at).as_millis() as u64
+            }
+        }
+    }
+}
+
+/// Sorts `agents` for the sidebar.
+///
+/// The sidebar groups agents by session and window, so sessions and windows are
+/// ordered by their best-ranked agent and agents by rank within their window.
+/// That keeps the list order identical to the drawn order, which j/k follow.
+pub fn sort_agents(agents: &mut [MonitoredAgent], mode: SortMode) {
+    let now = Instant::now();
+    let mut session_best: HashMap<String, u64> = HashMap::new();
+    let mut window_best: HashMap<(String, u32), u64> = HashMap::new();
+    for agent in agents.iter() {
+        let rank = mode.rank(agent, now);
+        let best = session_best.entry(agent.session.clone()).or_insert(rank);
+        *best = (*best).min(rank);
+        let best = window_best
+            .entry((agent.session.clone(), agent.window))
+            .or_insert(rank);
+        *best = (*best).min(rank);
+    }
+
+    agents.sort_by_cached_key(|agent| {
+        (
+            session_best[&agent.session],
+            agent.session.clone(),
+            window_best[&(agent.session.clone(), agent.window)],
+            agent.window,
+            mode.rank(agent, now),
+            agent.pane,
+        )
+    });
+}
+
+/// Targets under the cursor and in the multi-selection, captured before the
+/// agent list is replaced or reordered
+pub struct SelectionSnapshot {
+    cursor: Option<String>,
+    selected: Vec<String>,
+}
+
+impl SelectionSnapshot {
+    pub fn capture(state: &AppState) -> Self {
+        let agents = &state.agents.root_agents;
+        Self {
+            cursor: state.selected_agent().map(|a| a.target.clone()),
+            selected: state
+                .selected_agents
+                .iter()
+                .filter_map(|&idx| agents.get(idx))
+                .map(|a| a.target.clone())
+                .collect(),
+        }
+    }
+
+    /// Points the cursor and multi-selection back at the same agents by target.
+    /// Agents that are gone drop out; the cursor stays put if its agent is gone.
+    pub fn restore(self, state: &mut AppState) {
+        let agents = &state.agents.root_agents;
+        if let Some(idx) = self
+            .cursor
+            .and_then(|target| agents.iter().position(|a| a.target == target))
+        {
+            state.selected_index = idx;
+        }
+        state.selected_agents = agents
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| self.selected.contains(&a.target))
+            .map(|(idx, _)| idx)
+            .collect();
+    }
+}
+
+/// Re-sorts the agent list in `state.sort_mode`, keeping the selection on the same agents
+pub fn apply_sort(state: &mut AppState) {
+    let selection = SelectionSnapshot::capture(state);
+    sort_agents(&mut state.agents.root_agents, state.sort_mode);
+    selection.restore(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ApprovalType;
+    use std::time::Duration;
+
+    fn agent(session: &str, window: u32, pane: u32, status: AgentStatus) -> MonitoredAgent {
+        MonitoredAgent {
+            status,
+            ..MonitoredAgent::for_test(&format!("{}:{}.{}", session, window, pane))
+        }
+    }
+
+    fn approval() -> AgentStatus {
+        AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: "ls".to_string(),
+        }
+    }
+
+    fn working() -> AgentStatus {
+        AgentStatus::Processing {
+            activity: "Reading".to_string(),
+        }
+    }
+
+    fn targets(agents: &[MonitoredAgent]) -> Vec<&str> {
+        agents.iter().map(|a| a.target.as_str()).collect()
+    }
+
+    fn mixed() -> Vec<MonitoredAgent> {
+        vec![
+            agent("alpha", 0, 0, AgentStatus::Idle),
+            agent("alpha", 0, 1, working()),
+            agent("alpha", 10, 0, AgentStatus::Idle),
+            agent("alpha", 2, 0, AgentStatus::Unknown),
+            agent("beta", 0, 0, AgentStatus::Idle),
+            agent(
+                "beta",
+                1,
+                0,
+                AgentStatus::Error {
+                    message: "panic".to_string(),
+                },
+            ),
+            agent("gamma", 0, 0, approval()),
+        ]
+    }
+
+    #[test]
+    fn test_target_order_is_numeric_by_window() {
+        let mut agents = mixed();
+        agents.reverse();
+        sort_agents(&mut agents, SortMode::Target);
+        assert_eq!(
+            targets(&agents),
+            vec!["alpha:0.0", "alpha:0.1", "alpha:2.0", "alpha:10.0", "beta:0.0", "beta:1.0", "gamma:0.0"]
+        );
+    }
+
+    #[test]
+    fn test_attention_order() {
+        let mut agents = mixed();
+        sort_agents(&mut agents, SortMode::Attention);
+        // Sessions with pending agents come first, and within them the pending window
+        assert_eq!(
+            targets(&agents),
+            vec!["beta:1.0", "beta:0.0", "gamma:0.0", "alpha:0.1", "alpha:0.0", "alpha:10.0", "alpha:2.0"]
+        );
+    }
+
+    #[test]
+    fn test_uptime_order() {
+        let mut agents = vec![
+            agent("main", 0, 0, AgentStatus::Idle),
+            agent("main", 0, 1, AgentStatus::Idle),
+            agent("main", 0, 2, AgentStatus::Idle),
+        ];
+        agents[1].started_at -= Duration::from_secs(600);
+        agents[2].started_at -= Duration::from_secs(60);
+        sort_agents(&mut agents, SortMode::Uptime);
+        assert_eq!(targets(&agents), vec!["main:0.1", "main:0.2", "main:0.0"]);
+    }
+
+    #[test]
+    fn test_resort_keeps_selection_on_same_agents() {
+        let mut state = AppState::new();
+        state.agents.root_agents = mixed();
+        state.selected_index = 6; // gamma:0.0
+        state.selected_agents.insert(0); // alpha:0.0
+        state.selected_agents.insert(5); // beta:1.0
+
+        state.sort_mode = SortMode::Attention;
+        apply_sort(&mut state);
+
+        let agents = &state.agents.root_agents;
+        assert_eq!(agents[state.selected_index].target, "gamma:0.0");
+        let mut selected: Vec<&str> = state
+            .selected_agents
+            .iter()
+            .map(|&idx| agents[idx].target.as_str())
+            .collect();
+        selected.sort();
+        assert_eq!(selected, vec!["alpha:0.0", "beta:1.0"]);
+    }
+
+    #[test]
+    fn test_sort_mode_cycles() {
+        assert_eq!(SortMode::Target.next(), SortMode::Attention);
+        assert_eq!(SortMode::Attention.next(), SortMode::Uptime);
+        assert_eq!(SortMode::Uptime.next(), SortMode::Target);
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+/// Default time each spinner frame is shown
+pub const DEFAULT_SPINNER_TICK_MS: u64 = 80;
+
+const BRAILLE_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_FRAMES: &[&str] = &["|", "/", "-", "\\"];
+const DOTS_FRAMES: &[&str] = &[".  ", ".. ", "...", " ..", "  .", "   "];
+
+/// `spinner` in the `[ui]` config section: the frames shown for working agents.
+/// Terminals with poor braille support can use "ascii".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpinnerStyle {
+    #[default]
+    Braille,
+    Ascii,
+    Dots,
+}
+
+impl SpinnerStyle {
+    fn frames(self) -> &'static [&'static str] {
+        match self {
+            SpinnerStyle::Braille => BRAILLE_FRAMES,
+            SpinnerStyle::Ascii => ASCII_FRAMES,
+            SpinnerStyle::Dots => DOTS_FRAMES,
+        }
+    }
+}
+
+/// Picks the spinner frame from the time since start, so the animation speed
+/// doesn't depend on how often the UI redraws
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    style: SpinnerStyle,
+    interval: Duration,
+    started: Instant,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new(
+            SpinnerStyle::default(),
+            Duration::from_millis(DEFAULT_SPINNER_TICK_MS),
+        )
+    }
+}
+
+impl Spinner {
+    /// A spinner showing each frame of `style` for `interval` (at least 1ms)
+    pub fn new(style: SpinnerStyle, interval: Duration) -> Self {
+        Self {
+            style,
+            interval: interval.max(Duration::from_millis(1)),
+            started: Instant::now(),
+        }
+    }
+
+    /// The frame to draw at `now`
+    pub fn frame(&self, now: Instant) -> &'static str {
+        let frames = self.style.frames();
+        let ticks = now.duration_since(self.started).as_millis() / self.interval.as_millis();
+        frames[(ticks % frames.len() as u128) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_advance_with_interval() {
+        let spinner = Spinner::new(SpinnerStyle::Ascii, Duration::from_millis(100));
+        let at = |ms| spinner.frame(spinner.started + Duration::from_millis(ms));
+        assert_eq!(at(0), "|");
+        assert_eq!(at(99), "|");
+        assert_eq!(at(100), "/");
+        assert_eq!(at(350), "\\");
+        // Wraps around after the last frame
+        assert_eq!(at(400), "|");
+    }
+
+    #[test]
+    fn test_default_is_braille() {
+        let spinner = Spinner::default();
+        assert_eq!(spinner.frame(spinner.started), "⠋");
+        assert!(SpinnerStyle::Dots.frames().iter().all(|f| f.is_ascii()));
+    }
+}
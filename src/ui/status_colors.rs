@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Deserializer};
+
+use crate::agents::AgentStatus;
+
+/// `[status_colors]` section of the config: the color of each agent status in the
+/// tree, header and preview. Values are color names ("green"), indexes ("214") or
+/// hex ("#ffaa00").
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct StatusColors {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub idle: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub processing: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub approval: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub error: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub unknown: Color,
+}
+
+impl Default for StatusColors {
+    fn default() -> Self {
+        Self {
+            idle: Color::Green,
+            processing: Color::Yellow,
+            approval: Color::Red,
+            error: Color::Red,
+            unknown: Color::DarkGray,
+        }
+    }
+}
+
+impl StatusColors {
+    /// Color used for `status`
+    pub fn color(&self, status: &AgentStatus) -> Color {
+        match status {
+            AgentStatus::Idle => self.idle,
+            AgentStatus::Processing { .. } => self.processing,
+            AgentStatus::AwaitingApproval { .. } => self.approval,
+            AgentStatus::Error { .. } => self.error,
+            AgentStatus::Unknown => self.unknown,
+        }
+    }
+
+    /// Style for status text; pending approvals are bold so they stand out
+    pub fn style(&self, status: &AgentStatus) -> Style {
+        let style = Style::default().fg(self.color(status));
+        if matches!(status, AgentStatus::AwaitingApproval { .. }) {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        }
+    }
+}
+
+fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Color::from_str(&name).map_err(|_| serde::de::Error::custom(format!("unknown color '{}'", name)))
+}
+
+/// Like `deserialize_color`, for fields that may be left out
+pub(crate) fn deserialize_optional_color<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Color>, D::Error> {
+    deserialize_color(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ApprovalType;
+
+    #[test]
+    fn test_default_colors() {
+        let colors = StatusColors::default();
+        assert_eq!(colors.color(&AgentStatus::Idle), Color::Green);
+        assert_eq!(
+            colors.color(&AgentStatus::Processing {
+                activity: String::new()
+            }),
+            Color::Yellow
+        );
+        assert_eq!(colors.color(&AgentStatus::Unknown), Color::DarkGray);
+    }
+
+    #[test]
+    fn test_config_colors_propagate() {
+        let colors: StatusColors =
+            serde_json::from_str(r##"{"idle": "blue", "approval": "#ff8800", "error": "201"}"##)
+                .unwrap();
+        let approval = AgentStatus::AwaitingApproval {
+            approval_type: ApprovalType::ShellCommand,
+            details: String::new(),
+        };
+
+        assert_eq!(colors.color(&AgentStatus::Idle), Color::Blue);
+        assert_eq!(colors.color(&approval), Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(
+            colors.color(&AgentStatus::Error {
+                message: "boom".to_string()
+            }),
+            Color::Indexed(201)
+        );
+        // Unset statuses keep their defaults
+        assert_eq!(colors.color(&AgentStatus::Unknown), Color::DarkGray);
+        assert_eq!(
+            colors.style(&approval),
+            Style::default()
+                .fg(Color::Rgb(0xff, 0x88, 0x00))
+                .add_modifier(Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn test_unknown_color_rejected() {
+        assert!(serde_json::from_str::<StatusColors>(r#"{"idle": "not-a-color"}"#).is_err());
+    }
+}
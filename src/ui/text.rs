@@ -0,0 +1,58 @@
+/// The `width` characters of `line` starting at character `start`, or fewer
+/// where the line ends first. Counts chars rather than bytes, so the result
+/// always falls on char boundaries; wide characters still count as one.
+pub fn char_slice(line: &str, start: usize, width: usize) -> &str {
+    let mut indices = line.char_indices().map(|(i, _)| i);
+    let Some(from) = indices.nth(start) else {
+        return "";
+    };
+    if width == 0 {
+        return "";
+    }
+    let to = line[from..]
+        .char_indices()
+        .nth(width)
+        .map_or(line.len(), |(i, _)| from + i);
+    &line[from..to]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_slice_ascii() {
+        assert_eq!(char_slice("+let x = 1;", 0, usize::MAX), "+let x = 1;");
+        assert_eq!(char_slice("+let x = 1;", 5, usize::MAX), "x = 1;");
+        assert_eq!(char_slice("+let x = 1;", 1, 3), "let");
+        assert_eq!(char_slice("short", 5, 3), "");
+        assert_eq!(char_slice("short", 50, 3), "");
+        assert_eq!(char_slice("short", 0, 0), "");
+        assert_eq!(char_slice("", 0, 5), "");
+    }
+
+    #[test]
+    fn test_char_slice_multibyte() {
+        assert_eq!(char_slice("日本語テキスト", 3, usize::MAX), "テキスト");
+        assert_eq!(char_slice("日本語テキスト", 1, 2), "本語");
+        assert_eq!(char_slice("ok 🚀🔥 done", 3, 2), "🚀🔥");
+        // Combining marks are separate chars but never split a byte sequence
+        assert_eq!(char_slice("e\u{301}x", 1, 1), "\u{301}");
+
+        // Every start and width lands on a boundary
+        for line in [
+            "日本語テキスト",
+            "a🚀b🔥c",
+            "✽ Reading files…",
+            "❯ ⚠ エラー",
+        ] {
+            let len = line.chars().count();
+            for start in 0..=len + 1 {
+                for width in 0..=len + 1 {
+                    let slice = char_slice(line, start, width);
+                    assert_eq!(slice.chars().count(), width.min(len.saturating_sub(start)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::ffi::OsString;
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier, Style},
+};
+
+/// Colors shared by the widgets, plus the switch that turns color off entirely.
+///
+/// With color off (`NO_COLOR` or `no_color` in the config) every color is
+/// dropped from the rendered frame and highlights fall back to reverse video,
+/// so the TUI stays readable on monochrome terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// False when colors are disabled
+    pub color: bool,
+    /// Focused borders and titles
+    pub accent: Color,
+    /// Unfocused borders
+    pub border: Color,
+    /// Secondary text and separators
+    pub muted: Color,
+    /// Row under the cursor
+    pub cursor_bg: Color,
+    /// Multi-selected rows
+    pub selection_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            color: true,
+            accent: Color::Cyan,
+            border: Color::Gray,
+            muted: Color::DarkGray,
+            cursor_bg: Color::Rgb(50, 50, 70),
+            selection_bg: Color::Rgb(35, 35, 50),
+        }
+    }
+}
+
+impl Theme {
+    /// Theme without any color
+    pub fn monochrome() -> Self {
+        Self {
+            color: false,
+            accent: Color::Reset,
+            border: Color::Reset,
+            muted: Color::Reset,
+            cursor_bg: Color::Reset,
+            selection_bg: Color::Reset,
+        }
+    }
+
+    /// Monochrome when `no_color` is set or the `NO_COLOR` env var is non-empty
+    pub fn detect(no_color: bool) -> Self {
+        Self::select(no_color, std::env::var_os("NO_COLOR"))
+    }
+
+    fn select(no_color: bool, env: Option<OsString>) -> Self {
+        // https://no-color.org: any non-empty value disables color
+        if no_color || env.is_some_and(|v| !v.is_empty()) {
+            Self::monochrome()
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Foreground style for `color`; the plain default style when colors are off
+    pub fn fg(&self, color: Color) -> Style {
+        if self.color {
+            Style::default().fg(color)
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Style for a sidebar row under the cursor or in the multi-selection
+    pub fn selection(&self, is_cursor: bool) -> Style {
+        match (self.color, is_cursor) {
+            (true, true) => Style::default().bg(self.cursor_bg),
+            (true, false) => Style::default().bg(self.selection_bg),
+            (false, true) => Style::default().add_modifier(Modifier::REVERSED),
+            (false, false) => Style::default().add_modifier(Modifier::UNDERLINED),
+        }
+    }
+
+    /// Style for a footer button drawn on a `bg` colored background
+    pub fn button(&self, bg: Color) -> Style {
+        if self.color {
+            Style::default().fg(Color::Black).bg(bg)
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED)
+        }
+    }
+
+    /// Drops every color from `buf` when colors are off, keeping bold, reverse and
+    /// the other modifiers. Run once per frame after all widgets have drawn, so
+    /// widgets that still pick their own colors are covered too.
+    pub fn apply(&self, buf: &mut Buffer) {
+        if self.color {
+            return;
+        }
+        for cell in buf.content.iter_mut() {
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+            cell.underline_color = Color::Reset;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_no_color_env() {
+        assert!(Theme::select(false, None).color);
+        assert!(Theme::select(false, Some(OsString::new())).color);
+        assert!(!Theme::select(false, Some(OsString::from("1"))).color);
+        assert!(!Theme::select(true, None).color);
+    }
+
+    #[test]
+    fn test_monochrome_styles_have_no_color() {
+        let theme = Theme::monochrome();
+        assert_eq!(theme.fg(Color::Cyan), Style::default());
+        assert_eq!(theme.button(Color::Green).bg, None);
+        assert!(theme
+            .selection(true)
+            .add_modifier
+            .contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_apply_strips_colors_keeps_modifiers() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf.set_string(
+            0,
+            0,
+            "Y",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        Theme::default().apply(&mut buf);
+        assert_eq!(buf[(0, 0)].bg, Color::Green);
+
+        Theme::monochrome().apply(&mut buf);
+        let cell = &buf[(0, 0)];
+        assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+        assert!(cell.modifier.contains(Modifier::BOLD));
+        assert_eq!(cell.symbol(), "Y");
+    }
+}
@@ -1123,6 +1123,47 @@ pub async fn post_queue_retry(State(app): State<AppState>, Json(body): Json<Valu
     }
 }
 
+/// POST /api/queue/priority — Change a waiting task's priority (1=highest, 5=lowest)
+pub async fn post_queue_priority(
+    State(app): State<AppState>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    let task_id = body.get("task_id").and_then(|v| v.as_str()).unwrap_or("");
+    if task_id.is_empty() {
+        return Json(json!({"error": "task_id required"}));
+    }
+    let priority = match body.get("priority").and_then(|v| v.as_u64()) {
+        Some(p @ 1..=5) => p as u8,
+        _ => return Json(json!({"error": "priority must be 1-5"})),
+    };
+    let mut q = queue::load_queue();
+    if let Some(task) = q.tasks.iter_mut().find(|t| t.id == task_id) {
+        if matches!(
+            task.status,
+            queue::QueueStatus::Pending | queue::QueueStatus::Blocked
+        ) {
+            task.priority = priority;
+        } else {
+            return Json(json!({"error": "can only reprioritize pending/blocked tasks"}));
+        }
+    } else {
+        return Json(json!({"error": "task not found"}));
+    }
+    match queue::save_queue(&q) {
+        Ok(()) => {
+            app.state
+                .event_bus
+                .send(crate::state::events::StateEvent::QueueChanged {
+                    action: "reprioritized".into(),
+                    task_id: task_id.to_string(),
+                    task: String::new(),
+                });
+            Json(json!({"status": "reprioritized", "task_id": task_id, "priority": priority}))
+        }
+        Err(e) => Json(json!({"error": format!("{}", e)})),
+    }
+}
+
 /// GET /api/queue — Task queue (via tools::queue_list)
 pub async fn get_queue(State(app): State<AppState>) -> Json<Value> {
     let result = tools::queue_list(&app, types::QueueListRequest { status: None }).await;
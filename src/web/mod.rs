@@ -46,6 +46,7 @@ pub fn build_router(app: Arc<App>) -> Router {
         .route("/api/queue/done", post(api::post_queue_done))
         .route("/api/queue/delete", post(api::post_queue_delete))
         .route("/api/queue/retry", post(api::post_queue_retry))
+        .route("/api/queue/priority", post(api::post_queue_priority))
         // Enhanced monitoring endpoints
         .route("/api/monitor", get(api::get_monitor))
         .route("/api/pane/{id}/watch", get(api::get_watch))